@@ -73,18 +73,31 @@ export function validateEmail(email) {
   return parts[0].length > 0 && parts[1].includes('.') && parts[1].length > 2;
 }
 
-export function validateProcurementForm(title, description, city, targetAmount, deadlineMs) {
+/**
+ * Validate procurement form fields.
+ * minTarget: minimum target amount (0 only requires a positive amount).
+ * maxDays: furthest allowed deadline in days from now (0 disables the check).
+ */
+export function validateProcurementForm(title, description, city, targetAmount, deadlineMs, minTarget = 0, maxDays = 365) {
   if (wasmReady) {
-    return JSON.parse(wasmModule.validate_procurement_form(title, description, city, targetAmount, deadlineMs));
+    return JSON.parse(wasmModule.validate_procurement_form(title, description, city, targetAmount, deadlineMs, minTarget, maxDays));
   }
   // JS fallback
   const errors = {};
   if (!title.trim()) errors.title = 'Название обязательно';
-  else if (title.length > 200) errors.title = 'Название не должно превышать 200 символов';
+  else if ([...title].length > 200) errors.title = 'Название не должно превышать 200 символов';
   if (!description.trim()) errors.description = 'Описание обязательно';
+  else if ([...description].length > 5000) errors.description = 'Описание не должно превышать 5000 символов';
   if (!city.trim()) errors.city = 'Город обязателен';
   if (targetAmount <= 0) errors.target_amount = 'Целевая сумма должна быть положительной';
-  if (deadlineMs <= Date.now()) errors.deadline = 'Дедлайн должен быть в будущем';
+  else if (minTarget > 0 && targetAmount < minTarget) {
+    errors.target_amount = `Целевая сумма должна быть не меньше ${formatCurrency(minTarget)}`;
+  }
+  const now = Date.now();
+  if (deadlineMs <= now) errors.deadline = 'Дедлайн должен быть в будущем';
+  else if (maxDays > 0 && deadlineMs > now + maxDays * 86400000) {
+    errors.deadline = `Дедлайн не может быть позже чем через ${maxDays} дн.`;
+  }
   return errors;
 }
 
@@ -221,14 +234,22 @@ export function batchProcessProcurements(procurements) {
     const target = p.target_amount || 0;
     const progress = target > 0 ? Math.min(100, Math.max(0, Math.round((current / target) * 100))) : 0;
     let daysLeft = null;
+    let urgency = 'none';
     if (p.deadline) {
       const diff = new Date(p.deadline).getTime() - now;
       daysLeft = Math.max(0, Math.ceil(diff / 86400000));
+      if (!Number.isNaN(diff)) {
+        if (diff < 0) urgency = 'overdue';
+        else if (diff <= 3 * 86400000) urgency = 'soon';
+        else if (diff <= 7 * 86400000) urgency = 'week';
+        else urgency = 'ok';
+      }
     }
     return {
       ...p,
       progress,
       days_left: daysLeft,
+      urgency,
       formatted_current: formatCurrency(current),
       formatted_target: formatCurrency(target),
     };
@@ -237,12 +258,17 @@ export function batchProcessProcurements(procurements) {
 
 /**
  * Fuzzy search procurements by query string.
+ * Options: maxDistance (typo tolerance, 0 disables), offset/limit (page of results,
+ * limit 0 means no limit), translit (also match Latin spellings of Cyrillic words,
+ * "moskva" finds "Москва"), topN (keep only the best N results, 0 keeps all).
  * Returns array of { id, score } sorted by relevance.
  */
-export function searchProcurements(procurements, query) {
+export function searchProcurements(procurements, query, { maxDistance = 2, offset = 0, limit = 0, translit = true, topN = 0 } = {}) {
   if (wasmReady && Array.isArray(procurements) && procurements.length > 0 && query) {
     try {
-      return JSON.parse(wasmModule.search_procurements(JSON.stringify(procurements), query));
+      return JSON.parse(wasmModule.search_procurements(
+        JSON.stringify(procurements), query, maxDistance, offset, limit, translit, topN,
+      ));
     } catch (err) {
       console.warn('[WASM] search_procurements failed, using JS fallback:', err.message);
     }
@@ -263,8 +289,9 @@ export function searchProcurements(procurements, query) {
     }
     if (score > 0) results.push({ id: p.id, score });
   }
-  results.sort((a, b) => b.score - a.score);
-  return results;
+  results.sort((a, b) => b.score - a.score || b.id - a.id);
+  const kept = topN > 0 ? results.slice(0, topN) : results;
+  return kept.slice(offset, limit > 0 ? offset + limit : undefined);
 }
 
 /**
@@ -274,7 +301,7 @@ export function searchProcurements(procurements, query) {
 export function sortProcurements(procurements, sortBy, order = 'asc') {
   if (wasmReady && Array.isArray(procurements) && procurements.length > 0) {
     try {
-      return JSON.parse(wasmModule.sort_procurements(JSON.stringify(procurements), sortBy, order));
+      return JSON.parse(wasmModule.sort_procurements(JSON.stringify(procurements), sortBy, order, 'created', 0, 0));
     } catch (err) {
       console.warn('[WASM] sort_procurements failed, using JS fallback:', err.message);
     }
@@ -341,7 +368,7 @@ export function aggregateProcurementStats(procurements) {
 export function batchProcessMessages(messages, currentUserId) {
   if (wasmReady && Array.isArray(messages) && messages.length > 0) {
     try {
-      return JSON.parse(wasmModule.batch_process_messages(JSON.stringify(messages), BigInt(currentUserId || 0)));
+      return JSON.parse(wasmModule.batch_process_messages(JSON.stringify(messages), BigInt(currentUserId || 0), -new Date().getTimezoneOffset()));
     } catch (err) {
       console.warn('[WASM] batch_process_messages failed, using JS fallback:', err.message);
     }
@@ -380,12 +407,13 @@ export function searchMessages(messages, query) {
 }
 
 /**
- * Run WASM performance benchmark.
- * Returns elapsed time in ms or null if WASM is not available.
+ * Run WASM performance benchmark on a dataset generated from `seed`.
+ * Returns per-step timings in ms ({ generate_ms, batch_ms, search_ms, sort_ms,
+ * aggregate_ms, total_ms }) or null if WASM is not available.
  */
-export function runBenchmark(count = 1000) {
+export function runBenchmark(count = 1000, seed = 1) {
   if (wasmReady) {
-    return wasmModule.benchmark_batch_processing(count);
+    return JSON.parse(wasmModule.benchmark_batch_processing(count, seed));
   }
   return null;
 }
@@ -1,65 +1,301 @@
 /* tslint:disable */
 /* eslint-disable */
 
+/**
+ * Token-bucket rate limiter for throttling client actions (message send, search)
+ * Holds up to `capacity` tokens, refilled continuously at `refill_per_sec`;
+ * each allowed action spends one token. Refill is computed lazily on each call
+ */
+export class RateLimiter {
+    free(): void;
+    [Symbol.dispose](): void;
+    /**
+     * Create a limiter that starts full
+     */
+    constructor(capacity: number, refill_per_sec: number);
+    /**
+     * Tokens currently available (fractional while refilling)
+     */
+    tokens_remaining(): number;
+    /**
+     * Spend one token if available; returns false when the action should be throttled
+     */
+    try_acquire(): boolean;
+}
+
 /**
  * Aggregate procurement statistics from a JSON array
  * Returns JSON object with: total_count, active_count, total_amount, total_target,
- * overall_progress, avg_participants, cities (unique), by_status counts
+ * overall_progress, avg_participants, cities (unique), by_status counts,
+ * median_target, median_participants, p90_target,
+ * by_city / by_category ({name: {count, total_amount}}, missing names under "unknown";
+ * categories are merged by `canonicalize_category`),
+ * completed_count, funded_count (reached a positive target), funding_rate (% of total_count),
+ * overfunded_count (raised more than a positive target), overfunded_total (sum of the excess)
+ * Legacy string API: prefer `aggregate_procurement_stats_js`, which returns a JS object directly
  */
 export function aggregate_procurement_stats(json_input: string): string;
 
+/**
+ * `aggregate_procurement_stats` reporting malformed input instead of returning "{}"
+ * Returns JSON: { "ok": true, "data": {...} } or { "ok": false, "error": "..." }
+ */
+export function aggregate_procurement_stats_checked(json_input: string): string;
+
+/**
+ * Aggregate procurement statistics, returned as a plain JS object
+ * Same fields as `aggregate_procurement_stats` (numbers as JS numbers, maps as objects);
+ * invalid input yields an empty object
+ */
+export function aggregate_procurement_stats_js(json_input: string): any;
+
+/**
+ * Avatar initials and colors in one call
+ * Initials are those of `get_initials`, bg is `get_avatar_color_themed` of the first
+ * name, fg is "#000000" or "#ffffff", whichever contrasts more with bg
+ * Returns JSON: { "initials": "ИП", "bg": "#rrggbb", "fg": "#rrggbb" }
+ */
+export function avatar_spec(first: string, last: string, theme: string): string;
+
+/**
+ * Decode base64url (padded or not) into bytes; invalid input gives an empty array
+ */
+export function base64url_decode(s: string): Uint8Array;
+
+/**
+ * Encode bytes as unpadded base64url (safe in URLs and Telegram start params)
+ */
+export function base64url_encode(data: Uint8Array): string;
+
 /**
  * Batch-process messages: format text, compute date groups, format times
- * Input: JSON array of messages, current user ID
+ * Input: JSON array of messages, current user ID, timezone offset in minutes east of UTC
+ * (0 = UTC, 180 = Moscow; pass `-new Date().getTimezoneOffset()` for browser local time)
  * Output: JSON array with formatted fields
  */
-export function batch_process_messages(json_input: string, current_user_id: bigint): string;
+export function batch_process_messages(json_input: string, current_user_id: bigint, tz_offset_minutes: number): string;
+
+/**
+ * `batch_process_messages` reporting malformed input instead of returning "[]"
+ * Returns JSON: { "ok": true, "data": [...] } or { "ok": false, "error": "..." }
+ */
+export function batch_process_messages_checked(json_input: string, current_user_id: bigint, tz_offset_minutes: number): string;
 
 /**
  * Batch-process procurements: compute progress, days left, and format currency in one pass
  * Input: JSON array of procurements
- * Output: JSON array with computed fields added
+ * Output: JSON array with computed fields added (including `urgency`: "overdue", "soon"
+ * within 3 days, "week" within 7, "ok", or "none" without a deadline)
  */
 export function batch_process_procurements(json_input: string): string;
 
+/**
+ * `batch_process_procurements` reporting malformed input instead of returning "[]"
+ * Returns JSON: { "ok": true, "data": [...] } or { "ok": false, "error": "..." }
+ */
+export function batch_process_procurements_checked(json_input: string): string;
+
+/**
+ * Batch-process one window of procurements, for requesting large catalogs progressively
+ * Output matches the `[offset, offset + limit)` slice of `batch_process_procurements`;
+ * `limit <= 0` means "to the end" and offsets past the end yield "[]".
+ * The whole input is re-parsed on every call, so this only bounds the work and
+ * output size per call; for real streaming use `batch_process_procurements_msgpack`
+ */
+export function batch_process_procurements_chunk(json_input: string, offset: number, limit: number): string;
+
+/**
+ * Batch-process procurements with MessagePack input and output (`Uint8Array` in JS)
+ * The schema is identical to `batch_process_procurements`: an array of procurement
+ * maps in, an array of maps with the same computed fields out (field names as keys)
+ */
+export function batch_process_procurements_msgpack(input: Uint8Array): Uint8Array;
+
 /**
  * Run a performance benchmark for batch processing
- * Generates N random procurements and processes them, returning elapsed time in ms
+ * Generates N procurements from `seed` (same seed, same dataset) and runs batch
+ * processing, search, sort and aggregation on them
+ * Returns JSON with elapsed ms per step:
+ * { generate_ms, batch_ms, search_ms, sort_ms, aggregate_ms, total_ms }
+ */
+export function benchmark_batch_processing(count: number, seed: number): string;
+
+/**
+ * Build the payment QR payload for a procurement contribution
+ * Canonical query string with keys in alphabetical order:
+ * `amount=<2 decimals>&currency=<upper-case>&procurement_id=<id>&purpose=<percent-encoded>`
+ * Returns "" for a non-positive or non-finite amount
+ */
+export function build_payment_payload(procurement_id: bigint, amount: number, currency: string, purpose: string): string;
+
+/**
+ * Build a Telegram deep link that opens a procurement in the bot
+ * Returns `https://t.me/<bot>?start=<payload>` where the payload is `proc_<id>`
+ * base64url-encoded (a leading '@' in the bot username is ignored)
  */
-export function benchmark_batch_processing(count: number): number;
+export function build_share_link(bot_username: string, procurement_id: bigint): string;
 
 /**
- * Calculate procurement progress percentage
+ * Calculate procurement progress percentage, rounded to the nearest whole percent
+ * and clamped to [0, 100] (99.6% shows as 100, over-funding as 100)
  */
 export function calculate_progress(current_amount: number, target_amount: number): number;
 
+/**
+ * Calculate unrounded procurement progress percentage, clamped to [0, 100]
+ * (for progress bars with sub-percent precision)
+ */
+export function calculate_progress_f64(current_amount: number, target_amount: number): number;
+
+/**
+ * Calculate procurement progress percentage without the 100% cap (e.g. 150 for
+ * an over-funded procurement), rounded and floored at 0
+ */
+export function calculate_progress_uncapped(current_amount: number, target_amount: number): number;
+
+/**
+ * Map a free-text category to its canonical Russian label
+ * Known synonyms ("электроника", "Electronics", "elektronika") map to the catalog label
+ * ("Электроника"), with exact synonyms taking precedence over transliterations;
+ * unknown names are trimmed, whitespace-collapsed and title-cased ("ручная работа" ->
+ * "Ручная Работа"). Blank input gives ""
+ */
+export function canonicalize_category(raw: string): string;
+
+/**
+ * Mask profanity in text, keeping the first letter of each matched word (e.g. "f***")
+ * Words are matched whole (or by stem) after undoing simple substitutions such as
+ * `@` -> a, `0` -> o and Latin look-alikes in Cyrillic words. Best-effort only
+ */
+export function censor_profanity(text: string): string;
+
+/**
+ * Check whether text contains profanity (same matching as `censor_profanity`)
+ */
+export function contains_profanity(text: string): boolean;
+
+/**
+ * WCAG contrast ratio of two `#RRGGBB` colors, from 1.0 (identical) to 21.0 (black on white)
+ * Order of the arguments does not matter; invalid hex gives 0.0
+ */
+export function contrast_ratio(hex_a: string, hex_b: string): number;
+
 /**
  * Calculate days remaining until deadline
  */
 export function days_until(deadline_ms: number): number;
 
+/**
+ * Deadline phrase with a color hint for procurement cards
+ * Returns JSON: {label, days, urgency, color} where label is "осталось N дней" with
+ * Russian plural forms ("остался 1 день", "осталось 2 дня", whole days rounded down),
+ * "осталось менее дня" under a day, "Завершено" once the deadline has passed (days is
+ * then negative) or "Без срока" for a missing (NaN / infinite) deadline (days is null).
+ * urgency is the `urgency` bucket of `batch_process_procurements`
+ */
+export function deadline_phrase(deadline_ms: number): string;
+
+/**
+ * Compare two procurement lists by id for incremental UI updates
+ * "changed" lists ids present in both whose title, status, current_amount,
+ * target_amount, participant_count or deadline differ
+ * Returns JSON: { "added": [ids], "removed": [ids], "changed": [ids] }
+ * (added/changed in new-list order, removed in old-list order)
+ */
+export function diff_procurements(old_json: string, new_json: string): string;
+
 /**
  * Escape HTML to prevent XSS
  */
 export function escape_html(text: string): string;
 
 /**
- * Format currency amount (Russian rubles)
+ * Filter procurements by a JSON filter object
+ * Filters: status (exact), city and category (case-insensitive), min_amount / max_amount
+ * (current amount, inclusive), min_progress / max_progress (percent as in
+ * `batch_process_procurements`, inclusive). All provided filters must match.
+ * Returns JSON array of matching procurement IDs in input order
+ */
+export function filter_procurements(json_input: string, filters_json: string): string;
+
+/**
+ * Find procurements sharing a `procurement_fingerprint` (title, city, organizer, target)
+ * Returns JSON array of id groups, e.g. [[1, 4], [2, 7, 9]]: ids ascending within a
+ * group, groups ordered by their first id; procurements without a duplicate are omitted
+ */
+export function find_duplicate_procurements(json_input: string): string;
+
+/**
+ * Strip diacritics: "café" -> "cafe", "naïve" -> "naive"
+ * Text is NFC-composed, each character reduced to the base of its canonical
+ * decomposition, and leftover combining marks dropped. "й" is a letter of its own
+ * and kept; "ё" is folded to "е" only when `fold_yo` is set
+ */
+export function fold_diacritics(text: string, fold_yo: boolean): string;
+
+/**
+ * Format a number with the matching Russian plural form, e.g. "5 мест"
+ * forms_json: JSON array of three forms [one, few, many], e.g. ["место", "места", "мест"]
+ * Invalid forms give just the number
+ */
+export function format_count_ru(n: number, forms_json: string): string;
+
+/**
+ * Format currency amount (Russian rubles; shortcut for `format_money(amount, "RUB")`)
+ * Negative amounts get a single leading ASCII '-' (e.g. "-1 234,50 ₽")
  */
 export function format_currency(amount: number): string;
 
 /**
- * Format message text: escape HTML, convert URLs to links, convert newlines to <br>
+ * Format a file size with 1024-based Russian units (Б, КБ, МБ, ГБ)
+ * Bytes have no decimals, larger units one decimal with a comma (e.g. "3,4 МБ").
+ * Negative or non-finite (NaN, infinite) input gives "0 Б"
+ */
+export function format_file_size(bytes: number): string;
+
+/**
+ * Format message text: escape HTML, convert URLs, @mentions and #hashtags to links,
+ * render *bold* / _italic_, convert newlines to <br>
  */
 export function format_message_text(text: string): string;
 
 /**
- * Format relative time in Russian
+ * Format a money amount for a currency code: RUB (1 234,50 ₽), KZT (1 234,50 ₸),
+ * BYN (1 234,50 Br), EUR (1 234,50 €) and USD ($1,234.50); other codes are shown
+ * after the amount in the ruble layout. Kopecks/cents are omitted when zero
+ */
+export function format_money(amount: number, currency: string): string;
+
+/**
+ * Format a Russian ordinal number with its gender ending: "1-й", "1-я", "1-е"
+ * gender: "m" / "masculine" (участник), "f" / "feminine" (закупка),
+ * "n" / "neuter" (место); anything else is treated as masculine
+ */
+export function format_ordinal_ru(n: number, gender: string): string;
+
+/**
+ * Format phone number for display
+ * Russian numbers (11 digits starting with 7 or 8) become `+7 (XXX) XXX-XX-XX`,
+ * anything else is returned as `+` followed by the cleaned digits
+ */
+export function format_phone(phone: string): string;
+
+/**
+ * Format relative time in Russian, up to weeks, months and years
+ * Future timestamps within a minute (clock skew) read "только что",
+ * further ones are phrased forward ("через N мин.")
  */
 export function format_relative_time(timestamp_ms: number): string;
 
+/**
+ * Format relative time in Russian using full words with correct plural forms
+ * (e.g. "1 минуту назад", "3 часа назад", "25 дней назад")
+ */
+export function format_relative_time_full(timestamp_ms: number): string;
+
 /**
  * Generate unique platform user ID for websocket users
+ * Format: `web_` followed by a random (version 4) UUID
  */
 export function generate_platform_user_id(): string;
 
@@ -68,30 +304,342 @@ export function generate_platform_user_id(): string;
  */
 export function get_avatar_color(name: string): string;
 
+/**
+ * Generate avatar background color for a theme ("light" or "dark")
+ * The palette slot depends only on the name, so a user keeps the same hue
+ * when switching themes; unknown themes fall back to the dark palette
+ */
+export function get_avatar_color_themed(name: string, theme: string): string;
+
 /**
  * Get initials from first name and last name
+ * Takes the first grapheme of each name, so flags and combining sequences stay intact
  */
 export function get_initials(first_name: string, last_name: string): string;
 
+/**
+ * Get two-character initials from first name and last name
+ * When the last name is empty, the first two graphemes of the first name are used
+ * ("Александр" -> "АЛ"); emoji and combining sequences count as one character
+ */
+export function get_initials_v2(first_name: string, last_name: string): string;
+
+/**
+ * Gzip-compress a string (e.g. procurement JSON before caching it in IndexedDB)
+ * Returns the gzip bytes (`Uint8Array` in JS)
+ */
+export function gzip_compress(data: string): Uint8Array;
+
+/**
+ * Decompress gzip bytes produced by `gzip_compress` back into a string
+ * Invalid, truncated or non-UTF-8 data gives ""
+ */
+export function gzip_decompress(data: Uint8Array): string;
+
+/**
+ * HTML-escape text and wrap case-insensitive occurrences of each query word in <mark>
+ * Matching runs on the raw text, so entities such as `&amp;` are never split;
+ * overlapping or adjacent matches are merged into a single <mark>
+ */
+export function highlight_terms(text: string, query: string): string;
+
+/**
+ * Check whether an email uses a known disposable (throwaway) provider
+ * Case-insensitive; subdomains of listed providers also count
+ */
+export function is_disposable_email(email: string): boolean;
+
+/**
+ * Levenshtein edit distance between two strings, counted in characters (not bytes)
+ */
+export function levenshtein(a: string, b: string): number;
+
+/**
+ * Apply a patch to a procurement list, matching objects by `id`
+ * Base objects with an id in the patch are replaced in place, patch objects with new
+ * ids are appended in patch order, and everything else is kept untouched. Objects are
+ * copied as-is, so fields unknown to this crate survive; patch items without a numeric
+ * id are ignored (for a repeated id the last patch object wins)
+ * Returns the merged JSON array ([] if either input is invalid)
+ */
+export function merge_procurements(base_json: string, patch_json: string): string;
+
+/**
+ * Message length as users perceive it: grapheme clusters, so a compound emoji
+ * (family, flag, skin tone) counts as 1
+ */
+export function message_length(text: string): number;
+
+/**
+ * Check that a message fits within `max` graphemes (see `message_length`)
+ */
+export function message_length_ok(text: string, max: number): boolean;
+
+/**
+ * One-line preview of a message for chat list rows
+ * Markup is stripped and whitespace (including newlines) collapsed as in
+ * `message_text_to_plain`, then the text is cut to `max_chars` graphemes with "…"
+ * (see `truncate_text`). System messages are free-form text and are previewed as is;
+ * image and file messages without text become "Фото" / "Файл"
+ */
+export function message_preview(text: string, max_chars: number, message_type: string): string;
+
+/**
+ * Heuristic spam score of a chat message, from 0.0 (normal) to 1.0 (spam)
+ * Weighted sum of saturating features:
+ * - links: 0.4 * min(urls / 3, 1)
+ * - shouting: 0.25 * share of uppercase letters (messages with 8+ letters only)
+ * - repeated characters: 0.15 * min(chars in runs of 4+ identical chars / 10, 1)
+ * - promo keywords: 0.2 * min(words starting with a keyword / 2, 1)
+ *
+ * Each feature only grows with its input, so e.g. more links never lower the score
+ */
+export function message_spam_score(text: string): number;
+
+/**
+ * Convert message text to a single line of plain text (for notifications and search)
+ * Strips *bold* / _italic_ markers, keeps URLs literal, collapses whitespace and newlines
+ */
+export function message_text_to_plain(text: string): string;
+
+/**
+ * Count messages per local calendar day
+ * Input: JSON array of messages, timezone offset in minutes east of UTC (as in `batch_process_messages`)
+ * Output: JSON array of `{date: "YYYY-MM-DD", label, count}` in chronological order;
+ * messages without a parseable `created_at` are counted in a trailing `date: null` bucket
+ */
+export function messages_by_date(json_input: string, tz_offset_minutes: number): string;
+
+/**
+ * Normalize a phone number to E.164 storage form (`+` followed by digits, no spaces)
+ * A Russian trunk prefix is replaced (8XXXXXXXXXX -> +7XXXXXXXXXX); numbers without
+ * `+` are otherwise read as already including the country code. Returns "" when the
+ * result would not pass `validate_phone` or the input has no digits
+ */
+export function normalize_phone_e164(phone: string): string;
+
+/**
+ * Normalize a username for storage and comparison (trimmed, lowercase)
+ */
+export function normalize_username(name: string): string;
+
+/**
+ * Parse a deadline from a form value to milliseconds since the epoch
+ * Accepts `YYYY-MM-DD` (end of that day, 23:59:59.999 UTC) or a full ISO timestamp.
+ * Returns NaN for unparseable input
+ */
+export function parse_deadline(iso: string): number;
+
+/**
+ * Parse procurements from CSV (the `procurements_to_csv` format, header row required)
+ * Columns are matched by header name, so extra columns (e.g. description) are allowed
+ * and missing ones read as empty. Quoted fields and CRLF line endings are supported.
+ * Each row is checked with the procurement form rules (min_target and max_days disabled;
+ * description only when the column is present); non-empty id, current_amount and
+ * participant_count cells that aren't numbers are errors too, empty ones read as null.
+ * Returns JSON: { "procurements": [...], "errors": [...] } with one entry per data row
+ * in both arrays; a row with the wrong column count yields a null procurement and a
+ * "row" error, valid rows an empty error object
+ */
+export function parse_procurements_csv(csv: string): string;
+
+/**
+ * Decode a /start payload produced by `build_share_link`
+ * Returns the procurement ID, or -1 if the payload is malformed
+ */
+export function parse_start_param(param: string): bigint;
+
+/**
+ * Estimate password strength for a live registration meter
+ * score (0-4): +1 for 8+ chars, +1 for 12+ chars, +1 for 3+ character classes,
+ * +1 for all 4 classes; -1 for a run of 3+ repeated or sequential characters
+ * ("aaa", "abc", "321"); common passwords always score 0.
+ * The password is only inspected, never stored or logged
+ * Returns JSON: { score, length_ok, has_upper, has_lower, has_digit, has_symbol, suggestions }
+ */
+export function password_strength(pw: string): string;
+
+/**
+ * Text color for a background: "#000000" or "#ffffff", whichever contrasts more
+ * (black for an invalid color)
+ */
+export function pick_readable_text(bg_hex: string): string;
+
+/**
+ * Fingerprint of a procurement for duplicate detection: hex SHA-256 of the lowercased,
+ * whitespace-collapsed title, city and organizer plus the target rounded to kopecks
+ * Differences only in case or spacing give the same fingerprint
+ */
+export function procurement_fingerprint(title: string, city: string, organizer: string, target: number): string;
+
+/**
+ * Procurement "health" from 0 to 100 for an at-a-glance indicator
+ * health = 60 * pace + 25 * funded + 15 * momentum, clamped to [0, 100], where
+ * - funded: current / target, clamped to [0, 1] (reaching the target scores 100 overall)
+ * - expected: share of the created..deadline window already elapsed, in [0, 1]
+ *   (0 if either timestamp is missing/NaN)
+ * - pace: 1 + 2 * (funded - expected), clamped to [0, 1] — full when at or ahead
+ *   of a linear funding pace, zero when half the target behind it
+ * - momentum: participants per elapsed day (at least one day), capped at 1 per day
+ */
+export function procurement_health(current: number, target: number, deadline_ms: number, participants: number, created_ms: number): number;
+
+/**
+ * Export procurements as CSV (RFC 4180: comma-separated, CRLF line endings)
+ * Columns: id, title, city, status, current_amount, target_amount, progress,
+ * participant_count, deadline. Amounts are plain numbers; missing values are empty
+ */
+export function procurements_to_csv(json_input: string): string;
+
+/**
+ * Search, filter, sort and paginate procurements in one call (a list view)
+ * Params JSON: { query, filters, sort_by, order, offset, limit }, all optional.
+ * A non-blank query keeps only matching procurements (as `search_procurements`, exact
+ * matching) and, without sort_by, orders them by relevance; filters are those of
+ * `filter_procurements`; sort_by/order are those of `sort_procurements`, otherwise input
+ * order is kept. total counts matches before pagination
+ * Returns JSON: { "total": n, "ids": [...] }
+ */
+export function query_procurements(json_input: string, params_json: string): string;
+
+/**
+ * Find procurements similar to the one with `target_id`
+ * Score: 6 for the same category, 2 for the same city, plus up to 3 for title
+ * overlap (Jaccard similarity of title words, normalized as in search), so a shared
+ * category always outranks a shared city alone
+ * limit <= 0 means no limit
+ * Returns JSON array of {id, score} sorted by score (ties by id), without the target;
+ * [] if the target isn't in the list
+ */
+export function related_procurements(json_input: string, target_id: bigint, limit: number): string;
+
+/**
+ * Render a procurement description written in a small Markdown subset to safe HTML
+ * Blocks: "# " / "## " headings (rendered as <h2> / <h3>, below the page title),
+ * "- " bullet lists, and paragraphs separated by blank lines (single newlines become <br>).
+ * Inline: **bold** (or *bold* as in chat), _italic_, and autolinked http(s) URLs.
+ * All text is HTML-escaped first; raw HTML, images and other Markdown stay literal text
+ */
+export function render_description_md(md: string): string;
+
 /**
  * Search within messages by text content
  * Returns JSON array of matching message indices
  */
 export function search_messages(json_input: string, query: string): string;
 
+/**
+ * Search messages sent by one user, optionally narrowed by text
+ * Returns JSON array of matching message indices; an empty query matches every
+ * message of the user. System messages never match
+ */
+export function search_messages_by_user(json_input: string, user_id: bigint, query: string): string;
+
+/**
+ * Search messages sent within a time window, optionally narrowed by text
+ * Returns JSON array of indices of messages whose `created_at` falls in
+ * `[start_ms, end_ms]` (inclusive); messages without a parseable date are skipped.
+ * An inverted or NaN window yields "[]"
+ */
+export function search_messages_in_range(json_input: string, start_ms: number, end_ms: number, query: string): string;
+
 /**
  * Fuzzy search procurements by query string
- * Returns JSON array of matching procurement IDs with relevance scores, sorted by relevance
+ * max_distance: Levenshtein distance up to which a query word still matches a title
+ * word when it isn't a substring of the title (0 disables fuzzy matching, 2 is typical);
+ * capped at a third of the query word's length
+ * Matching ignores case and diacritics, and treats "ё" as "е" ("cafe" finds "Café");
+ * "й" stays distinct from "и". Case folding is Unicode's default (locale-specific
+ * rules such as Turkish dotted/dotless I are out of scope)
+ * Scoring weights are described on `procurement_match_score`
+ * offset/limit: page of results to return (limit <= 0 means no limit)
+ * translit: also match Cyrillic and Latin spellings of the same word ("moskva" / "москва")
+ * top_n: when > 0, only the best top_n results are kept (selected with a bounded heap
+ * instead of sorting every match); offset/limit then page within them
+ * Returns JSON array of matching procurement IDs with relevance scores, sorted by relevance;
+ * equal scores are ordered by descending id (newer first), so output is reproducible
+ */
+export function search_procurements(json_input: string, query: string, max_distance: number, offset: number, limit: number, translit: boolean, top_n: number): string;
+
+/**
+ * `search_procurements` reporting malformed input instead of returning "[]"
+ * Returns JSON: { "ok": true, "data": [...] } or { "ok": false, "error": "..." }
+ */
+export function search_procurements_checked(json_input: string, query: string, max_distance: number, offset: number, limit: number, translit: boolean, top_n: number): string;
+
+/**
+ * Search procurements like `search_procurements` (exact matching, no typo tolerance)
+ * and report where query words matched in the title
+ * Returns JSON array of {id, score, title_ranges}, where title_ranges is a list of
+ * `[start, end)` pairs in char (Unicode scalar) offsets into the title, sorted and merged
  */
-export function search_procurements(json_input: string, query: string): string;
+export function search_procurements_highlighted(json_input: string, query: string): string;
+
+/**
+ * Build a URL slug from text (e.g. a procurement title)
+ * Lowercases, transliterates Cyrillic to Latin, replaces runs of other characters
+ * with a single hyphen and trims hyphens at the ends. Capped at 80 characters
+ * without cutting a transliterated letter in half
+ */
+export function slugify(text: string): string;
 
 /**
  * Sort procurements by a specified field
- * sort_by: "title", "amount", "progress", "deadline", "participants", "created"
+ * sort_by: "title", "amount", "progress", "deadline", "participants", "created", "urgency"
  * order: "asc" or "desc"
+ * then_by: tiebreak field from the same list ("created" orders ties newest first,
+ * other fields ascending; "created" is the recommended default). Remaining ties,
+ * or all ties when then_by is empty, are ordered by id ascending.
+ * "deadline" and "created" are compared as parsed timestamps; missing or
+ * unparseable values sort last in either order.
+ * "urgency" puts upcoming deadlines first (soonest first for "asc"), then overdue
+ * ones, then procurements without a (parseable) deadline; these groups keep their
+ * position regardless of order, which only applies within a group.
+ * offset/limit: page of results to return (limit <= 0 means no limit)
  * Returns JSON array of sorted procurement IDs
  */
-export function sort_procurements(json_input: string, sort_by: string, order: string): string;
+export function sort_procurements(json_input: string, sort_by: string, order: string, then_by: string, offset: number, limit: number): string;
+
+/**
+ * `sort_procurements` reporting malformed input instead of returning "[]"
+ * Returns JSON: { "ok": true, "data": [ids] } or { "ok": false, "error": "..." }
+ */
+export function sort_procurements_checked(json_input: string, sort_by: string, order: string, then_by: string, offset: number, limit: number): string;
+
+/**
+ * Character count, word count and estimated reading time of a text
+ * chars counts graphemes, words are Unicode word segments; reading_minutes is
+ * rounded up at ~180 wpm and at least 1 for non-empty text
+ * Returns JSON: { "chars": n, "words": n, "reading_minutes": n }
+ */
+export function text_stats(text: string): string;
+
+/**
+ * Short label for the time left until a deadline
+ * "Завершено" once the deadline has passed, "N часов" under a day (hours rounded up,
+ * so never "0 часов"), otherwise "N дней" (whole days, as in `days_until`), with
+ * Russian plural forms ("1 час", "3 часа", "21 день", "2 дня")
+ */
+export function time_until_label(deadline_ms: number): string;
+
+/**
+ * Truncate text to at most `max_chars` graphemes, ending with "…" when cut
+ * The ellipsis counts toward the limit; trailing whitespace before it is dropped.
+ * Text that already fits is returned unchanged; `max_chars <= 0` gives ""
+ */
+export function truncate_text(text: string, max_chars: number): string;
+
+/**
+ * Validate an attachment's MIME type (case-insensitive, parameters after ';' ignored) and size
+ * Returns "" if valid, otherwise an error message
+ */
+export function validate_attachment(mime: string, size_bytes: number): string;
+
+/**
+ * Validate a deadline string from a form (see `parse_deadline` for accepted formats)
+ * Returns "" if valid, otherwise an error message
+ */
+export function validate_deadline_string(iso: string): string;
 
 /**
  * Validate email format
@@ -99,39 +647,175 @@ export function sort_procurements(json_input: string, sort_by: string, order: st
 export function validate_email(email: string): boolean;
 
 /**
- * Validate phone number format (Russian phone number)
+ * Validate email format strictly (for required fields such as organizer accounts)
+ * A common-sense subset of RFC 5322: no spaces, one '@', no leading/trailing or
+ * consecutive dots in the local part, and a dotted domain of letter/digit/hyphen
+ * labels ending in an alphabetic TLD of at least 2 characters
+ */
+export function validate_email_strict(email: string): boolean;
+
+/**
+ * Validate a Russian INN (taxpayer ID) including its check digits
+ * 10 digits: legal entity (one check digit); 12 digits: individual or sole
+ * proprietor (two check digits). Anything else, including non-digits, is invalid
+ */
+export function validate_inn(inn: string): boolean;
+
+/**
+ * Validate phone number format (international, with per-country length checks for CIS codes)
  */
 export function validate_phone(phone: string): boolean;
 
 /**
  * Validate procurement form data
+ * min_target: minimum allowed target amount (0.0 only requires a positive amount)
+ * max_days: furthest allowed deadline in days from now (365 recommended, 0 disables the check)
  * Returns JSON string with validation errors (empty object if valid)
  */
-export function validate_procurement_form(title: string, description: string, city: string, target_amount: number, deadline_ms: number): string;
+export function validate_procurement_form(title: string, description: string, city: string, target_amount: number, deadline_ms: number, min_target: number, max_days: number): string;
+
+/**
+ * Validate several procurement forms in one call
+ * Input: JSON array of objects with the `validate_procurement_form` parameters as fields
+ * (missing fields count as empty / 0). Returns a JSON array of error objects in input
+ * order (empty object where valid); an item that isn't a form object gets a "form" error
+ */
+export function validate_procurement_forms(json_input: string): string;
+
+/**
+ * Check procurement JSON against the shape the batch functions expect, before processing
+ * Reports missing required fields (id, title) and wrong types (e.g. target_amount as a
+ * string); unknown fields are ignored. An empty array means the input is valid
+ * Returns JSON array of { "index": n, "field": "...", "error": "..." }; input that is
+ * not a JSON array gives a single entry with null index and field
+ */
+export function validate_procurement_json(json_input: string): string;
+
+/**
+ * Validate a username: 3-32 chars of `[A-Za-z0-9_]`, starting with a letter,
+ * no consecutive underscores, not reserved
+ * Returns "" if valid, otherwise an error message
+ */
+export function validate_username(name: string): string;
+
+/**
+ * Verify a hex HMAC-SHA256 signature of a payload (e.g. a webhook `X-Signature` header)
+ * Hex may be upper- or lowercase; the comparison is constant-time.
+ * Returns false for malformed or wrong-length signatures
+ */
+export function verify_hmac_sha256(payload: string, signature_hex: string, secret: string): boolean;
+
+/**
+ * Verify Telegram Mini App `initData` against the bot token
+ * Implements the documented scheme: the data-check-string is every field except
+ * `hash`, sorted by key as `key=value` lines; secret = HMAC-SHA256("WebAppData", bot_token)
+ * and `hash` must equal hex(HMAC-SHA256(secret, data-check-string)).
+ * Returns false on a missing or mismatching hash, or malformed input
+ */
+export function verify_telegram_init_data(init_data: string, bot_token: string): boolean;
 
 export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
 
 export interface InitOutput {
     readonly memory: WebAssembly.Memory;
+    readonly __wbg_ratelimiter_free: (a: number, b: number) => void;
     readonly aggregate_procurement_stats: (a: number, b: number) => [number, number];
-    readonly batch_process_messages: (a: number, b: number, c: bigint) => [number, number];
+    readonly aggregate_procurement_stats_checked: (a: number, b: number) => [number, number];
+    readonly aggregate_procurement_stats_js: (a: number, b: number) => any;
+    readonly avatar_spec: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+    readonly base64url_decode: (a: number, b: number) => [number, number];
+    readonly base64url_encode: (a: number, b: number) => [number, number];
+    readonly batch_process_messages: (a: number, b: number, c: bigint, d: number) => [number, number];
+    readonly batch_process_messages_checked: (a: number, b: number, c: bigint, d: number) => [number, number];
     readonly batch_process_procurements: (a: number, b: number) => [number, number];
-    readonly benchmark_batch_processing: (a: number) => number;
+    readonly batch_process_procurements_checked: (a: number, b: number) => [number, number];
+    readonly batch_process_procurements_chunk: (a: number, b: number, c: number, d: number) => [number, number];
+    readonly batch_process_procurements_msgpack: (a: number, b: number) => [number, number];
+    readonly benchmark_batch_processing: (a: number, b: number) => [number, number];
+    readonly build_payment_payload: (a: bigint, b: number, c: number, d: number, e: number, f: number) => [number, number];
+    readonly build_share_link: (a: number, b: number, c: bigint) => [number, number];
     readonly calculate_progress: (a: number, b: number) => number;
+    readonly calculate_progress_f64: (a: number, b: number) => number;
+    readonly canonicalize_category: (a: number, b: number) => [number, number];
+    readonly censor_profanity: (a: number, b: number) => [number, number];
+    readonly contains_profanity: (a: number, b: number) => number;
+    readonly contrast_ratio: (a: number, b: number, c: number, d: number) => number;
+    readonly deadline_phrase: (a: number) => [number, number];
+    readonly diff_procurements: (a: number, b: number, c: number, d: number) => [number, number];
     readonly escape_html: (a: number, b: number) => [number, number];
+    readonly filter_procurements: (a: number, b: number, c: number, d: number) => [number, number];
+    readonly find_duplicate_procurements: (a: number, b: number) => [number, number];
+    readonly fold_diacritics: (a: number, b: number, c: number) => [number, number];
+    readonly format_count_ru: (a: number, b: number, c: number) => [number, number];
     readonly format_currency: (a: number) => [number, number];
+    readonly format_file_size: (a: number) => [number, number];
     readonly format_message_text: (a: number, b: number) => [number, number];
+    readonly format_money: (a: number, b: number, c: number) => [number, number];
+    readonly format_ordinal_ru: (a: number, b: number, c: number) => [number, number];
+    readonly format_phone: (a: number, b: number) => [number, number];
     readonly format_relative_time: (a: number) => [number, number];
+    readonly format_relative_time_full: (a: number) => [number, number];
     readonly generate_platform_user_id: () => [number, number];
     readonly get_avatar_color: (a: number, b: number) => [number, number];
+    readonly get_avatar_color_themed: (a: number, b: number, c: number, d: number) => [number, number];
     readonly get_initials: (a: number, b: number, c: number, d: number) => [number, number];
+    readonly get_initials_v2: (a: number, b: number, c: number, d: number) => [number, number];
+    readonly gzip_compress: (a: number, b: number) => [number, number];
+    readonly gzip_decompress: (a: number, b: number) => [number, number];
+    readonly highlight_terms: (a: number, b: number, c: number, d: number) => [number, number];
+    readonly is_disposable_email: (a: number, b: number) => number;
+    readonly levenshtein: (a: number, b: number, c: number, d: number) => number;
+    readonly merge_procurements: (a: number, b: number, c: number, d: number) => [number, number];
+    readonly message_length: (a: number, b: number) => number;
+    readonly message_length_ok: (a: number, b: number, c: number) => number;
+    readonly message_preview: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+    readonly message_spam_score: (a: number, b: number) => number;
+    readonly message_text_to_plain: (a: number, b: number) => [number, number];
+    readonly messages_by_date: (a: number, b: number, c: number) => [number, number];
+    readonly normalize_phone_e164: (a: number, b: number) => [number, number];
+    readonly normalize_username: (a: number, b: number) => [number, number];
+    readonly parse_deadline: (a: number, b: number) => number;
+    readonly parse_procurements_csv: (a: number, b: number) => [number, number];
+    readonly parse_start_param: (a: number, b: number) => bigint;
+    readonly password_strength: (a: number, b: number) => [number, number];
+    readonly pick_readable_text: (a: number, b: number) => [number, number];
+    readonly procurement_fingerprint: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
+    readonly procurement_health: (a: number, b: number, c: number, d: number, e: number) => number;
+    readonly procurements_to_csv: (a: number, b: number) => [number, number];
+    readonly query_procurements: (a: number, b: number, c: number, d: number) => [number, number];
+    readonly ratelimiter_new: (a: number, b: number) => number;
+    readonly ratelimiter_tokens_remaining: (a: number) => number;
+    readonly ratelimiter_try_acquire: (a: number) => number;
+    readonly related_procurements: (a: number, b: number, c: bigint, d: number) => [number, number];
+    readonly render_description_md: (a: number, b: number) => [number, number];
     readonly search_messages: (a: number, b: number, c: number, d: number) => [number, number];
-    readonly search_procurements: (a: number, b: number, c: number, d: number) => [number, number];
-    readonly sort_procurements: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+    readonly search_messages_by_user: (a: number, b: number, c: bigint, d: number, e: number) => [number, number];
+    readonly search_messages_in_range: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+    readonly search_procurements: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => [number, number];
+    readonly search_procurements_checked: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => [number, number];
+    readonly search_procurements_highlighted: (a: number, b: number, c: number, d: number) => [number, number];
+    readonly slugify: (a: number, b: number) => [number, number];
+    readonly sort_procurements: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => [number, number];
+    readonly sort_procurements_checked: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => [number, number];
+    readonly text_stats: (a: number, b: number) => [number, number];
+    readonly time_until_label: (a: number) => [number, number];
+    readonly truncate_text: (a: number, b: number, c: number) => [number, number];
+    readonly validate_attachment: (a: number, b: number, c: number) => [number, number];
+    readonly validate_deadline_string: (a: number, b: number) => [number, number];
     readonly validate_email: (a: number, b: number) => number;
+    readonly validate_email_strict: (a: number, b: number) => number;
+    readonly validate_inn: (a: number, b: number) => number;
     readonly validate_phone: (a: number, b: number) => number;
-    readonly validate_procurement_form: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => [number, number];
+    readonly validate_procurement_form: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => [number, number];
+    readonly validate_procurement_forms: (a: number, b: number) => [number, number];
+    readonly validate_procurement_json: (a: number, b: number) => [number, number];
+    readonly validate_username: (a: number, b: number) => [number, number];
+    readonly verify_hmac_sha256: (a: number, b: number, c: number, d: number, e: number, f: number) => number;
+    readonly verify_telegram_init_data: (a: number, b: number, c: number, d: number) => number;
     readonly days_until: (a: number) => number;
+    readonly calculate_progress_uncapped: (a: number, b: number) => number;
+    readonly __wbindgen_exn_store: (a: number) => void;
+    readonly __externref_table_alloc: () => number;
     readonly __wbindgen_externrefs: WebAssembly.Table;
     readonly __wbindgen_malloc: (a: number, b: number) => number;
     readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
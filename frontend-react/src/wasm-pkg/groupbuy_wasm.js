@@ -1,9 +1,61 @@
 /* @ts-self-types="./groupbuy_wasm.d.ts" */
 
+/**
+ * Token-bucket rate limiter for throttling client actions (message send, search)
+ * Holds up to `capacity` tokens, refilled continuously at `refill_per_sec`;
+ * each allowed action spends one token. Refill is computed lazily on each call
+ */
+export class RateLimiter {
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        RateLimiterFinalization.unregister(this);
+        return ptr;
+    }
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_ratelimiter_free(ptr, 0);
+    }
+    /**
+     * Create a limiter that starts full
+     * @param {number} capacity
+     * @param {number} refill_per_sec
+     */
+    constructor(capacity, refill_per_sec) {
+        const ret = wasm.ratelimiter_new(capacity, refill_per_sec);
+        this.__wbg_ptr = ret >>> 0;
+        RateLimiterFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * Tokens currently available (fractional while refilling)
+     * @returns {number}
+     */
+    tokens_remaining() {
+        const ret = wasm.ratelimiter_tokens_remaining(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Spend one token if available; returns false when the action should be throttled
+     * @returns {boolean}
+     */
+    try_acquire() {
+        const ret = wasm.ratelimiter_try_acquire(this.__wbg_ptr);
+        return ret !== 0;
+    }
+}
+if (Symbol.dispose) RateLimiter.prototype[Symbol.dispose] = RateLimiter.prototype.free;
+
 /**
  * Aggregate procurement statistics from a JSON array
  * Returns JSON object with: total_count, active_count, total_amount, total_target,
- * overall_progress, avg_participants, cities (unique), by_status counts
+ * overall_progress, avg_participants, cities (unique), by_status counts,
+ * median_target, median_participants, p90_target,
+ * by_city / by_category ({name: {count, total_amount}}, missing names under "unknown";
+ * categories are merged by `canonicalize_category`),
+ * completed_count, funded_count (reached a positive target), funding_rate (% of total_count),
+ * overfunded_count (raised more than a positive target), overfunded_total (sum of the excess)
+ * Legacy string API: prefer `aggregate_procurement_stats_js`, which returns a JS object directly
  * @param {string} json_input
  * @returns {string}
  */
@@ -22,21 +74,144 @@ export function aggregate_procurement_stats(json_input) {
     }
 }
 
+/**
+ * `aggregate_procurement_stats` reporting malformed input instead of returning "{}"
+ * Returns JSON: { "ok": true, "data": {...} } or { "ok": false, "error": "..." }
+ * @param {string} json_input
+ * @returns {string}
+ */
+export function aggregate_procurement_stats_checked(json_input) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.aggregate_procurement_stats_checked(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Aggregate procurement statistics, returned as a plain JS object
+ * Same fields as `aggregate_procurement_stats` (numbers as JS numbers, maps as objects);
+ * invalid input yields an empty object
+ * @param {string} json_input
+ * @returns {any}
+ */
+export function aggregate_procurement_stats_js(json_input) {
+    const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.aggregate_procurement_stats_js(ptr0, len0);
+    return ret;
+}
+
+/**
+ * Avatar initials and colors in one call
+ * Initials are those of `get_initials`, bg is `get_avatar_color_themed` of the first
+ * name, fg is "#000000" or "#ffffff", whichever contrasts more with bg
+ * Returns JSON: { "initials": "ИП", "bg": "#rrggbb", "fg": "#rrggbb" }
+ * @param {string} first
+ * @param {string} last
+ * @param {string} theme
+ * @returns {string}
+ */
+export function avatar_spec(first, last, theme) {
+    let deferred4_0;
+    let deferred4_1;
+    try {
+        const ptr0 = passStringToWasm0(first, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(last, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ptr2 = passStringToWasm0(theme, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len2 = WASM_VECTOR_LEN;
+        const ret = wasm.avatar_spec(ptr0, len0, ptr1, len1, ptr2, len2);
+        deferred4_0 = ret[0];
+        deferred4_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred4_0, deferred4_1, 1);
+    }
+}
+
+/**
+ * Decode base64url (padded or not) into bytes; invalid input gives an empty array
+ * @param {string} s
+ * @returns {Uint8Array}
+ */
+export function base64url_decode(s) {
+    const ptr0 = passStringToWasm0(s, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.base64url_decode(ptr0, len0);
+    var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+    return v2;
+}
+
+/**
+ * Encode bytes as unpadded base64url (safe in URLs and Telegram start params)
+ * @param {Uint8Array} data
+ * @returns {string}
+ */
+export function base64url_encode(data) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passArray8ToWasm0(data, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.base64url_encode(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
 /**
  * Batch-process messages: format text, compute date groups, format times
- * Input: JSON array of messages, current user ID
+ * Input: JSON array of messages, current user ID, timezone offset in minutes east of UTC
+ * (0 = UTC, 180 = Moscow; pass `-new Date().getTimezoneOffset()` for browser local time)
  * Output: JSON array with formatted fields
  * @param {string} json_input
  * @param {bigint} current_user_id
+ * @param {number} tz_offset_minutes
+ * @returns {string}
+ */
+export function batch_process_messages(json_input, current_user_id, tz_offset_minutes) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.batch_process_messages(ptr0, len0, current_user_id, tz_offset_minutes);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * `batch_process_messages` reporting malformed input instead of returning "[]"
+ * Returns JSON: { "ok": true, "data": [...] } or { "ok": false, "error": "..." }
+ * @param {string} json_input
+ * @param {bigint} current_user_id
+ * @param {number} tz_offset_minutes
  * @returns {string}
  */
-export function batch_process_messages(json_input, current_user_id) {
+export function batch_process_messages_checked(json_input, current_user_id, tz_offset_minutes) {
     let deferred2_0;
     let deferred2_1;
     try {
         const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
         const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.batch_process_messages(ptr0, len0, current_user_id);
+        const ret = wasm.batch_process_messages_checked(ptr0, len0, current_user_id, tz_offset_minutes);
         deferred2_0 = ret[0];
         deferred2_1 = ret[1];
         return getStringFromWasm0(ret[0], ret[1]);
@@ -48,7 +223,8 @@ export function batch_process_messages(json_input, current_user_id) {
 /**
  * Batch-process procurements: compute progress, days left, and format currency in one pass
  * Input: JSON array of procurements
- * Output: JSON array with computed fields added
+ * Output: JSON array with computed fields added (including `urgency`: "overdue", "soon"
+ * within 3 days, "week" within 7, "ok", or "none" without a deadline)
  * @param {string} json_input
  * @returns {string}
  */
@@ -67,19 +243,146 @@ export function batch_process_procurements(json_input) {
     }
 }
 
+/**
+ * `batch_process_procurements` reporting malformed input instead of returning "[]"
+ * Returns JSON: { "ok": true, "data": [...] } or { "ok": false, "error": "..." }
+ * @param {string} json_input
+ * @returns {string}
+ */
+export function batch_process_procurements_checked(json_input) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.batch_process_procurements_checked(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Batch-process one window of procurements, for requesting large catalogs progressively
+ * Output matches the `[offset, offset + limit)` slice of `batch_process_procurements`;
+ * `limit <= 0` means "to the end" and offsets past the end yield "[]".
+ * The whole input is re-parsed on every call, so this only bounds the work and
+ * output size per call; for real streaming use `batch_process_procurements_msgpack`
+ * @param {string} json_input
+ * @param {number} offset
+ * @param {number} limit
+ * @returns {string}
+ */
+export function batch_process_procurements_chunk(json_input, offset, limit) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.batch_process_procurements_chunk(ptr0, len0, offset, limit);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Batch-process procurements with MessagePack input and output (`Uint8Array` in JS)
+ * The schema is identical to `batch_process_procurements`: an array of procurement
+ * maps in, an array of maps with the same computed fields out (field names as keys)
+ * @param {Uint8Array} input
+ * @returns {Uint8Array}
+ */
+export function batch_process_procurements_msgpack(input) {
+    const ptr0 = passArray8ToWasm0(input, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.batch_process_procurements_msgpack(ptr0, len0);
+    var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+    return v2;
+}
+
 /**
  * Run a performance benchmark for batch processing
- * Generates N random procurements and processes them, returning elapsed time in ms
+ * Generates N procurements from `seed` (same seed, same dataset) and runs batch
+ * processing, search, sort and aggregation on them
+ * Returns JSON with elapsed ms per step:
+ * { generate_ms, batch_ms, search_ms, sort_ms, aggregate_ms, total_ms }
  * @param {number} count
- * @returns {number}
+ * @param {number} seed
+ * @returns {string}
  */
-export function benchmark_batch_processing(count) {
-    const ret = wasm.benchmark_batch_processing(count);
-    return ret;
+export function benchmark_batch_processing(count, seed) {
+    let deferred1_0;
+    let deferred1_1;
+    try {
+        const ret = wasm.benchmark_batch_processing(count, seed);
+        deferred1_0 = ret[0];
+        deferred1_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+    }
+}
+
+/**
+ * Build the payment QR payload for a procurement contribution
+ * Canonical query string with keys in alphabetical order:
+ * `amount=<2 decimals>&currency=<upper-case>&procurement_id=<id>&purpose=<percent-encoded>`
+ * Returns "" for a non-positive or non-finite amount
+ * @param {bigint} procurement_id
+ * @param {number} amount
+ * @param {string} currency
+ * @param {string} purpose
+ * @returns {string}
+ */
+export function build_payment_payload(procurement_id, amount, currency, purpose) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(currency, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(purpose, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.build_payment_payload(procurement_id, amount, ptr0, len0, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Build a Telegram deep link that opens a procurement in the bot
+ * Returns `https://t.me/<bot>?start=<payload>` where the payload is `proc_<id>`
+ * base64url-encoded (a leading '@' in the bot username is ignored)
+ * @param {string} bot_username
+ * @param {bigint} procurement_id
+ * @returns {string}
+ */
+export function build_share_link(bot_username, procurement_id) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(bot_username, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.build_share_link(ptr0, len0, procurement_id);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
 }
 
 /**
- * Calculate procurement progress percentage
+ * Calculate procurement progress percentage, rounded to the nearest whole percent
+ * and clamped to [0, 100] (99.6% shows as 100, over-funding as 100)
  * @param {number} current_amount
  * @param {number} target_amount
  * @returns {number}
@@ -90,27 +393,67 @@ export function calculate_progress(current_amount, target_amount) {
 }
 
 /**
- * Calculate days remaining until deadline
- * @param {number} deadline_ms
+ * Calculate unrounded procurement progress percentage, clamped to [0, 100]
+ * (for progress bars with sub-percent precision)
+ * @param {number} current_amount
+ * @param {number} target_amount
  * @returns {number}
  */
-export function days_until(deadline_ms) {
-    const ret = wasm.days_until(deadline_ms);
+export function calculate_progress_f64(current_amount, target_amount) {
+    const ret = wasm.calculate_progress_f64(current_amount, target_amount);
     return ret;
 }
 
 /**
- * Escape HTML to prevent XSS
+ * Calculate procurement progress percentage without the 100% cap (e.g. 150 for
+ * an over-funded procurement), rounded and floored at 0
+ * @param {number} current_amount
+ * @param {number} target_amount
+ * @returns {number}
+ */
+export function calculate_progress_uncapped(current_amount, target_amount) {
+    const ret = wasm.calculate_progress_uncapped(current_amount, target_amount);
+    return ret;
+}
+
+/**
+ * Map a free-text category to its canonical Russian label
+ * Known synonyms ("электроника", "Electronics", "elektronika") map to the catalog label
+ * ("Электроника"), with exact synonyms taking precedence over transliterations;
+ * unknown names are trimmed, whitespace-collapsed and title-cased ("ручная работа" ->
+ * "Ручная Работа"). Blank input gives ""
+ * @param {string} raw
+ * @returns {string}
+ */
+export function canonicalize_category(raw) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(raw, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.canonicalize_category(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Mask profanity in text, keeping the first letter of each matched word (e.g. "f***")
+ * Words are matched whole (or by stem) after undoing simple substitutions such as
+ * `@` -> a, `0` -> o and Latin look-alikes in Cyrillic words. Best-effort only
  * @param {string} text
  * @returns {string}
  */
-export function escape_html(text) {
+export function censor_profanity(text) {
     let deferred2_0;
     let deferred2_1;
     try {
         const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
         const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.escape_html(ptr0, len0);
+        const ret = wasm.censor_profanity(ptr0, len0);
         deferred2_0 = ret[0];
         deferred2_1 = ret[1];
         return getStringFromWasm0(ret[0], ret[1]);
@@ -120,35 +463,1307 @@ export function escape_html(text) {
 }
 
 /**
- * Format currency amount (Russian rubles)
- * @param {number} amount
+ * Check whether text contains profanity (same matching as `censor_profanity`)
+ * @param {string} text
+ * @returns {boolean}
+ */
+export function contains_profanity(text) {
+    const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.contains_profanity(ptr0, len0);
+    return ret !== 0;
+}
+
+/**
+ * WCAG contrast ratio of two `#RRGGBB` colors, from 1.0 (identical) to 21.0 (black on white)
+ * Order of the arguments does not matter; invalid hex gives 0.0
+ * @param {string} hex_a
+ * @param {string} hex_b
+ * @returns {number}
+ */
+export function contrast_ratio(hex_a, hex_b) {
+    const ptr0 = passStringToWasm0(hex_a, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ptr1 = passStringToWasm0(hex_b, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ret = wasm.contrast_ratio(ptr0, len0, ptr1, len1);
+    return ret;
+}
+
+/**
+ * Calculate days remaining until deadline
+ * @param {number} deadline_ms
+ * @returns {number}
+ */
+export function days_until(deadline_ms) {
+    const ret = wasm.days_until(deadline_ms);
+    return ret;
+}
+
+/**
+ * Deadline phrase with a color hint for procurement cards
+ * Returns JSON: {label, days, urgency, color} where label is "осталось N дней" with
+ * Russian plural forms ("остался 1 день", "осталось 2 дня", whole days rounded down),
+ * "осталось менее дня" under a day, "Завершено" once the deadline has passed (days is
+ * then negative) or "Без срока" for a missing (NaN / infinite) deadline (days is null).
+ * urgency is the `urgency` bucket of `batch_process_procurements`
+ * @param {number} deadline_ms
  * @returns {string}
  */
-export function format_currency(amount) {
+export function deadline_phrase(deadline_ms) {
     let deferred1_0;
     let deferred1_1;
     try {
-        const ret = wasm.format_currency(amount);
+        const ret = wasm.deadline_phrase(deadline_ms);
         deferred1_0 = ret[0];
         deferred1_1 = ret[1];
         return getStringFromWasm0(ret[0], ret[1]);
     } finally {
-        wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+        wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+    }
+}
+
+/**
+ * Compare two procurement lists by id for incremental UI updates
+ * "changed" lists ids present in both whose title, status, current_amount,
+ * target_amount, participant_count or deadline differ
+ * Returns JSON: { "added": [ids], "removed": [ids], "changed": [ids] }
+ * (added/changed in new-list order, removed in old-list order)
+ * @param {string} old_json
+ * @param {string} new_json
+ * @returns {string}
+ */
+export function diff_procurements(old_json, new_json) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(old_json, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(new_json, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.diff_procurements(ptr0, len0, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Escape HTML to prevent XSS
+ * @param {string} text
+ * @returns {string}
+ */
+export function escape_html(text) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.escape_html(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Filter procurements by a JSON filter object
+ * Filters: status (exact), city and category (case-insensitive), min_amount / max_amount
+ * (current amount, inclusive), min_progress / max_progress (percent as in
+ * `batch_process_procurements`, inclusive). All provided filters must match.
+ * Returns JSON array of matching procurement IDs in input order
+ * @param {string} json_input
+ * @param {string} filters_json
+ * @returns {string}
+ */
+export function filter_procurements(json_input, filters_json) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(filters_json, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.filter_procurements(ptr0, len0, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Find procurements sharing a `procurement_fingerprint` (title, city, organizer, target)
+ * Returns JSON array of id groups, e.g. [[1, 4], [2, 7, 9]]: ids ascending within a
+ * group, groups ordered by their first id; procurements without a duplicate are omitted
+ * @param {string} json_input
+ * @returns {string}
+ */
+export function find_duplicate_procurements(json_input) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.find_duplicate_procurements(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Strip diacritics: "café" -> "cafe", "naïve" -> "naive"
+ * Text is NFC-composed, each character reduced to the base of its canonical
+ * decomposition, and leftover combining marks dropped. "й" is a letter of its own
+ * and kept; "ё" is folded to "е" only when `fold_yo` is set
+ * @param {string} text
+ * @param {boolean} fold_yo
+ * @returns {string}
+ */
+export function fold_diacritics(text, fold_yo) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.fold_diacritics(ptr0, len0, fold_yo);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Format a number with the matching Russian plural form, e.g. "5 мест"
+ * forms_json: JSON array of three forms [one, few, many], e.g. ["место", "места", "мест"]
+ * Invalid forms give just the number
+ * @param {number} n
+ * @param {string} forms_json
+ * @returns {string}
+ */
+export function format_count_ru(n, forms_json) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(forms_json, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.format_count_ru(n, ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Format currency amount (Russian rubles; shortcut for `format_money(amount, "RUB")`)
+ * Negative amounts get a single leading ASCII '-' (e.g. "-1 234,50 ₽")
+ * @param {number} amount
+ * @returns {string}
+ */
+export function format_currency(amount) {
+    let deferred1_0;
+    let deferred1_1;
+    try {
+        const ret = wasm.format_currency(amount);
+        deferred1_0 = ret[0];
+        deferred1_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+    }
+}
+
+/**
+ * Format a file size with 1024-based Russian units (Б, КБ, МБ, ГБ)
+ * Bytes have no decimals, larger units one decimal with a comma (e.g. "3,4 МБ").
+ * Negative or non-finite (NaN, infinite) input gives "0 Б"
+ * @param {number} bytes
+ * @returns {string}
+ */
+export function format_file_size(bytes) {
+    let deferred1_0;
+    let deferred1_1;
+    try {
+        const ret = wasm.format_file_size(bytes);
+        deferred1_0 = ret[0];
+        deferred1_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+    }
+}
+
+/**
+ * Format message text: escape HTML, convert URLs, @mentions and #hashtags to links,
+ * render *bold* / _italic_, convert newlines to <br>
+ * @param {string} text
+ * @returns {string}
+ */
+export function format_message_text(text) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.format_message_text(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Format a money amount for a currency code: RUB (1 234,50 ₽), KZT (1 234,50 ₸),
+ * BYN (1 234,50 Br), EUR (1 234,50 €) and USD ($1,234.50); other codes are shown
+ * after the amount in the ruble layout. Kopecks/cents are omitted when zero
+ * @param {number} amount
+ * @param {string} currency
+ * @returns {string}
+ */
+export function format_money(amount, currency) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(currency, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.format_money(amount, ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Format a Russian ordinal number with its gender ending: "1-й", "1-я", "1-е"
+ * gender: "m" / "masculine" (участник), "f" / "feminine" (закупка),
+ * "n" / "neuter" (место); anything else is treated as masculine
+ * @param {number} n
+ * @param {string} gender
+ * @returns {string}
+ */
+export function format_ordinal_ru(n, gender) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(gender, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.format_ordinal_ru(n, ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Format phone number for display
+ * Russian numbers (11 digits starting with 7 or 8) become `+7 (XXX) XXX-XX-XX`,
+ * anything else is returned as `+` followed by the cleaned digits
+ * @param {string} phone
+ * @returns {string}
+ */
+export function format_phone(phone) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(phone, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.format_phone(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Format relative time in Russian, up to weeks, months and years
+ * Future timestamps within a minute (clock skew) read "только что",
+ * further ones are phrased forward ("через N мин.")
+ * @param {number} timestamp_ms
+ * @returns {string}
+ */
+export function format_relative_time(timestamp_ms) {
+    let deferred1_0;
+    let deferred1_1;
+    try {
+        const ret = wasm.format_relative_time(timestamp_ms);
+        deferred1_0 = ret[0];
+        deferred1_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+    }
+}
+
+/**
+ * Format relative time in Russian using full words with correct plural forms
+ * (e.g. "1 минуту назад", "3 часа назад", "25 дней назад")
+ * @param {number} timestamp_ms
+ * @returns {string}
+ */
+export function format_relative_time_full(timestamp_ms) {
+    let deferred1_0;
+    let deferred1_1;
+    try {
+        const ret = wasm.format_relative_time_full(timestamp_ms);
+        deferred1_0 = ret[0];
+        deferred1_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+    }
+}
+
+/**
+ * Generate unique platform user ID for websocket users
+ * Format: `web_` followed by a random (version 4) UUID
+ * @returns {string}
+ */
+export function generate_platform_user_id() {
+    let deferred1_0;
+    let deferred1_1;
+    try {
+        const ret = wasm.generate_platform_user_id();
+        deferred1_0 = ret[0];
+        deferred1_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+    }
+}
+
+/**
+ * Generate avatar background color based on name (hash-based)
+ * @param {string} name
+ * @returns {string}
+ */
+export function get_avatar_color(name) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.get_avatar_color(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Generate avatar background color for a theme ("light" or "dark")
+ * The palette slot depends only on the name, so a user keeps the same hue
+ * when switching themes; unknown themes fall back to the dark palette
+ * @param {string} name
+ * @param {string} theme
+ * @returns {string}
+ */
+export function get_avatar_color_themed(name, theme) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(theme, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.get_avatar_color_themed(ptr0, len0, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Get initials from first name and last name
+ * Takes the first grapheme of each name, so flags and combining sequences stay intact
+ * @param {string} first_name
+ * @param {string} last_name
+ * @returns {string}
+ */
+export function get_initials(first_name, last_name) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(first_name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(last_name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.get_initials(ptr0, len0, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Get two-character initials from first name and last name
+ * When the last name is empty, the first two graphemes of the first name are used
+ * ("Александр" -> "АЛ"); emoji and combining sequences count as one character
+ * @param {string} first_name
+ * @param {string} last_name
+ * @returns {string}
+ */
+export function get_initials_v2(first_name, last_name) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(first_name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(last_name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.get_initials_v2(ptr0, len0, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Gzip-compress a string (e.g. procurement JSON before caching it in IndexedDB)
+ * Returns the gzip bytes (`Uint8Array` in JS)
+ * @param {string} data
+ * @returns {Uint8Array}
+ */
+export function gzip_compress(data) {
+    const ptr0 = passStringToWasm0(data, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.gzip_compress(ptr0, len0);
+    var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+    return v2;
+}
+
+/**
+ * Decompress gzip bytes produced by `gzip_compress` back into a string
+ * Invalid, truncated or non-UTF-8 data gives ""
+ * @param {Uint8Array} data
+ * @returns {string}
+ */
+export function gzip_decompress(data) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passArray8ToWasm0(data, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.gzip_decompress(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * HTML-escape text and wrap case-insensitive occurrences of each query word in <mark>
+ * Matching runs on the raw text, so entities such as `&amp;` are never split;
+ * overlapping or adjacent matches are merged into a single <mark>
+ * @param {string} text
+ * @param {string} query
+ * @returns {string}
+ */
+export function highlight_terms(text, query) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(query, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.highlight_terms(ptr0, len0, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Check whether an email uses a known disposable (throwaway) provider
+ * Case-insensitive; subdomains of listed providers also count
+ * @param {string} email
+ * @returns {boolean}
+ */
+export function is_disposable_email(email) {
+    const ptr0 = passStringToWasm0(email, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.is_disposable_email(ptr0, len0);
+    return ret !== 0;
+}
+
+/**
+ * Levenshtein edit distance between two strings, counted in characters (not bytes)
+ * @param {string} a
+ * @param {string} b
+ * @returns {number}
+ */
+export function levenshtein(a, b) {
+    const ptr0 = passStringToWasm0(a, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ptr1 = passStringToWasm0(b, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ret = wasm.levenshtein(ptr0, len0, ptr1, len1);
+    return ret;
+}
+
+/**
+ * Apply a patch to a procurement list, matching objects by `id`
+ * Base objects with an id in the patch are replaced in place, patch objects with new
+ * ids are appended in patch order, and everything else is kept untouched. Objects are
+ * copied as-is, so fields unknown to this crate survive; patch items without a numeric
+ * id are ignored (for a repeated id the last patch object wins)
+ * Returns the merged JSON array ([] if either input is invalid)
+ * @param {string} base_json
+ * @param {string} patch_json
+ * @returns {string}
+ */
+export function merge_procurements(base_json, patch_json) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(base_json, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(patch_json, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.merge_procurements(ptr0, len0, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Message length as users perceive it: grapheme clusters, so a compound emoji
+ * (family, flag, skin tone) counts as 1
+ * @param {string} text
+ * @returns {number}
+ */
+export function message_length(text) {
+    const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.message_length(ptr0, len0);
+    return ret;
+}
+
+/**
+ * Check that a message fits within `max` graphemes (see `message_length`)
+ * @param {string} text
+ * @param {number} max
+ * @returns {boolean}
+ */
+export function message_length_ok(text, max) {
+    const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.message_length_ok(ptr0, len0, max);
+    return ret !== 0;
+}
+
+/**
+ * One-line preview of a message for chat list rows
+ * Markup is stripped and whitespace (including newlines) collapsed as in
+ * `message_text_to_plain`, then the text is cut to `max_chars` graphemes with "…"
+ * (see `truncate_text`). System messages are free-form text and are previewed as is;
+ * image and file messages without text become "Фото" / "Файл"
+ * @param {string} text
+ * @param {number} max_chars
+ * @param {string} message_type
+ * @returns {string}
+ */
+export function message_preview(text, max_chars, message_type) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(message_type, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.message_preview(ptr0, len0, max_chars, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Heuristic spam score of a chat message, from 0.0 (normal) to 1.0 (spam)
+ * Weighted sum of saturating features:
+ * - links: 0.4 * min(urls / 3, 1)
+ * - shouting: 0.25 * share of uppercase letters (messages with 8+ letters only)
+ * - repeated characters: 0.15 * min(chars in runs of 4+ identical chars / 10, 1)
+ * - promo keywords: 0.2 * min(words starting with a keyword / 2, 1)
+ *
+ * Each feature only grows with its input, so e.g. more links never lower the score
+ * @param {string} text
+ * @returns {number}
+ */
+export function message_spam_score(text) {
+    const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.message_spam_score(ptr0, len0);
+    return ret;
+}
+
+/**
+ * Convert message text to a single line of plain text (for notifications and search)
+ * Strips *bold* / _italic_ markers, keeps URLs literal, collapses whitespace and newlines
+ * @param {string} text
+ * @returns {string}
+ */
+export function message_text_to_plain(text) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.message_text_to_plain(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Count messages per local calendar day
+ * Input: JSON array of messages, timezone offset in minutes east of UTC (as in `batch_process_messages`)
+ * Output: JSON array of `{date: "YYYY-MM-DD", label, count}` in chronological order;
+ * messages without a parseable `created_at` are counted in a trailing `date: null` bucket
+ * @param {string} json_input
+ * @param {number} tz_offset_minutes
+ * @returns {string}
+ */
+export function messages_by_date(json_input, tz_offset_minutes) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.messages_by_date(ptr0, len0, tz_offset_minutes);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Normalize a phone number to E.164 storage form (`+` followed by digits, no spaces)
+ * A Russian trunk prefix is replaced (8XXXXXXXXXX -> +7XXXXXXXXXX); numbers without
+ * `+` are otherwise read as already including the country code. Returns "" when the
+ * result would not pass `validate_phone` or the input has no digits
+ * @param {string} phone
+ * @returns {string}
+ */
+export function normalize_phone_e164(phone) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(phone, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.normalize_phone_e164(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Normalize a username for storage and comparison (trimmed, lowercase)
+ * @param {string} name
+ * @returns {string}
+ */
+export function normalize_username(name) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.normalize_username(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Parse a deadline from a form value to milliseconds since the epoch
+ * Accepts `YYYY-MM-DD` (end of that day, 23:59:59.999 UTC) or a full ISO timestamp.
+ * Returns NaN for unparseable input
+ * @param {string} iso
+ * @returns {number}
+ */
+export function parse_deadline(iso) {
+    const ptr0 = passStringToWasm0(iso, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.parse_deadline(ptr0, len0);
+    return ret;
+}
+
+/**
+ * Parse procurements from CSV (the `procurements_to_csv` format, header row required)
+ * Columns are matched by header name, so extra columns (e.g. description) are allowed
+ * and missing ones read as empty. Quoted fields and CRLF line endings are supported.
+ * Each row is checked with the procurement form rules (min_target and max_days disabled;
+ * description only when the column is present); non-empty id, current_amount and
+ * participant_count cells that aren't numbers are errors too, empty ones read as null.
+ * Returns JSON: { "procurements": [...], "errors": [...] } with one entry per data row
+ * in both arrays; a row with the wrong column count yields a null procurement and a
+ * "row" error, valid rows an empty error object
+ * @param {string} csv
+ * @returns {string}
+ */
+export function parse_procurements_csv(csv) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(csv, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.parse_procurements_csv(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Decode a /start payload produced by `build_share_link`
+ * Returns the procurement ID, or -1 if the payload is malformed
+ * @param {string} param
+ * @returns {bigint}
+ */
+export function parse_start_param(param) {
+    const ptr0 = passStringToWasm0(param, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.parse_start_param(ptr0, len0);
+    return ret;
+}
+
+/**
+ * Estimate password strength for a live registration meter
+ * score (0-4): +1 for 8+ chars, +1 for 12+ chars, +1 for 3+ character classes,
+ * +1 for all 4 classes; -1 for a run of 3+ repeated or sequential characters
+ * ("aaa", "abc", "321"); common passwords always score 0.
+ * The password is only inspected, never stored or logged
+ * Returns JSON: { score, length_ok, has_upper, has_lower, has_digit, has_symbol, suggestions }
+ * @param {string} pw
+ * @returns {string}
+ */
+export function password_strength(pw) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(pw, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.password_strength(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Text color for a background: "#000000" or "#ffffff", whichever contrasts more
+ * (black for an invalid color)
+ * @param {string} bg_hex
+ * @returns {string}
+ */
+export function pick_readable_text(bg_hex) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(bg_hex, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.pick_readable_text(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Fingerprint of a procurement for duplicate detection: hex SHA-256 of the lowercased,
+ * whitespace-collapsed title, city and organizer plus the target rounded to kopecks
+ * Differences only in case or spacing give the same fingerprint
+ * @param {string} title
+ * @param {string} city
+ * @param {string} organizer
+ * @param {number} target
+ * @returns {string}
+ */
+export function procurement_fingerprint(title, city, organizer, target) {
+    let deferred4_0;
+    let deferred4_1;
+    try {
+        const ptr0 = passStringToWasm0(title, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(city, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ptr2 = passStringToWasm0(organizer, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len2 = WASM_VECTOR_LEN;
+        const ret = wasm.procurement_fingerprint(ptr0, len0, ptr1, len1, ptr2, len2, target);
+        deferred4_0 = ret[0];
+        deferred4_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred4_0, deferred4_1, 1);
+    }
+}
+
+/**
+ * Procurement "health" from 0 to 100 for an at-a-glance indicator
+ * health = 60 * pace + 25 * funded + 15 * momentum, clamped to [0, 100], where
+ * - funded: current / target, clamped to [0, 1] (reaching the target scores 100 overall)
+ * - expected: share of the created..deadline window already elapsed, in [0, 1]
+ *   (0 if either timestamp is missing/NaN)
+ * - pace: 1 + 2 * (funded - expected), clamped to [0, 1] — full when at or ahead
+ *   of a linear funding pace, zero when half the target behind it
+ * - momentum: participants per elapsed day (at least one day), capped at 1 per day
+ * @param {number} current
+ * @param {number} target
+ * @param {number} deadline_ms
+ * @param {number} participants
+ * @param {number} created_ms
+ * @returns {number}
+ */
+export function procurement_health(current, target, deadline_ms, participants, created_ms) {
+    const ret = wasm.procurement_health(current, target, deadline_ms, participants, created_ms);
+    return ret;
+}
+
+/**
+ * Export procurements as CSV (RFC 4180: comma-separated, CRLF line endings)
+ * Columns: id, title, city, status, current_amount, target_amount, progress,
+ * participant_count, deadline. Amounts are plain numbers; missing values are empty
+ * @param {string} json_input
+ * @returns {string}
+ */
+export function procurements_to_csv(json_input) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.procurements_to_csv(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Search, filter, sort and paginate procurements in one call (a list view)
+ * Params JSON: { query, filters, sort_by, order, offset, limit }, all optional.
+ * A non-blank query keeps only matching procurements (as `search_procurements`, exact
+ * matching) and, without sort_by, orders them by relevance; filters are those of
+ * `filter_procurements`; sort_by/order are those of `sort_procurements`, otherwise input
+ * order is kept. total counts matches before pagination
+ * Returns JSON: { "total": n, "ids": [...] }
+ * @param {string} json_input
+ * @param {string} params_json
+ * @returns {string}
+ */
+export function query_procurements(json_input, params_json) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(params_json, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.query_procurements(ptr0, len0, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Find procurements similar to the one with `target_id`
+ * Score: 6 for the same category, 2 for the same city, plus up to 3 for title
+ * overlap (Jaccard similarity of title words, normalized as in search), so a shared
+ * category always outranks a shared city alone
+ * limit <= 0 means no limit
+ * Returns JSON array of {id, score} sorted by score (ties by id), without the target;
+ * [] if the target isn't in the list
+ * @param {string} json_input
+ * @param {bigint} target_id
+ * @param {number} limit
+ * @returns {string}
+ */
+export function related_procurements(json_input, target_id, limit) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.related_procurements(ptr0, len0, target_id, limit);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Render a procurement description written in a small Markdown subset to safe HTML
+ * Blocks: "# " / "## " headings (rendered as <h2> / <h3>, below the page title),
+ * "- " bullet lists, and paragraphs separated by blank lines (single newlines become <br>).
+ * Inline: **bold** (or *bold* as in chat), _italic_, and autolinked http(s) URLs.
+ * All text is HTML-escaped first; raw HTML, images and other Markdown stay literal text
+ * @param {string} md
+ * @returns {string}
+ */
+export function render_description_md(md) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(md, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.render_description_md(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Search within messages by text content
+ * Returns JSON array of matching message indices
+ * @param {string} json_input
+ * @param {string} query
+ * @returns {string}
+ */
+export function search_messages(json_input, query) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(query, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.search_messages(ptr0, len0, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Search messages sent by one user, optionally narrowed by text
+ * Returns JSON array of matching message indices; an empty query matches every
+ * message of the user. System messages never match
+ * @param {string} json_input
+ * @param {bigint} user_id
+ * @param {string} query
+ * @returns {string}
+ */
+export function search_messages_by_user(json_input, user_id, query) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(query, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.search_messages_by_user(ptr0, len0, user_id, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Search messages sent within a time window, optionally narrowed by text
+ * Returns JSON array of indices of messages whose `created_at` falls in
+ * `[start_ms, end_ms]` (inclusive); messages without a parseable date are skipped.
+ * An inverted or NaN window yields "[]"
+ * @param {string} json_input
+ * @param {number} start_ms
+ * @param {number} end_ms
+ * @param {string} query
+ * @returns {string}
+ */
+export function search_messages_in_range(json_input, start_ms, end_ms, query) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(query, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.search_messages_in_range(ptr0, len0, start_ms, end_ms, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Fuzzy search procurements by query string
+ * max_distance: Levenshtein distance up to which a query word still matches a title
+ * word when it isn't a substring of the title (0 disables fuzzy matching, 2 is typical);
+ * capped at a third of the query word's length
+ * Matching ignores case and diacritics, and treats "ё" as "е" ("cafe" finds "Café");
+ * "й" stays distinct from "и". Case folding is Unicode's default (locale-specific
+ * rules such as Turkish dotted/dotless I are out of scope)
+ * Scoring weights are described on `procurement_match_score`
+ * offset/limit: page of results to return (limit <= 0 means no limit)
+ * translit: also match Cyrillic and Latin spellings of the same word ("moskva" / "москва")
+ * top_n: when > 0, only the best top_n results are kept (selected with a bounded heap
+ * instead of sorting every match); offset/limit then page within them
+ * Returns JSON array of matching procurement IDs with relevance scores, sorted by relevance;
+ * equal scores are ordered by descending id (newer first), so output is reproducible
+ * @param {string} json_input
+ * @param {string} query
+ * @param {number} max_distance
+ * @param {number} offset
+ * @param {number} limit
+ * @param {boolean} translit
+ * @param {number} top_n
+ * @returns {string}
+ */
+export function search_procurements(json_input, query, max_distance, offset, limit, translit, top_n) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(query, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.search_procurements(ptr0, len0, ptr1, len1, max_distance, offset, limit, translit, top_n);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * `search_procurements` reporting malformed input instead of returning "[]"
+ * Returns JSON: { "ok": true, "data": [...] } or { "ok": false, "error": "..." }
+ * @param {string} json_input
+ * @param {string} query
+ * @param {number} max_distance
+ * @param {number} offset
+ * @param {number} limit
+ * @param {boolean} translit
+ * @param {number} top_n
+ * @returns {string}
+ */
+export function search_procurements_checked(json_input, query, max_distance, offset, limit, translit, top_n) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(query, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.search_procurements_checked(ptr0, len0, ptr1, len1, max_distance, offset, limit, translit, top_n);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Search procurements like `search_procurements` (exact matching, no typo tolerance)
+ * and report where query words matched in the title
+ * Returns JSON array of {id, score, title_ranges}, where title_ranges is a list of
+ * `[start, end)` pairs in char (Unicode scalar) offsets into the title, sorted and merged
+ * @param {string} json_input
+ * @param {string} query
+ * @returns {string}
+ */
+export function search_procurements_highlighted(json_input, query) {
+    let deferred3_0;
+    let deferred3_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(query, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.search_procurements_highlighted(ptr0, len0, ptr1, len1);
+        deferred3_0 = ret[0];
+        deferred3_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+    }
+}
+
+/**
+ * Build a URL slug from text (e.g. a procurement title)
+ * Lowercases, transliterates Cyrillic to Latin, replaces runs of other characters
+ * with a single hyphen and trims hyphens at the ends. Capped at 80 characters
+ * without cutting a transliterated letter in half
+ * @param {string} text
+ * @returns {string}
+ */
+export function slugify(text) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.slugify(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Sort procurements by a specified field
+ * sort_by: "title", "amount", "progress", "deadline", "participants", "created", "urgency"
+ * order: "asc" or "desc"
+ * then_by: tiebreak field from the same list ("created" orders ties newest first,
+ * other fields ascending; "created" is the recommended default). Remaining ties,
+ * or all ties when then_by is empty, are ordered by id ascending.
+ * "deadline" and "created" are compared as parsed timestamps; missing or
+ * unparseable values sort last in either order.
+ * "urgency" puts upcoming deadlines first (soonest first for "asc"), then overdue
+ * ones, then procurements without a (parseable) deadline; these groups keep their
+ * position regardless of order, which only applies within a group.
+ * offset/limit: page of results to return (limit <= 0 means no limit)
+ * Returns JSON array of sorted procurement IDs
+ * @param {string} json_input
+ * @param {string} sort_by
+ * @param {string} order
+ * @param {string} then_by
+ * @param {number} offset
+ * @param {number} limit
+ * @returns {string}
+ */
+export function sort_procurements(json_input, sort_by, order, then_by, offset, limit) {
+    let deferred5_0;
+    let deferred5_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(sort_by, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ptr2 = passStringToWasm0(order, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len2 = WASM_VECTOR_LEN;
+        const ptr3 = passStringToWasm0(then_by, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len3 = WASM_VECTOR_LEN;
+        const ret = wasm.sort_procurements(ptr0, len0, ptr1, len1, ptr2, len2, ptr3, len3, offset, limit);
+        deferred5_0 = ret[0];
+        deferred5_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred5_0, deferred5_1, 1);
+    }
+}
+
+/**
+ * `sort_procurements` reporting malformed input instead of returning "[]"
+ * Returns JSON: { "ok": true, "data": [ids] } or { "ok": false, "error": "..." }
+ * @param {string} json_input
+ * @param {string} sort_by
+ * @param {string} order
+ * @param {string} then_by
+ * @param {number} offset
+ * @param {number} limit
+ * @returns {string}
+ */
+export function sort_procurements_checked(json_input, sort_by, order, then_by, offset, limit) {
+    let deferred5_0;
+    let deferred5_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(sort_by, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ptr2 = passStringToWasm0(order, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len2 = WASM_VECTOR_LEN;
+        const ptr3 = passStringToWasm0(then_by, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len3 = WASM_VECTOR_LEN;
+        const ret = wasm.sort_procurements_checked(ptr0, len0, ptr1, len1, ptr2, len2, ptr3, len3, offset, limit);
+        deferred5_0 = ret[0];
+        deferred5_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred5_0, deferred5_1, 1);
     }
 }
 
 /**
- * Format message text: escape HTML, convert URLs to links, convert newlines to <br>
+ * Character count, word count and estimated reading time of a text
+ * chars counts graphemes, words are Unicode word segments; reading_minutes is
+ * rounded up at ~180 wpm and at least 1 for non-empty text
+ * Returns JSON: { "chars": n, "words": n, "reading_minutes": n }
  * @param {string} text
  * @returns {string}
  */
-export function format_message_text(text) {
+export function text_stats(text) {
     let deferred2_0;
     let deferred2_1;
     try {
         const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
         const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.format_message_text(ptr0, len0);
+        const ret = wasm.text_stats(ptr0, len0);
         deferred2_0 = ret[0];
         deferred2_1 = ret[1];
         return getStringFromWasm0(ret[0], ret[1]);
@@ -158,32 +1773,18 @@ export function format_message_text(text) {
 }
 
 /**
- * Format relative time in Russian
- * @param {number} timestamp_ms
- * @returns {string}
- */
-export function format_relative_time(timestamp_ms) {
-    let deferred1_0;
-    let deferred1_1;
-    try {
-        const ret = wasm.format_relative_time(timestamp_ms);
-        deferred1_0 = ret[0];
-        deferred1_1 = ret[1];
-        return getStringFromWasm0(ret[0], ret[1]);
-    } finally {
-        wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
-    }
-}
-
-/**
- * Generate unique platform user ID for websocket users
+ * Short label for the time left until a deadline
+ * "Завершено" once the deadline has passed, "N часов" under a day (hours rounded up,
+ * so never "0 часов"), otherwise "N дней" (whole days, as in `days_until`), with
+ * Russian plural forms ("1 час", "3 часа", "21 день", "2 дня")
+ * @param {number} deadline_ms
  * @returns {string}
  */
-export function generate_platform_user_id() {
+export function time_until_label(deadline_ms) {
     let deferred1_0;
     let deferred1_1;
     try {
-        const ret = wasm.generate_platform_user_id();
+        const ret = wasm.time_until_label(deadline_ms);
         deferred1_0 = ret[0];
         deferred1_1 = ret[1];
         return getStringFromWasm0(ret[0], ret[1]);
@@ -193,17 +1794,20 @@ export function generate_platform_user_id() {
 }
 
 /**
- * Generate avatar background color based on name (hash-based)
- * @param {string} name
+ * Truncate text to at most `max_chars` graphemes, ending with "…" when cut
+ * The ellipsis counts toward the limit; trailing whitespace before it is dropped.
+ * Text that already fits is returned unchanged; `max_chars <= 0` gives ""
+ * @param {string} text
+ * @param {number} max_chars
  * @returns {string}
  */
-export function get_avatar_color(name) {
+export function truncate_text(text, max_chars) {
     let deferred2_0;
     let deferred2_1;
     try {
-        const ptr0 = passStringToWasm0(name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const ptr0 = passStringToWasm0(text, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
         const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.get_avatar_color(ptr0, len0);
+        const ret = wasm.truncate_text(ptr0, len0, max_chars);
         deferred2_0 = ret[0];
         deferred2_1 = ret[1];
         return getStringFromWasm0(ret[0], ret[1]);
@@ -213,119 +1817,91 @@ export function get_avatar_color(name) {
 }
 
 /**
- * Get initials from first name and last name
- * @param {string} first_name
- * @param {string} last_name
+ * Validate an attachment's MIME type (case-insensitive, parameters after ';' ignored) and size
+ * Returns "" if valid, otherwise an error message
+ * @param {string} mime
+ * @param {number} size_bytes
  * @returns {string}
  */
-export function get_initials(first_name, last_name) {
-    let deferred3_0;
-    let deferred3_1;
+export function validate_attachment(mime, size_bytes) {
+    let deferred2_0;
+    let deferred2_1;
     try {
-        const ptr0 = passStringToWasm0(first_name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const ptr0 = passStringToWasm0(mime, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
         const len0 = WASM_VECTOR_LEN;
-        const ptr1 = passStringToWasm0(last_name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
-        const len1 = WASM_VECTOR_LEN;
-        const ret = wasm.get_initials(ptr0, len0, ptr1, len1);
-        deferred3_0 = ret[0];
-        deferred3_1 = ret[1];
+        const ret = wasm.validate_attachment(ptr0, len0, size_bytes);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
         return getStringFromWasm0(ret[0], ret[1]);
     } finally {
-        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
     }
 }
 
 /**
- * Search within messages by text content
- * Returns JSON array of matching message indices
- * @param {string} json_input
- * @param {string} query
+ * Validate a deadline string from a form (see `parse_deadline` for accepted formats)
+ * Returns "" if valid, otherwise an error message
+ * @param {string} iso
  * @returns {string}
  */
-export function search_messages(json_input, query) {
-    let deferred3_0;
-    let deferred3_1;
+export function validate_deadline_string(iso) {
+    let deferred2_0;
+    let deferred2_1;
     try {
-        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const ptr0 = passStringToWasm0(iso, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
         const len0 = WASM_VECTOR_LEN;
-        const ptr1 = passStringToWasm0(query, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
-        const len1 = WASM_VECTOR_LEN;
-        const ret = wasm.search_messages(ptr0, len0, ptr1, len1);
-        deferred3_0 = ret[0];
-        deferred3_1 = ret[1];
+        const ret = wasm.validate_deadline_string(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
         return getStringFromWasm0(ret[0], ret[1]);
     } finally {
-        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
     }
 }
 
 /**
- * Fuzzy search procurements by query string
- * Returns JSON array of matching procurement IDs with relevance scores, sorted by relevance
- * @param {string} json_input
- * @param {string} query
- * @returns {string}
+ * Validate email format
+ * @param {string} email
+ * @returns {boolean}
  */
-export function search_procurements(json_input, query) {
-    let deferred3_0;
-    let deferred3_1;
-    try {
-        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
-        const len0 = WASM_VECTOR_LEN;
-        const ptr1 = passStringToWasm0(query, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
-        const len1 = WASM_VECTOR_LEN;
-        const ret = wasm.search_procurements(ptr0, len0, ptr1, len1);
-        deferred3_0 = ret[0];
-        deferred3_1 = ret[1];
-        return getStringFromWasm0(ret[0], ret[1]);
-    } finally {
-        wasm.__wbindgen_free(deferred3_0, deferred3_1, 1);
-    }
+export function validate_email(email) {
+    const ptr0 = passStringToWasm0(email, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.validate_email(ptr0, len0);
+    return ret !== 0;
 }
 
 /**
- * Sort procurements by a specified field
- * sort_by: "title", "amount", "progress", "deadline", "participants", "created"
- * order: "asc" or "desc"
- * Returns JSON array of sorted procurement IDs
- * @param {string} json_input
- * @param {string} sort_by
- * @param {string} order
- * @returns {string}
+ * Validate email format strictly (for required fields such as organizer accounts)
+ * A common-sense subset of RFC 5322: no spaces, one '@', no leading/trailing or
+ * consecutive dots in the local part, and a dotted domain of letter/digit/hyphen
+ * labels ending in an alphabetic TLD of at least 2 characters
+ * @param {string} email
+ * @returns {boolean}
  */
-export function sort_procurements(json_input, sort_by, order) {
-    let deferred4_0;
-    let deferred4_1;
-    try {
-        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
-        const len0 = WASM_VECTOR_LEN;
-        const ptr1 = passStringToWasm0(sort_by, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
-        const len1 = WASM_VECTOR_LEN;
-        const ptr2 = passStringToWasm0(order, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
-        const len2 = WASM_VECTOR_LEN;
-        const ret = wasm.sort_procurements(ptr0, len0, ptr1, len1, ptr2, len2);
-        deferred4_0 = ret[0];
-        deferred4_1 = ret[1];
-        return getStringFromWasm0(ret[0], ret[1]);
-    } finally {
-        wasm.__wbindgen_free(deferred4_0, deferred4_1, 1);
-    }
+export function validate_email_strict(email) {
+    const ptr0 = passStringToWasm0(email, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.validate_email_strict(ptr0, len0);
+    return ret !== 0;
 }
 
 /**
- * Validate email format
- * @param {string} email
+ * Validate a Russian INN (taxpayer ID) including its check digits
+ * 10 digits: legal entity (one check digit); 12 digits: individual or sole
+ * proprietor (two check digits). Anything else, including non-digits, is invalid
+ * @param {string} inn
  * @returns {boolean}
  */
-export function validate_email(email) {
-    const ptr0 = passStringToWasm0(email, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+export function validate_inn(inn) {
+    const ptr0 = passStringToWasm0(inn, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
     const len0 = WASM_VECTOR_LEN;
-    const ret = wasm.validate_email(ptr0, len0);
+    const ret = wasm.validate_inn(ptr0, len0);
     return ret !== 0;
 }
 
 /**
- * Validate phone number format (Russian phone number)
+ * Validate phone number format (international, with per-country length checks for CIS codes)
  * @param {string} phone
  * @returns {boolean}
  */
@@ -338,15 +1914,19 @@ export function validate_phone(phone) {
 
 /**
  * Validate procurement form data
+ * min_target: minimum allowed target amount (0.0 only requires a positive amount)
+ * max_days: furthest allowed deadline in days from now (365 recommended, 0 disables the check)
  * Returns JSON string with validation errors (empty object if valid)
  * @param {string} title
  * @param {string} description
  * @param {string} city
  * @param {number} target_amount
  * @param {number} deadline_ms
+ * @param {number} min_target
+ * @param {number} max_days
  * @returns {string}
  */
-export function validate_procurement_form(title, description, city, target_amount, deadline_ms) {
+export function validate_procurement_form(title, description, city, target_amount, deadline_ms, min_target, max_days) {
     let deferred4_0;
     let deferred4_1;
     try {
@@ -356,7 +1936,7 @@ export function validate_procurement_form(title, description, city, target_amoun
         const len1 = WASM_VECTOR_LEN;
         const ptr2 = passStringToWasm0(city, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
         const len2 = WASM_VECTOR_LEN;
-        const ret = wasm.validate_procurement_form(ptr0, len0, ptr1, len1, ptr2, len2, target_amount, deadline_ms);
+        const ret = wasm.validate_procurement_form(ptr0, len0, ptr1, len1, ptr2, len2, target_amount, deadline_ms, min_target, max_days);
         deferred4_0 = ret[0];
         deferred4_1 = ret[1];
         return getStringFromWasm0(ret[0], ret[1]);
@@ -365,58 +1945,277 @@ export function validate_procurement_form(title, description, city, target_amoun
     }
 }
 
+/**
+ * Validate several procurement forms in one call
+ * Input: JSON array of objects with the `validate_procurement_form` parameters as fields
+ * (missing fields count as empty / 0). Returns a JSON array of error objects in input
+ * order (empty object where valid); an item that isn't a form object gets a "form" error
+ * @param {string} json_input
+ * @returns {string}
+ */
+export function validate_procurement_forms(json_input) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.validate_procurement_forms(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Check procurement JSON against the shape the batch functions expect, before processing
+ * Reports missing required fields (id, title) and wrong types (e.g. target_amount as a
+ * string); unknown fields are ignored. An empty array means the input is valid
+ * Returns JSON array of { "index": n, "field": "...", "error": "..." }; input that is
+ * not a JSON array gives a single entry with null index and field
+ * @param {string} json_input
+ * @returns {string}
+ */
+export function validate_procurement_json(json_input) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(json_input, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.validate_procurement_json(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Validate a username: 3-32 chars of `[A-Za-z0-9_]`, starting with a letter,
+ * no consecutive underscores, not reserved
+ * Returns "" if valid, otherwise an error message
+ * @param {string} name
+ * @returns {string}
+ */
+export function validate_username(name) {
+    let deferred2_0;
+    let deferred2_1;
+    try {
+        const ptr0 = passStringToWasm0(name, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.validate_username(ptr0, len0);
+        deferred2_0 = ret[0];
+        deferred2_1 = ret[1];
+        return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+        wasm.__wbindgen_free(deferred2_0, deferred2_1, 1);
+    }
+}
+
+/**
+ * Verify a hex HMAC-SHA256 signature of a payload (e.g. a webhook `X-Signature` header)
+ * Hex may be upper- or lowercase; the comparison is constant-time.
+ * Returns false for malformed or wrong-length signatures
+ * @param {string} payload
+ * @param {string} signature_hex
+ * @param {string} secret
+ * @returns {boolean}
+ */
+export function verify_hmac_sha256(payload, signature_hex, secret) {
+    const ptr0 = passStringToWasm0(payload, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ptr1 = passStringToWasm0(signature_hex, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ptr2 = passStringToWasm0(secret, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len2 = WASM_VECTOR_LEN;
+    const ret = wasm.verify_hmac_sha256(ptr0, len0, ptr1, len1, ptr2, len2);
+    return ret !== 0;
+}
+
+/**
+ * Verify Telegram Mini App `initData` against the bot token
+ * Implements the documented scheme: the data-check-string is every field except
+ * `hash`, sorted by key as `key=value` lines; secret = HMAC-SHA256("WebAppData", bot_token)
+ * and `hash` must equal hex(HMAC-SHA256(secret, data-check-string)).
+ * Returns false on a missing or mismatching hash, or malformed input
+ * @param {string} init_data
+ * @param {string} bot_token
+ * @returns {boolean}
+ */
+export function verify_telegram_init_data(init_data, bot_token) {
+    const ptr0 = passStringToWasm0(init_data, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ptr1 = passStringToWasm0(bot_token, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ret = wasm.verify_telegram_init_data(ptr0, len0, ptr1, len1);
+    return ret !== 0;
+}
+
 function __wbg_get_imports() {
     const import0 = {
         __proto__: null,
-        __wbg___wbindgen_throw_be289d5034ed271b: function(arg0, arg1) {
-            throw new Error(getStringFromWasm0(arg0, arg1));
+        __wbg_Error_8c4e43fe74559d73: function(arg0, arg1) {
+            const ret = Error(getStringFromWasm0(arg0, arg1));
+            return ret;
         },
-        __wbg_getDate_db46eca87d2b4907: function(arg0) {
-            const ret = arg0.getDate();
+        __wbg___wbindgen_is_function_0095a73b8b156f76: function(arg0) {
+            const ret = typeof(arg0) === 'function';
             return ret;
         },
-        __wbg_getFullYear_30ddf266b7612036: function(arg0) {
-            const ret = arg0.getFullYear();
+        __wbg___wbindgen_is_object_5ae8e5880f2c1fbd: function(arg0) {
+            const val = arg0;
+            const ret = typeof(val) === 'object' && val !== null;
             return ret;
         },
-        __wbg_getHours_e02e88722301c418: function(arg0) {
-            const ret = arg0.getHours();
+        __wbg___wbindgen_is_string_cd444516edc5b180: function(arg0) {
+            const ret = typeof(arg0) === 'string';
             return ret;
         },
-        __wbg_getMinutes_eb73ceeb8231f6f4: function(arg0) {
-            const ret = arg0.getMinutes();
+        __wbg___wbindgen_is_undefined_9e4d92534c42d778: function(arg0) {
+            const ret = arg0 === undefined;
             return ret;
         },
-        __wbg_getMonth_4af888e76b42bb51: function(arg0) {
-            const ret = arg0.getMonth();
+        __wbg___wbindgen_throw_be289d5034ed271b: function(arg0, arg1) {
+            throw new Error(getStringFromWasm0(arg0, arg1));
+        },
+        __wbg_call_389efe28435a9388: function() { return handleError(function (arg0, arg1) {
+            const ret = arg0.call(arg1);
+            return ret;
+        }, arguments); },
+        __wbg_call_4708e0c13bdc8e95: function() { return handleError(function (arg0, arg1, arg2) {
+            const ret = arg0.call(arg1, arg2);
+            return ret;
+        }, arguments); },
+        __wbg_crypto_86f2631e91b51511: function(arg0) {
+            const ret = arg0.crypto;
             return ret;
         },
+        __wbg_getRandomValues_b3f15fcbfabb0f8b: function() { return handleError(function (arg0, arg1) {
+            arg0.getRandomValues(arg1);
+        }, arguments); },
         __wbg_getTime_1e3cd1391c5c3995: function(arg0) {
             const ret = arg0.getTime();
             return ret;
         },
+        __wbg_length_32ed9a279acd054c: function(arg0) {
+            const ret = arg0.length;
+            return ret;
+        },
+        __wbg_msCrypto_d562bbe83e0d4b91: function(arg0) {
+            const ret = arg0.msCrypto;
+            return ret;
+        },
         __wbg_new_245cd5c49157e602: function(arg0) {
             const ret = new Date(arg0);
             return ret;
         },
+        __wbg_new_361308b2356cecd0: function() {
+            const ret = new Object();
+            return ret;
+        },
+        __wbg_new_3eb36ae241fe6f44: function() {
+            const ret = new Array();
+            return ret;
+        },
+        __wbg_new_dca287b076112a51: function() {
+            const ret = new Map();
+            return ret;
+        },
+        __wbg_new_no_args_1c7c842f08d00ebb: function(arg0, arg1) {
+            const ret = new Function(getStringFromWasm0(arg0, arg1));
+            return ret;
+        },
+        __wbg_new_with_length_a2c39cbe88fd8ff1: function(arg0) {
+            const ret = new Uint8Array(arg0 >>> 0);
+            return ret;
+        },
+        __wbg_node_e1f24f89a7336c2e: function(arg0) {
+            const ret = arg0.node;
+            return ret;
+        },
         __wbg_now_a3af9a2f4bbaa4d1: function() {
             const ret = Date.now();
             return ret;
         },
+        __wbg_process_3975fd6c72f520aa: function(arg0) {
+            const ret = arg0.process;
+            return ret;
+        },
+        __wbg_prototypesetcall_bdcdcc5842e4d77d: function(arg0, arg1, arg2) {
+            Uint8Array.prototype.set.call(getArrayU8FromWasm0(arg0, arg1), arg2);
+        },
+        __wbg_randomFillSync_f8c153b79f285817: function() { return handleError(function (arg0, arg1) {
+            arg0.randomFillSync(arg1);
+        }, arguments); },
         __wbg_random_912284dbf636f269: function() {
             const ret = Math.random();
             return ret;
         },
+        __wbg_require_b74f47fc2d022fd6: function() { return handleError(function () {
+            const ret = module.require;
+            return ret;
+        }, arguments); },
+        __wbg_set_1eb0999cf5d27fc8: function(arg0, arg1, arg2) {
+            const ret = arg0.set(arg1, arg2);
+            return ret;
+        },
+        __wbg_set_3f1d0b984ed272ed: function(arg0, arg1, arg2) {
+            arg0[arg1] = arg2;
+        },
+        __wbg_set_f43e577aea94465b: function(arg0, arg1, arg2) {
+            arg0[arg1 >>> 0] = arg2;
+        },
+        __wbg_static_accessor_GLOBAL_12837167ad935116: function() {
+            const ret = typeof global === 'undefined' ? null : global;
+            return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
+        },
+        __wbg_static_accessor_GLOBAL_THIS_e628e89ab3b1c95f: function() {
+            const ret = typeof globalThis === 'undefined' ? null : globalThis;
+            return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
+        },
+        __wbg_static_accessor_SELF_a621d3dfbb60d0ce: function() {
+            const ret = typeof self === 'undefined' ? null : self;
+            return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
+        },
+        __wbg_static_accessor_WINDOW_f8727f0cf888e0bd: function() {
+            const ret = typeof window === 'undefined' ? null : window;
+            return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
+        },
+        __wbg_subarray_a96e1fef17ed23cb: function(arg0, arg1, arg2) {
+            const ret = arg0.subarray(arg1 >>> 0, arg2 >>> 0);
+            return ret;
+        },
+        __wbg_versions_4e31226f5e8dc909: function(arg0) {
+            const ret = arg0.versions;
+            return ret;
+        },
         __wbindgen_cast_0000000000000001: function(arg0) {
             // Cast intrinsic for `F64 -> Externref`.
             const ret = arg0;
             return ret;
         },
-        __wbindgen_cast_0000000000000002: function(arg0, arg1) {
+        __wbindgen_cast_0000000000000002: function(arg0) {
+            // Cast intrinsic for `I64 -> Externref`.
+            const ret = arg0;
+            return ret;
+        },
+        __wbindgen_cast_0000000000000003: function(arg0, arg1) {
+            // Cast intrinsic for `Ref(Slice(U8)) -> NamedExternref("Uint8Array")`.
+            const ret = getArrayU8FromWasm0(arg0, arg1);
+            return ret;
+        },
+        __wbindgen_cast_0000000000000004: function(arg0, arg1) {
             // Cast intrinsic for `Ref(String) -> Externref`.
             const ret = getStringFromWasm0(arg0, arg1);
             return ret;
         },
+        __wbindgen_cast_0000000000000005: function(arg0) {
+            // Cast intrinsic for `U64 -> Externref`.
+            const ret = BigInt.asUintN(64, arg0);
+            return ret;
+        },
         __wbindgen_init_externref_table: function() {
             const table = wasm.__wbindgen_externrefs;
             const offset = table.grow(4);
@@ -433,6 +2232,21 @@ function __wbg_get_imports() {
     };
 }
 
+const RateLimiterFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_ratelimiter_free(ptr >>> 0, 1));
+
+function addToExternrefTable0(obj) {
+    const idx = wasm.__externref_table_alloc();
+    wasm.__wbindgen_externrefs.set(idx, obj);
+    return idx;
+}
+
+function getArrayU8FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getUint8ArrayMemory0().subarray(ptr / 1, ptr / 1 + len);
+}
+
 function getStringFromWasm0(ptr, len) {
     ptr = ptr >>> 0;
     return decodeText(ptr, len);
@@ -446,6 +2260,26 @@ function getUint8ArrayMemory0() {
     return cachedUint8ArrayMemory0;
 }
 
+function handleError(f, args) {
+    try {
+        return f.apply(this, args);
+    } catch (e) {
+        const idx = addToExternrefTable0(e);
+        wasm.__wbindgen_exn_store(idx);
+    }
+}
+
+function isLikeNone(x) {
+    return x === undefined || x === null;
+}
+
+function passArray8ToWasm0(arg, malloc) {
+    const ptr = malloc(arg.length * 1, 1) >>> 0;
+    getUint8ArrayMemory0().set(arg, ptr / 1);
+    WASM_VECTOR_LEN = arg.length;
+    return ptr;
+}
+
 function passStringToWasm0(arg, malloc, realloc) {
     if (realloc === undefined) {
         const buf = cachedTextEncoder.encode(arg);
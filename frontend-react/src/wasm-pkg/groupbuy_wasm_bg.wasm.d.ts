@@ -1,25 +1,104 @@
 /* tslint:disable */
 /* eslint-disable */
 export const memory: WebAssembly.Memory;
+export const __wbg_ratelimiter_free: (a: number, b: number) => void;
 export const aggregate_procurement_stats: (a: number, b: number) => [number, number];
-export const batch_process_messages: (a: number, b: number, c: bigint) => [number, number];
+export const aggregate_procurement_stats_checked: (a: number, b: number) => [number, number];
+export const aggregate_procurement_stats_js: (a: number, b: number) => any;
+export const avatar_spec: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+export const base64url_decode: (a: number, b: number) => [number, number];
+export const base64url_encode: (a: number, b: number) => [number, number];
+export const batch_process_messages: (a: number, b: number, c: bigint, d: number) => [number, number];
+export const batch_process_messages_checked: (a: number, b: number, c: bigint, d: number) => [number, number];
 export const batch_process_procurements: (a: number, b: number) => [number, number];
-export const benchmark_batch_processing: (a: number) => number;
+export const batch_process_procurements_checked: (a: number, b: number) => [number, number];
+export const batch_process_procurements_chunk: (a: number, b: number, c: number, d: number) => [number, number];
+export const batch_process_procurements_msgpack: (a: number, b: number) => [number, number];
+export const benchmark_batch_processing: (a: number, b: number) => [number, number];
+export const build_payment_payload: (a: bigint, b: number, c: number, d: number, e: number, f: number) => [number, number];
+export const build_share_link: (a: number, b: number, c: bigint) => [number, number];
 export const calculate_progress: (a: number, b: number) => number;
+export const calculate_progress_f64: (a: number, b: number) => number;
+export const canonicalize_category: (a: number, b: number) => [number, number];
+export const censor_profanity: (a: number, b: number) => [number, number];
+export const contains_profanity: (a: number, b: number) => number;
+export const contrast_ratio: (a: number, b: number, c: number, d: number) => number;
+export const deadline_phrase: (a: number) => [number, number];
+export const diff_procurements: (a: number, b: number, c: number, d: number) => [number, number];
 export const escape_html: (a: number, b: number) => [number, number];
+export const filter_procurements: (a: number, b: number, c: number, d: number) => [number, number];
+export const find_duplicate_procurements: (a: number, b: number) => [number, number];
+export const fold_diacritics: (a: number, b: number, c: number) => [number, number];
+export const format_count_ru: (a: number, b: number, c: number) => [number, number];
 export const format_currency: (a: number) => [number, number];
+export const format_file_size: (a: number) => [number, number];
 export const format_message_text: (a: number, b: number) => [number, number];
+export const format_money: (a: number, b: number, c: number) => [number, number];
+export const format_ordinal_ru: (a: number, b: number, c: number) => [number, number];
+export const format_phone: (a: number, b: number) => [number, number];
 export const format_relative_time: (a: number) => [number, number];
+export const format_relative_time_full: (a: number) => [number, number];
 export const generate_platform_user_id: () => [number, number];
 export const get_avatar_color: (a: number, b: number) => [number, number];
+export const get_avatar_color_themed: (a: number, b: number, c: number, d: number) => [number, number];
 export const get_initials: (a: number, b: number, c: number, d: number) => [number, number];
+export const get_initials_v2: (a: number, b: number, c: number, d: number) => [number, number];
+export const gzip_compress: (a: number, b: number) => [number, number];
+export const gzip_decompress: (a: number, b: number) => [number, number];
+export const highlight_terms: (a: number, b: number, c: number, d: number) => [number, number];
+export const is_disposable_email: (a: number, b: number) => number;
+export const levenshtein: (a: number, b: number, c: number, d: number) => number;
+export const merge_procurements: (a: number, b: number, c: number, d: number) => [number, number];
+export const message_length: (a: number, b: number) => number;
+export const message_length_ok: (a: number, b: number, c: number) => number;
+export const message_preview: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const message_spam_score: (a: number, b: number) => number;
+export const message_text_to_plain: (a: number, b: number) => [number, number];
+export const messages_by_date: (a: number, b: number, c: number) => [number, number];
+export const normalize_phone_e164: (a: number, b: number) => [number, number];
+export const normalize_username: (a: number, b: number) => [number, number];
+export const parse_deadline: (a: number, b: number) => number;
+export const parse_procurements_csv: (a: number, b: number) => [number, number];
+export const parse_start_param: (a: number, b: number) => bigint;
+export const password_strength: (a: number, b: number) => [number, number];
+export const pick_readable_text: (a: number, b: number) => [number, number];
+export const procurement_fingerprint: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
+export const procurement_health: (a: number, b: number, c: number, d: number, e: number) => number;
+export const procurements_to_csv: (a: number, b: number) => [number, number];
+export const query_procurements: (a: number, b: number, c: number, d: number) => [number, number];
+export const ratelimiter_new: (a: number, b: number) => number;
+export const ratelimiter_tokens_remaining: (a: number) => number;
+export const ratelimiter_try_acquire: (a: number) => number;
+export const related_procurements: (a: number, b: number, c: bigint, d: number) => [number, number];
+export const render_description_md: (a: number, b: number) => [number, number];
 export const search_messages: (a: number, b: number, c: number, d: number) => [number, number];
-export const search_procurements: (a: number, b: number, c: number, d: number) => [number, number];
-export const sort_procurements: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+export const search_messages_by_user: (a: number, b: number, c: bigint, d: number, e: number) => [number, number];
+export const search_messages_in_range: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+export const search_procurements: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => [number, number];
+export const search_procurements_checked: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => [number, number];
+export const search_procurements_highlighted: (a: number, b: number, c: number, d: number) => [number, number];
+export const slugify: (a: number, b: number) => [number, number];
+export const sort_procurements: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => [number, number];
+export const sort_procurements_checked: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => [number, number];
+export const text_stats: (a: number, b: number) => [number, number];
+export const time_until_label: (a: number) => [number, number];
+export const truncate_text: (a: number, b: number, c: number) => [number, number];
+export const validate_attachment: (a: number, b: number, c: number) => [number, number];
+export const validate_deadline_string: (a: number, b: number) => [number, number];
 export const validate_email: (a: number, b: number) => number;
+export const validate_email_strict: (a: number, b: number) => number;
+export const validate_inn: (a: number, b: number) => number;
 export const validate_phone: (a: number, b: number) => number;
-export const validate_procurement_form: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => [number, number];
+export const validate_procurement_form: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => [number, number];
+export const validate_procurement_forms: (a: number, b: number) => [number, number];
+export const validate_procurement_json: (a: number, b: number) => [number, number];
+export const validate_username: (a: number, b: number) => [number, number];
+export const verify_hmac_sha256: (a: number, b: number, c: number, d: number, e: number, f: number) => number;
+export const verify_telegram_init_data: (a: number, b: number, c: number, d: number) => number;
 export const days_until: (a: number) => number;
+export const calculate_progress_uncapped: (a: number, b: number) => number;
+export const __wbindgen_exn_store: (a: number) => void;
+export const __externref_table_alloc: () => number;
 export const __wbindgen_externrefs: WebAssembly.Table;
 export const __wbindgen_malloc: (a: number, b: number) => number;
 export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
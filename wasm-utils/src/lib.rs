@@ -1,20 +1,69 @@
+use std::borrow::Cow;
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_normalization::UnicodeNormalization;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use once_cell::sync::Lazy;
+use regex::Regex;
 
 // ──────────────────────────────────────────────
 // Validation functions
 // ──────────────────────────────────────────────
 
-/// Validate phone number format (Russian phone number)
+/// Supported country calling codes with the expected national number length
+/// (Russia/Kazakhstan, Belarus, Ukraine, Kyrgyzstan, Uzbekistan)
+const PHONE_COUNTRY_CODES: [(&str, usize); 5] = [
+    ("7", 10),
+    ("375", 9),
+    ("380", 9),
+    ("996", 9),
+    ("998", 9),
+];
+
+/// Validate phone number format (international, with per-country length checks for CIS codes)
 #[wasm_bindgen]
 pub fn validate_phone(phone: &str) -> bool {
     let cleaned: String = phone.chars().filter(|c| c.is_ascii_digit() || *c == '+').collect();
     if cleaned.is_empty() {
         return true; // phone is optional
     }
-    let re_pattern = cleaned.starts_with('+') && cleaned.len() >= 11 && cleaned.len() <= 16;
-    let digits_only = cleaned.trim_start_matches('+');
-    re_pattern && digits_only.chars().all(|c| c.is_ascii_digit())
+    // '+' is only allowed as the very first character
+    let digits = match cleaned.strip_prefix('+') {
+        Some(rest) if !rest.contains('+') => rest,
+        _ => return false,
+    };
+
+    for (code, national_len) in PHONE_COUNTRY_CODES.iter() {
+        if let Some(national) = digits.strip_prefix(code) {
+            return national.len() == *national_len;
+        }
+    }
+
+    // Unknown country: generic E.164 rules (no leading zero, 10-15 digits total)
+    !digits.starts_with('0') && digits.len() >= 10 && digits.len() <= 15
+}
+
+/// Normalize a phone number to E.164 storage form (`+` followed by digits, no spaces)
+/// A Russian trunk prefix is replaced (8XXXXXXXXXX -> +7XXXXXXXXXX); numbers without
+/// `+` are otherwise read as already including the country code. Returns "" when the
+/// result would not pass `validate_phone` or the input has no digits
+#[wasm_bindgen]
+pub fn normalize_phone_e164(phone: &str) -> String {
+    let cleaned: String = phone.chars().filter(|c| c.is_ascii_digit() || *c == '+').collect();
+    let digits = match cleaned.strip_prefix('+') {
+        Some(rest) => rest.to_string(),
+        None if cleaned.len() == 11 && cleaned.starts_with('8') => format!("7{}", &cleaned[1..]),
+        None => cleaned,
+    };
+    if digits.is_empty() || digits.contains('+') {
+        return String::new();
+    }
+
+    let normalized = format!("+{}", digits);
+    if validate_phone(&normalized) { normalized } else { String::new() }
 }
 
 /// Validate email format
@@ -32,47 +81,400 @@ pub fn validate_email(email: &str) -> bool {
     !local.is_empty() && domain.contains('.') && domain.len() > 2
 }
 
+/// Validate email format strictly (for required fields such as organizer accounts)
+/// A common-sense subset of RFC 5322: no spaces, one '@', no leading/trailing or
+/// consecutive dots in the local part, and a dotted domain of letter/digit/hyphen
+/// labels ending in an alphabetic TLD of at least 2 characters
+#[wasm_bindgen]
+pub fn validate_email_strict(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+    if email.chars().any(char::is_whitespace) || domain.contains('@') {
+        return false;
+    }
+
+    if local.is_empty() || local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return false;
+    }
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    let valid_label = |label: &&str| {
+        !label.is_empty()
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+    };
+    let tld = labels.last().copied().unwrap_or("");
+    labels.len() >= 2
+        && labels.iter().all(valid_label)
+        && tld.chars().count() >= 2
+        && tld.chars().all(char::is_alphabetic)
+}
+
+/// Known disposable email domains; must stay sorted for binary search
+const DISPOSABLE_EMAIL_DOMAINS: [&str; 24] = [
+    "10minutemail.com",
+    "10minutemail.net",
+    "20minutemail.com",
+    "discard.email",
+    "dispostable.com",
+    "dropmail.me",
+    "emailondeck.com",
+    "fakeinbox.com",
+    "getnada.com",
+    "guerrillamail.biz",
+    "guerrillamail.com",
+    "guerrillamail.de",
+    "guerrillamail.net",
+    "guerrillamail.org",
+    "maildrop.cc",
+    "mailinator.com",
+    "mailinator.net",
+    "mintemail.com",
+    "mohmal.com",
+    "sharklasers.com",
+    "temp-mail.org",
+    "tempmail.com",
+    "throwawaymail.com",
+    "yopmail.com",
+];
+
+/// Check whether an email uses a known disposable (throwaway) provider
+/// Case-insensitive; subdomains of listed providers also count
+#[wasm_bindgen]
+pub fn is_disposable_email(email: &str) -> bool {
+    let Some((_, domain)) = email.trim().rsplit_once('@') else {
+        return false;
+    };
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+
+    // Check the domain itself, then each parent domain
+    let mut candidate = domain.as_str();
+    loop {
+        if DISPOSABLE_EMAIL_DOMAINS.binary_search(&candidate).is_ok() {
+            return true;
+        }
+        match candidate.split_once('.') {
+            Some((_, parent)) if parent.contains('.') => candidate = parent,
+            _ => return false,
+        }
+    }
+}
+
+/// Usernames that can't be registered (compared case-insensitively)
+const RESERVED_USERNAMES: [&str; 4] = ["admin", "bot", "root", "support"];
+
+/// Validate a username: 3-32 chars of `[A-Za-z0-9_]`, starting with a letter,
+/// no consecutive underscores, not reserved
+/// Returns "" if valid, otherwise an error message
+#[wasm_bindgen]
+pub fn validate_username(name: &str) -> String {
+    let len = name.chars().count();
+    if !(3..=32).contains(&len) {
+        "Имя пользователя должно содержать от 3 до 32 символов".to_string()
+    } else if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        "Имя пользователя должно начинаться с латинской буквы".to_string()
+    } else if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        "Допустимы только латинские буквы, цифры и _".to_string()
+    } else if name.contains("__") {
+        "Имя пользователя не может содержать два _ подряд".to_string()
+    } else if RESERVED_USERNAMES.contains(&name.to_ascii_lowercase().as_str()) {
+        "Это имя пользователя зарезервировано".to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// Normalize a username for storage and comparison (trimmed, lowercase)
+#[wasm_bindgen]
+pub fn normalize_username(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Frequently used passwords rejected by `password_strength` (compared lowercased)
+const COMMON_PASSWORDS: [&str; 20] = [
+    "000000", "111111", "123123", "12345", "123456", "1234567", "12345678", "123456789",
+    "1q2w3e4r", "654321", "abc123", "admin", "iloveyou", "password", "password1",
+    "qwerty", "qwerty123", "qwertyuiop", "welcome", "йцукен",
+];
+
+/// Estimate password strength for a live registration meter
+/// score (0-4): +1 for 8+ chars, +1 for 12+ chars, +1 for 3+ character classes,
+/// +1 for all 4 classes; -1 for a run of 3+ repeated or sequential characters
+/// ("aaa", "abc", "321"); common passwords always score 0.
+/// The password is only inspected, never stored or logged
+/// Returns JSON: { score, length_ok, has_upper, has_lower, has_digit, has_symbol, suggestions }
+#[wasm_bindgen]
+pub fn password_strength(pw: &str) -> String {
+    let chars: Vec<char> = pw.chars().collect();
+    let length_ok = chars.len() >= 8;
+    let has_upper = chars.iter().any(|c| c.is_uppercase());
+    let has_lower = chars.iter().any(|c| c.is_lowercase());
+    let has_digit = chars.iter().any(|c| c.is_ascii_digit());
+    let has_symbol = chars.iter().any(|c| !c.is_alphanumeric() && !c.is_whitespace());
+    let classes = [has_upper, has_lower, has_digit, has_symbol].iter().filter(|&&b| b).count();
+
+    let is_common = COMMON_PASSWORDS.contains(&pw.to_lowercase().as_str());
+    let has_repeat = chars.windows(3).any(|w| w[0] == w[1] && w[1] == w[2]);
+    let has_sequence = chars.windows(3).any(|w| {
+        let (a, b, c) = (w[0] as i64, w[1] as i64, w[2] as i64);
+        w.iter().all(|ch| ch.is_alphanumeric()) && b - a == c - b && (b - a).abs() == 1
+    });
+
+    let mut score: i32 = 0;
+    if length_ok {
+        score += 1;
+    }
+    if chars.len() >= 12 {
+        score += 1;
+    }
+    if classes >= 3 {
+        score += 1;
+    }
+    if classes == 4 {
+        score += 1;
+    }
+    if has_repeat || has_sequence {
+        score -= 1;
+    }
+    if is_common {
+        score = 0;
+    }
+
+    let mut suggestions: Vec<&str> = Vec::new();
+    if chars.is_empty() {
+        suggestions.push("Введите пароль");
+    } else {
+        if is_common {
+            suggestions.push("Этот пароль слишком распространён");
+        }
+        if !length_ok {
+            suggestions.push("Используйте не меньше 8 символов");
+        }
+        if !has_upper {
+            suggestions.push("Добавьте заглавные буквы");
+        }
+        if !has_lower {
+            suggestions.push("Добавьте строчные буквы");
+        }
+        if !has_digit {
+            suggestions.push("Добавьте цифры");
+        }
+        if !has_symbol {
+            suggestions.push("Добавьте символы, например ! или %");
+        }
+        if has_repeat {
+            suggestions.push("Избегайте повторяющихся символов");
+        }
+        if has_sequence {
+            suggestions.push("Избегайте последовательностей вроде abc или 123");
+        }
+    }
+
+    serde_json::json!({
+        "score": score.clamp(0, 4),
+        "length_ok": length_ok,
+        "has_upper": has_upper,
+        "has_lower": has_lower,
+        "has_digit": has_digit,
+        "has_symbol": has_symbol,
+        "suggestions": suggestions,
+    }).to_string()
+}
+
+/// Validate a Russian INN (taxpayer ID) including its check digits
+/// 10 digits: legal entity (one check digit); 12 digits: individual or sole
+/// proprietor (two check digits). Anything else, including non-digits, is invalid
+#[wasm_bindgen]
+pub fn validate_inn(inn: &str) -> bool {
+    const WEIGHTS_10: [u32; 9] = [2, 4, 10, 3, 5, 9, 4, 6, 8];
+    const WEIGHTS_11: [u32; 10] = [7, 2, 4, 10, 3, 5, 9, 4, 6, 8];
+    const WEIGHTS_12: [u32; 11] = [3, 7, 2, 4, 10, 3, 5, 9, 4, 6, 8];
+
+    let digits: Vec<u32> = match inn.chars().map(|c| c.to_digit(10)).collect() {
+        Some(digits) => digits,
+        None => return false,
+    };
+    let check_digit = |weights: &[u32]| {
+        weights.iter().zip(&digits).map(|(w, d)| w * d).sum::<u32>() % 11 % 10
+    };
+
+    match digits.len() {
+        10 => check_digit(&WEIGHTS_10) == digits[9],
+        12 => check_digit(&WEIGHTS_11) == digits[10] && check_digit(&WEIGHTS_12) == digits[11],
+        _ => false,
+    }
+}
+
 /// Validate procurement form data
+/// min_target: minimum allowed target amount (0.0 only requires a positive amount)
+/// max_days: furthest allowed deadline in days from now (365 recommended, 0 disables the check)
 /// Returns JSON string with validation errors (empty object if valid)
 #[wasm_bindgen]
-pub fn validate_procurement_form(title: &str, description: &str, city: &str, target_amount: f64, deadline_ms: f64) -> String {
-    let mut errors: Vec<(&str, &str)> = Vec::new();
+pub fn validate_procurement_form(title: &str, description: &str, city: &str, target_amount: f64, deadline_ms: f64, min_target: f64, max_days: i32) -> String {
+    validate_procurement_form_at(title, description, city, target_amount, deadline_ms, min_target, max_days, js_sys::Date::now())
+}
+
+/// Internal helper: validate procurement form against an explicit current time
+#[allow(clippy::too_many_arguments)]
+fn validate_procurement_form_at(title: &str, description: &str, city: &str, target_amount: f64, deadline_ms: f64, min_target: f64, max_days: i32, now_ms: f64) -> String {
+    let errors = procurement_form_errors(title, description, city, target_amount, deadline_ms, min_target, max_days, now_ms);
+    serde_json::to_string(&errors).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Internal helper: procurement form errors keyed by field name
+#[allow(clippy::too_many_arguments)]
+fn procurement_form_errors(title: &str, description: &str, city: &str, target_amount: f64, deadline_ms: f64, min_target: f64, max_days: i32, now_ms: f64) -> serde_json::Map<String, serde_json::Value> {
+    let mut errors = serde_json::Map::new();
+    let mut add_error = |key: &str, msg: &str| {
+        errors.insert(key.to_string(), serde_json::Value::String(msg.to_string()));
+    };
 
     if title.trim().is_empty() {
-        errors.push(("title", "Название обязательно"));
-    } else if title.len() > 200 {
-        errors.push(("title", "Название не должно превышать 200 символов"));
+        add_error("title", "Название обязательно");
+    } else if title.chars().count() > 200 {
+        add_error("title", "Название не должно превышать 200 символов");
     }
 
     if description.trim().is_empty() {
-        errors.push(("description", "Описание обязательно"));
+        add_error("description", "Описание обязательно");
+    } else if description.chars().count() > 5000 {
+        add_error("description", "Описание не должно превышать 5000 символов");
     }
 
     if city.trim().is_empty() {
-        errors.push(("city", "Город обязателен"));
+        add_error("city", "Город обязателен");
     }
 
     if target_amount <= 0.0 {
-        errors.push(("target_amount", "Целевая сумма должна быть положительной"));
+        add_error("target_amount", "Целевая сумма должна быть положительной");
+    } else if min_target > 0.0 && target_amount < min_target {
+        add_error("target_amount", &format!("Целевая сумма должна быть не меньше {}", format_currency_value(min_target)));
     }
 
-    let now_ms = js_sys::Date::now();
     if deadline_ms <= now_ms {
-        errors.push(("deadline", "Дедлайн должен быть в будущем"));
+        add_error("deadline", "Дедлайн должен быть в будущем");
+    } else if max_days > 0 && deadline_ms > now_ms + max_days as f64 * 86_400_000.0 {
+        add_error("deadline", &format!("Дедлайн не может быть позже чем через {} дн.", max_days));
     }
 
-    if errors.is_empty() {
-        "{}".to_string()
-    } else {
-        let mut result = String::from("{");
-        for (i, (key, msg)) in errors.iter().enumerate() {
-            if i > 0 {
-                result.push(',');
-            }
-            result.push_str(&format!("\"{}\":\"{}\"", key, msg));
+    errors
+}
+
+/// MIME types accepted for chat attachments
+const ALLOWED_ATTACHMENT_MIMES: [&str; 6] = [
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "video/mp4",
+    "video/quicktime",
+];
+
+/// Maximum attachment size in bytes (25 MiB)
+const MAX_ATTACHMENT_BYTES: f64 = 25.0 * 1024.0 * 1024.0;
+
+/// Validate an attachment's MIME type (case-insensitive, parameters after ';' ignored) and size
+/// Returns "" if valid, otherwise an error message
+#[wasm_bindgen]
+pub fn validate_attachment(mime: &str, size_bytes: f64) -> String {
+    let essence = mime.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+    if !ALLOWED_ATTACHMENT_MIMES.contains(&essence.as_str()) {
+        return "Недопустимый формат".to_string();
+    }
+    if size_bytes.is_nan() || size_bytes < 0.0 {
+        return "Некорректный размер файла".to_string();
+    }
+    if size_bytes > MAX_ATTACHMENT_BYTES {
+        return "Файл слишком большой".to_string();
+    }
+    String::new()
+}
+
+/// Procurement form fields for `validate_procurement_forms` (same as `validate_procurement_form`)
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ProcurementForm {
+    title: String,
+    description: String,
+    city: String,
+    target_amount: f64,
+    deadline_ms: f64,
+    min_target: f64,
+    max_days: i32,
+}
+
+/// Validate several procurement forms in one call
+/// Input: JSON array of objects with the `validate_procurement_form` parameters as fields
+/// (missing fields count as empty / 0). Returns a JSON array of error objects in input
+/// order (empty object where valid); an item that isn't a form object gets a "form" error
+#[wasm_bindgen]
+pub fn validate_procurement_forms(json_input: &str) -> String {
+    validate_procurement_forms_at(json_input, js_sys::Date::now())
+}
+
+/// Internal helper: validate procurement forms against an explicit current time
+fn validate_procurement_forms_at(json_input: &str, now_ms: f64) -> String {
+    let items: Vec<serde_json::Value> = match serde_json::from_str(json_input) {
+        Ok(items) => items,
+        Err(_) => return "[]".to_string(),
+    };
+
+    let results: Vec<serde_json::Value> = items.into_iter().map(|item| {
+        match serde_json::from_value::<ProcurementForm>(item) {
+            Ok(form) => serde_json::Value::Object(procurement_form_errors(
+                &form.title, &form.description, &form.city, form.target_amount,
+                form.deadline_ms, form.min_target, form.max_days, now_ms,
+            )),
+            Err(_) => serde_json::json!({ "form": "Неверный формат формы" }),
         }
-        result.push('}');
-        result
+    }).collect();
+
+    serde_json::to_string(&results).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Parse a deadline from a form value to milliseconds since the epoch
+/// Accepts `YYYY-MM-DD` (end of that day, 23:59:59.999 UTC) or a full ISO timestamp.
+/// Returns NaN for unparseable input
+#[wasm_bindgen]
+pub fn parse_deadline(iso: &str) -> f64 {
+    let iso = iso.trim();
+    match parse_iso_ms(iso) {
+        Some(ms) if iso.len() == 10 => ms + 86_400_000.0 - 1.0,
+        Some(ms) => ms,
+        None => f64::NAN,
+    }
+}
+
+/// Validate a deadline string from a form (see `parse_deadline` for accepted formats)
+/// Returns "" if valid, otherwise an error message
+#[wasm_bindgen]
+pub fn validate_deadline_string(iso: &str) -> String {
+    validate_deadline_string_at(iso, js_sys::Date::now())
+}
+
+/// Internal helper: validate a deadline string against an explicit current time
+fn validate_deadline_string_at(iso: &str, now_ms: f64) -> String {
+    let deadline_ms = parse_deadline(iso);
+    if let Some(error) = deadline_format_error(iso, deadline_ms) {
+        error.to_string()
+    } else if deadline_ms <= now_ms {
+        "Дедлайн должен быть в будущем".to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// Internal helper: error for a deadline string that is empty or didn't parse
+/// (`deadline_ms` is its `parse_deadline` result), None otherwise
+fn deadline_format_error(iso: &str, deadline_ms: f64) -> Option<&'static str> {
+    if iso.trim().is_empty() {
+        Some("Дедлайн обязателен")
+    } else if deadline_ms.is_nan() {
+        Some("Неверный формат даты")
+    } else {
+        None
     }
 }
 
@@ -80,14 +482,33 @@ pub fn validate_procurement_form(title: &str, description: &str, city: &str, tar
 // Formatting functions
 // ──────────────────────────────────────────────
 
-/// Calculate procurement progress percentage
+/// Calculate procurement progress percentage, rounded to the nearest whole percent
+/// and clamped to [0, 100] (99.6% shows as 100, over-funding as 100)
 #[wasm_bindgen]
 pub fn calculate_progress(current_amount: f64, target_amount: f64) -> i32 {
-    if target_amount <= 0.0 {
+    calculate_progress_f64(current_amount, target_amount).round() as i32
+}
+
+/// Calculate procurement progress percentage without the 100% cap (e.g. 150 for
+/// an over-funded procurement), rounded and floored at 0
+#[wasm_bindgen]
+pub fn calculate_progress_uncapped(current_amount: f64, target_amount: f64) -> i32 {
+    if target_amount <= 0.0 || target_amount.is_nan() {
         return 0;
     }
-    let progress = (current_amount / target_amount * 100.0) as i32;
-    progress.min(100).max(0)
+    let progress = (current_amount / target_amount * 100.0).round();
+    if progress.is_nan() { 0 } else { progress.max(0.0) as i32 }
+}
+
+/// Calculate unrounded procurement progress percentage, clamped to [0, 100]
+/// (for progress bars with sub-percent precision)
+#[wasm_bindgen]
+pub fn calculate_progress_f64(current_amount: f64, target_amount: f64) -> f64 {
+    if target_amount <= 0.0 || target_amount.is_nan() {
+        return 0.0;
+    }
+    let progress = current_amount / target_amount * 100.0;
+    if progress.is_nan() { 0.0 } else { progress.clamp(0.0, 100.0) }
 }
 
 /// Calculate days remaining until deadline
@@ -99,36 +520,200 @@ pub fn days_until(deadline_ms: f64) -> i32 {
     days.max(0)
 }
 
-/// Format currency amount (Russian rubles)
+/// Short label for the time left until a deadline
+/// "Завершено" once the deadline has passed, "N часов" under a day (hours rounded up,
+/// so never "0 часов"), otherwise "N дней" (whole days, as in `days_until`), with
+/// Russian plural forms ("1 час", "3 часа", "21 день", "2 дня")
 #[wasm_bindgen]
-pub fn format_currency(amount: f64) -> String {
-    let integer = amount.trunc() as i64;
-    let fraction = ((amount.fract() * 100.0).round() as i64).abs();
+pub fn time_until_label(deadline_ms: f64) -> String {
+    time_until_label_at(deadline_ms, js_sys::Date::now())
+}
 
-    // Format with thousands separator
-    let int_str = integer.to_string();
-    let mut formatted = String::new();
-    for (i, ch) in int_str.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 && ch != '-' {
-            formatted.push(' ');
+/// Internal helper: time-until label against an explicit current time
+fn time_until_label_at(deadline_ms: f64, now_ms: f64) -> String {
+    let diff_ms = deadline_ms - now_ms;
+    if diff_ms.is_nan() || diff_ms <= 0.0 {
+        "Завершено".to_string()
+    } else if diff_ms < 86_400_000.0 {
+        let hours = (diff_ms / 3_600_000.0).ceil() as i64;
+        format!("{} {}", hours, ru_plural(hours, "час", "часа", "часов"))
+    } else {
+        let days = (diff_ms / 86_400_000.0).floor() as i64;
+        format!("{} {}", days, ru_plural(days, "день", "дня", "дней"))
+    }
+}
+
+/// Card color hint for each `urgency_bucket` value
+const URGENCY_COLORS: [(&str, &str); 5] = [
+    ("overdue", "#9e9e9e"),
+    ("soon", "#e53935"),
+    ("week", "#fb8c00"),
+    ("ok", "#43a047"),
+    ("none", "#9e9e9e"),
+];
+
+/// Deadline phrase with a color hint for procurement cards
+/// Returns JSON: {label, days, urgency, color} where label is "осталось N дней" with
+/// Russian plural forms ("остался 1 день", "осталось 2 дня", whole days rounded down),
+/// "осталось менее дня" under a day, "Завершено" once the deadline has passed (days is
+/// then negative) or "Без срока" for a missing (NaN / infinite) deadline (days is null).
+/// urgency is the `urgency` bucket of `batch_process_procurements`
+#[wasm_bindgen]
+pub fn deadline_phrase(deadline_ms: f64) -> String {
+    deadline_phrase_at(deadline_ms, js_sys::Date::now())
+}
+
+/// Internal helper: deadline phrase against an explicit current time
+fn deadline_phrase_at(deadline_ms: f64, now_ms: f64) -> String {
+    let deadline = if deadline_ms.is_finite() { Some(deadline_ms) } else { None };
+    let urgency = urgency_bucket(deadline, now_ms);
+    let color = URGENCY_COLORS.iter()
+        .find(|(bucket, _)| *bucket == urgency)
+        .map_or("#9e9e9e", |(_, color)| *color);
+
+    let (label, days) = match deadline {
+        None => ("Без срока".to_string(), None),
+        Some(ms) => {
+            let days = ((ms - now_ms) / 86_400_000.0).floor() as i64;
+            if ms < now_ms {
+                ("Завершено".to_string(), Some(days))
+            } else if days == 0 {
+                ("осталось менее дня".to_string(), Some(days))
+            } else {
+                let label = format!(
+                    "{} {} {}",
+                    ru_plural(days, "остался", "осталось", "осталось"),
+                    days,
+                    ru_plural(days, "день", "дня", "дней"),
+                );
+                (label, Some(days))
+            }
         }
-        formatted.push(ch);
+    };
+
+    serde_json::json!({
+        "label": label,
+        "days": days,
+        "urgency": urgency,
+        "color": color,
+    }).to_string()
+}
+
+/// Procurement "health" from 0 to 100 for an at-a-glance indicator
+/// health = 60 * pace + 25 * funded + 15 * momentum, clamped to [0, 100], where
+/// - funded: current / target, clamped to [0, 1] (reaching the target scores 100 overall)
+/// - expected: share of the created..deadline window already elapsed, in [0, 1]
+///   (0 if either timestamp is missing/NaN)
+/// - pace: 1 + 2 * (funded - expected), clamped to [0, 1] — full when at or ahead
+///   of a linear funding pace, zero when half the target behind it
+/// - momentum: participants per elapsed day (at least one day), capped at 1 per day
+#[wasm_bindgen]
+pub fn procurement_health(current: f64, target: f64, deadline_ms: f64, participants: i32, created_ms: f64) -> f64 {
+    procurement_health_at(current, target, deadline_ms, participants, created_ms, js_sys::Date::now())
+}
+
+/// Internal helper: procurement health against an explicit current time
+fn procurement_health_at(current: f64, target: f64, deadline_ms: f64, participants: i32, created_ms: f64, now_ms: f64) -> f64 {
+    let funded = if target > 0.0 { (current / target).clamp(0.0, 1.0) } else { 0.0 };
+    if funded >= 1.0 {
+        return 100.0;
     }
-    let formatted: String = formatted.chars().rev().collect();
 
-    if fraction > 0 {
-        format!("{},{:02} \u{20bd}", formatted, fraction)
+    let expected = if !deadline_ms.is_finite() || !created_ms.is_finite() {
+        0.0
+    } else if deadline_ms <= created_ms {
+        1.0
     } else {
-        format!("{} \u{20bd}", formatted)
+        ((now_ms - created_ms) / (deadline_ms - created_ms)).clamp(0.0, 1.0)
+    };
+    let pace = (1.0 + 2.0 * (funded - expected)).clamp(0.0, 1.0);
+
+    let elapsed_days = if created_ms.is_finite() { ((now_ms - created_ms) / 86_400_000.0).max(1.0) } else { 1.0 };
+    let momentum = (participants.max(0) as f64 / elapsed_days).min(1.0);
+
+    (60.0 * pace + 25.0 * funded + 15.0 * momentum).clamp(0.0, 100.0)
+}
+
+/// Format phone number for display
+/// Russian numbers (11 digits starting with 7 or 8) become `+7 (XXX) XXX-XX-XX`,
+/// anything else is returned as `+` followed by the cleaned digits
+#[wasm_bindgen]
+pub fn format_phone(phone: &str) -> String {
+    let digits: String = phone.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() {
+        return String::new();
+    }
+
+    if digits.len() == 11 && (digits.starts_with('7') || digits.starts_with('8')) {
+        let national = &digits[1..];
+        return format!("+7 ({}) {}-{}-{}",
+            &national[..3],
+            &national[3..6],
+            &national[6..8],
+            &national[8..10]
+        );
+    }
+
+    format!("+{}", digits)
+}
+
+/// Format currency amount (Russian rubles; shortcut for `format_money(amount, "RUB")`)
+/// Negative amounts get a single leading ASCII '-' (e.g. "-1 234,50 ₽")
+#[wasm_bindgen]
+pub fn format_currency(amount: f64) -> String {
+    format_currency_value(amount)
+}
+
+/// Format a file size with 1024-based Russian units (Б, КБ, МБ, ГБ)
+/// Bytes have no decimals, larger units one decimal with a comma (e.g. "3,4 МБ").
+/// Negative or non-finite (NaN, infinite) input gives "0 Б"
+#[wasm_bindgen]
+pub fn format_file_size(bytes: f64) -> String {
+    const UNITS: [&str; 3] = ["КБ", "МБ", "ГБ"];
+
+    if !bytes.is_finite() || bytes < 0.0 {
+        return "0 Б".to_string();
+    }
+    if bytes < 1024.0 {
+        return format!("{} Б", bytes.floor());
+    }
+
+    let mut value = bytes / 1024.0;
+    let mut unit = 0;
+    // Step up a unit when rounding would show "1024,0"
+    while unit < UNITS.len() - 1 && (value * 10.0).round() >= 10_240.0 {
+        value /= 1024.0;
+        unit += 1;
     }
+    format!("{:.1} {}", value, UNITS[unit]).replace('.', ",")
 }
 
-/// Format relative time in Russian
+/// Format relative time in Russian, up to weeks, months and years
+/// Future timestamps within a minute (clock skew) read "только что",
+/// further ones are phrased forward ("через N мин.")
 #[wasm_bindgen]
 pub fn format_relative_time(timestamp_ms: f64) -> String {
-    let now_ms = js_sys::Date::now();
+    format_relative_time_at(timestamp_ms, js_sys::Date::now())
+}
+
+/// Internal helper: relative time against an explicit current time
+fn format_relative_time_at(timestamp_ms: f64, now_ms: f64) -> String {
     let diff_sec = ((now_ms - timestamp_ms) / 1000.0) as i64;
 
+    if diff_sec < 0 {
+        let ahead_sec = -diff_sec;
+        if ahead_sec < 60 {
+            return "только что".to_string();
+        }
+        if ahead_sec < 3600 {
+            return format!("через {} мин.", ahead_sec / 60);
+        }
+        if ahead_sec < 86400 {
+            return format!("через {} ч.", ahead_sec / 3600);
+        }
+        return format!("через {} дн.", ahead_sec / 86400);
+    }
+
     if diff_sec < 60 {
         return "только что".to_string();
     }
@@ -144,36 +729,224 @@ pub fn format_relative_time(timestamp_ms: f64) -> String {
     if days == 1 {
         return "вчера".to_string();
     }
-    format!("{} дн. назад", days)
+    // Approximate calendar brackets: a week is 7 days, a month 30, a year 365
+    if days < 7 {
+        return format!("{} дн. назад", days);
+    }
+    if days < 30 {
+        return format!("{} нед. назад", days / 7);
+    }
+    if days < 365 {
+        return format!("{} мес. назад", days / 30);
+    }
+    let years = days / 365;
+    format!("{} {} назад", years, ru_plural(years, "год", "года", "лет"))
+}
+
+/// Format relative time in Russian using full words with correct plural forms
+/// (e.g. "1 минуту назад", "3 часа назад", "25 дней назад")
+#[wasm_bindgen]
+pub fn format_relative_time_full(timestamp_ms: f64) -> String {
+    format_relative_time_full_at(timestamp_ms, js_sys::Date::now())
+}
+
+/// Internal helper: full-word relative time against an explicit current time
+fn format_relative_time_full_at(timestamp_ms: f64, now_ms: f64) -> String {
+    let diff_sec = ((now_ms - timestamp_ms) / 1000.0) as i64;
+
+    if diff_sec < 60 {
+        return "только что".to_string();
+    }
+    if diff_sec < 3600 {
+        let mins = diff_sec / 60;
+        return format!("{} {} назад", mins, ru_plural(mins, "минуту", "минуты", "минут"));
+    }
+    if diff_sec < 86400 {
+        let hours = diff_sec / 3600;
+        return format!("{} {} назад", hours, ru_plural(hours, "час", "часа", "часов"));
+    }
+    let days = diff_sec / 86400;
+    if days == 1 {
+        return "вчера".to_string();
+    }
+    format!("{} {} назад", days, ru_plural(days, "день", "дня", "дней"))
+}
+
+/// Pick the Russian plural form for a number: one (1, 21), few (2-4, 22-24), many (5-20, 11-14)
+fn ru_plural<'a>(n: i64, one: &'a str, few: &'a str, many: &'a str) -> &'a str {
+    let n = n.abs();
+    let mod10 = n % 10;
+    let mod100 = n % 100;
+    if (11..=14).contains(&mod100) {
+        many
+    } else if mod10 == 1 {
+        one
+    } else if (2..=4).contains(&mod10) {
+        few
+    } else {
+        many
+    }
+}
+
+/// Format a Russian ordinal number with its gender ending: "1-й", "1-я", "1-е"
+/// gender: "m" / "masculine" (участник), "f" / "feminine" (закупка),
+/// "n" / "neuter" (место); anything else is treated as masculine
+#[wasm_bindgen]
+pub fn format_ordinal_ru(n: i32, gender: &str) -> String {
+    let ending = match gender.trim().to_lowercase().as_str() {
+        "f" | "feminine" => "я",
+        "n" | "neuter" => "е",
+        _ => "й",
+    };
+    format!("{}-{}", n, ending)
+}
+
+/// Format a number with the matching Russian plural form, e.g. "5 мест"
+/// forms_json: JSON array of three forms [one, few, many], e.g. ["место", "места", "мест"]
+/// Invalid forms give just the number
+#[wasm_bindgen]
+pub fn format_count_ru(n: i32, forms_json: &str) -> String {
+    match serde_json::from_str::<[String; 3]>(forms_json) {
+        Ok([one, few, many]) => format!("{} {}", n, ru_plural(n as i64, &one, &few, &many)),
+        Err(_) => n.to_string(),
+    }
 }
 
 /// Generate unique platform user ID for websocket users
+/// Format: `web_` followed by a random (version 4) UUID
 #[wasm_bindgen]
 pub fn generate_platform_user_id() -> String {
-    let timestamp = js_sys::Date::now() as u64;
-    let random = (js_sys::Math::random() * 1_000_000_000.0) as u64;
-    format!("web_{}_{}", timestamp, random)
+    let mut bytes = [0u8; 16];
+    if getrandom::getrandom(&mut bytes).is_err() {
+        // Fall back to several independent Math.random() draws
+        for chunk in bytes.chunks_mut(4) {
+            let word = (js_sys::Math::random() * 4_294_967_296.0) as u32;
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+    }
+    bytes[6] = (bytes[6] & 0x0f) | 0x40; // version 4
+    bytes[8] = (bytes[8] & 0x3f) | 0x80; // RFC 4122 variant
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("web_{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
 }
 
+/// Default avatar palette (Telegram-style, used on dark backgrounds)
+const AVATAR_COLORS_DARK: [&str; 8] = [
+    "#e17076", "#faa774", "#a695e7", "#7bc862",
+    "#6ec9cb", "#65aadd", "#ee7aae", "#f5a623",
+];
+
+/// Avatar palette for the light theme: same hues, darkened for white text contrast
+const AVATAR_COLORS_LIGHT: [&str; 8] = [
+    "#c0392b", "#c25e00", "#7e57c2", "#2e7d32",
+    "#00838f", "#1565c0", "#c2185b", "#a35f00",
+];
+
 /// Generate avatar background color based on name (hash-based)
 #[wasm_bindgen]
 pub fn get_avatar_color(name: &str) -> String {
-    let colors = [
-        "#e17076", "#faa774", "#a695e7", "#7bc862",
-        "#6ec9cb", "#65aadd", "#ee7aae", "#f5a623",
-    ];
+    AVATAR_COLORS_DARK[avatar_color_index(name)].to_string()
+}
+
+/// Generate avatar background color for a theme ("light" or "dark")
+/// The palette slot depends only on the name, so a user keeps the same hue
+/// when switching themes; unknown themes fall back to the dark palette
+#[wasm_bindgen]
+pub fn get_avatar_color_themed(name: &str, theme: &str) -> String {
+    let colors = match theme {
+        "light" => &AVATAR_COLORS_LIGHT,
+        _ => &AVATAR_COLORS_DARK,
+    };
+    colors[avatar_color_index(name)].to_string()
+}
+
+/// Avatar initials and colors in one call
+/// Initials are those of `get_initials`, bg is `get_avatar_color_themed` of the first
+/// name, fg is "#000000" or "#ffffff", whichever contrasts more with bg
+/// Returns JSON: { "initials": "ИП", "bg": "#rrggbb", "fg": "#rrggbb" }
+#[wasm_bindgen]
+pub fn avatar_spec(first: &str, last: &str, theme: &str) -> String {
+    let bg = get_avatar_color_themed(first, theme);
+    serde_json::json!({
+        "initials": get_initials(first, last),
+        "fg": readable_text_color(&bg),
+        "bg": bg,
+    }).to_string()
+}
+
+/// WCAG contrast ratio of two `#RRGGBB` colors, from 1.0 (identical) to 21.0 (black on white)
+/// Order of the arguments does not matter; invalid hex gives 0.0
+#[wasm_bindgen]
+pub fn contrast_ratio(hex_a: &str, hex_b: &str) -> f64 {
+    match (parse_hex_color(hex_a), parse_hex_color(hex_b)) {
+        (Some(a), Some(b)) => {
+            let (la, lb) = (relative_luminance(a), relative_luminance(b));
+            (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+        }
+        _ => 0.0,
+    }
+}
+
+/// Text color for a background: "#000000" or "#ffffff", whichever contrasts more
+/// (black for an invalid color)
+#[wasm_bindgen]
+pub fn pick_readable_text(bg_hex: &str) -> String {
+    readable_text_color(bg_hex).to_string()
+}
+
+/// Internal helper: black or white text, whichever has the higher WCAG contrast on `bg_hex`
+fn readable_text_color(bg_hex: &str) -> &'static str {
+    if contrast_ratio(bg_hex, "#ffffff") > contrast_ratio(bg_hex, "#000000") {
+        "#ffffff"
+    } else {
+        "#000000"
+    }
+}
+
+/// Internal helper: parse a `#RRGGBB` color into its channels
+fn parse_hex_color(hex: &str) -> Option<[u8; 3]> {
+    let digits = hex.trim().strip_prefix('#')?;
+    if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Internal helper: WCAG 2.x relative luminance of an sRGB color (0.0 black to 1.0 white)
+fn relative_luminance(rgb: [u8; 3]) -> f64 {
+    let linear = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * linear(rgb[0]) + 0.7152 * linear(rgb[1]) + 0.0722 * linear(rgb[2])
+}
+
+/// Internal helper: palette slot for a name (identical for every palette)
+fn avatar_color_index(name: &str) -> usize {
+    name_hash(name) as usize % AVATAR_COLORS_DARK.len()
+}
+
+/// Deterministic name hash used for avatar colors
+/// Java-style `h = h * 31 + c` over Unicode scalar values with wrapping i32
+/// arithmetic, then the absolute value (i32::MIN maps to 2^31). Only fixed-width
+/// integer ops are involved, so the result is identical on wasm32 and on any
+/// server-side target and can be used to pre-render avatars.
+fn name_hash(name: &str) -> u32 {
     let mut hash: i32 = 0;
     for ch in name.chars() {
         hash = (ch as i32).wrapping_add(hash.wrapping_shl(5).wrapping_sub(hash));
     }
-    colors[(hash.unsigned_abs() as usize) % colors.len()].to_string()
+    hash.unsigned_abs()
 }
 
 /// Get initials from first name and last name
+/// Takes the first grapheme of each name, so flags and combining sequences stay intact
 #[wasm_bindgen]
 pub fn get_initials(first_name: &str, last_name: &str) -> String {
-    let first = first_name.chars().next().map(|c| c.to_uppercase().to_string()).unwrap_or_default();
-    let last = last_name.chars().next().map(|c| c.to_uppercase().to_string()).unwrap_or_default();
+    let first = first_name.graphemes(true).next().map(uppercase_grapheme).unwrap_or_default();
+    let last = last_name.graphemes(true).next().map(uppercase_grapheme).unwrap_or_default();
     let result = format!("{}{}", first, last);
     if result.is_empty() {
         "?".to_string()
@@ -182,16 +955,49 @@ pub fn get_initials(first_name: &str, last_name: &str) -> String {
     }
 }
 
-/// Escape HTML to prevent XSS
+/// Get two-character initials from first name and last name
+/// When the last name is empty, the first two graphemes of the first name are used
+/// ("Александр" -> "АЛ"); emoji and combining sequences count as one character
 #[wasm_bindgen]
-pub fn escape_html(text: &str) -> String {
-    let mut result = String::with_capacity(text.len());
-    for ch in text.chars() {
-        match ch {
-            '&' => result.push_str("&amp;"),
-            '<' => result.push_str("&lt;"),
-            '>' => result.push_str("&gt;"),
-            '"' => result.push_str("&quot;"),
+pub fn get_initials_v2(first_name: &str, last_name: &str) -> String {
+    let first_name = first_name.trim();
+    let last_name = last_name.trim();
+
+    let result: String = if last_name.is_empty() {
+        first_name.graphemes(true).take(2).map(uppercase_grapheme).collect()
+    } else {
+        first_name.graphemes(true).take(1)
+            .chain(last_name.graphemes(true).take(1))
+            .map(uppercase_grapheme)
+            .collect()
+    };
+
+    if result.is_empty() {
+        "?".to_string()
+    } else {
+        result
+    }
+}
+
+/// Internal helper: uppercase a grapheme, leaving non-cased ones (emoji) untouched
+fn uppercase_grapheme(grapheme: &str) -> String {
+    if grapheme.chars().any(|c| c.is_lowercase()) {
+        grapheme.to_uppercase()
+    } else {
+        grapheme.to_string()
+    }
+}
+
+/// Escape HTML to prevent XSS
+#[wasm_bindgen]
+pub fn escape_html(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
             '\'' => result.push_str("&#x27;"),
             _ => result.push(ch),
         }
@@ -199,665 +1005,5048 @@ pub fn escape_html(text: &str) -> String {
     result
 }
 
-/// Format message text: escape HTML, convert URLs to links, convert newlines to <br>
+/// Format message text: escape HTML, convert URLs, @mentions and #hashtags to links,
+/// render *bold* / _italic_, convert newlines to <br>
 #[wasm_bindgen]
 pub fn format_message_text(text: &str) -> String {
     if text.is_empty() {
         return String::new();
     }
 
-    let escaped = escape_html(text);
+    // One pass over the raw text converts URLs, mentions and hashtags to links;
+    // everything in between is escaped and gets inline markup
+    let mut result = String::with_capacity(text.len());
+    let mut plain_start = 0;
+    let mut pos = 0;
+
+    while let Some(token) = MESSAGE_TOKEN_RE.find_at(text, pos) {
+        let (token, matched) = (token.range(), token.as_str());
+        let prev_is_word = text[..token.start].chars().last().is_some_and(is_word_char);
+
+        // The first character tells which alternative matched
+        let link = if let Some(username) = matched.strip_prefix('@') {
+            let followed_by_word = text[token.end..].chars().next().is_some_and(is_word_char);
+            (!prev_is_word && username.len() <= 32 && !followed_by_word).then(|| {
+                (token.len(), format!("<a href=\"/u/{}\" class=\"mention\">@{}</a>", username, username))
+            })
+        } else if let Some(tag) = matched.strip_prefix('#') {
+            (!prev_is_word).then(|| {
+                (token.len(), format!("<a href=\"/tag/{}\" class=\"hashtag\">#{}</a>", tag, tag))
+            })
+        } else {
+            let url = trim_url_punctuation(matched);
+            Some((url.len(), format!("<a href=\"{}\" target=\"_blank\" rel=\"noopener\">{}</a>",
+                escape_href(url), escape_html(url))))
+        };
 
-    // Convert URLs to clickable links
-    let mut result = String::with_capacity(escaped.len());
-    let mut remaining = escaped.as_str();
-
-    while !remaining.is_empty() {
-        if let Some(http_pos) = remaining.find("http") {
-            let before = &remaining[..http_pos];
-            result.push_str(before);
-
-            let after = &remaining[http_pos..];
-            // Check if it's https:// or http://
-            let is_url = after.starts_with("https://") || after.starts_with("http://");
-
-            if is_url {
-                // Find end of URL (whitespace or end of string)
-                let url_end = after.find(|c: char| c.is_whitespace() || c == '<')
-                    .unwrap_or(after.len());
-                let url = &after[..url_end];
-                result.push_str(&format!("<a href=\"{}\" target=\"_blank\" rel=\"noopener\">{}</a>", url, url));
-                remaining = &after[url_end..];
-            } else {
-                result.push_str(&after[..4]);
-                remaining = &after[4..];
+        match link {
+            Some((len, html)) => {
+                result.push_str(&render_inline_markup(&escape_html(&text[plain_start..token.start])));
+                result.push_str(&html);
+                pos = token.start + len;
+                plain_start = pos;
             }
-        } else {
-            result.push_str(remaining);
-            break;
+            // Not a mention / hashtag here; a URL may still start inside the token
+            None => pos = token.start + 1,
         }
     }
+    result.push_str(&render_inline_markup(&escape_html(&text[plain_start..])));
 
     // Convert newlines to <br>
     result.replace('\n', "<br>")
 }
 
-// ──────────────────────────────────────────────
-// High-performance batch processing functions
-// ──────────────────────────────────────────────
+/// Internal helper: escape a URL for a double-quoted href attribute
+/// Unlike `escape_html`, '&' is kept literal so query strings stay valid URLs
+fn escape_href(url: &str) -> String {
+    let mut result = String::with_capacity(url.len());
+    for ch in url.chars() {
+        match ch {
+            '<' => result.push_str("%3C"),
+            '>' => result.push_str("%3E"),
+            '"' => result.push_str("%22"),
+            '\'' => result.push_str("%27"),
+            _ => result.push(ch),
+        }
+    }
+    result
+}
 
-/// Procurement data for batch operations (internal)
-#[derive(Serialize, Deserialize, Clone)]
-struct Procurement {
-    id: i64,
-    title: String,
-    description: Option<String>,
-    city: Option<String>,
-    status: Option<String>,
-    current_amount: Option<f64>,
-    target_amount: Option<f64>,
-    deadline: Option<String>,
-    participant_count: Option<i32>,
-    category: Option<String>,
-    organizer_name: Option<String>,
-    created_at: Option<String>,
-    #[serde(default)]
-    progress: Option<f64>,
+/// URL pattern: http(s) up to whitespace, tag delimiters or end of string
+/// (trailing punctuation is trimmed separately by `trim_url_punctuation`)
+const URL_PATTERN: &str = r#"https?://[^\s<>"]*"#;
+
+/// Compiled `URL_PATTERN`
+static URL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(URL_PATTERN).expect("valid URL regex"));
+
+/// Links recognized in message text: URLs, @mentions and #hashtags
+/// Word boundaries and the 32-char username limit are checked by the caller,
+/// since the regex crate has no look-around
+static MESSAGE_TOKEN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"{}|@[A-Za-z0-9_]+|#[\p{{Alphabetic}}\p{{N}}]+", URL_PATTERN))
+        .expect("valid message token regex")
+});
+
+/// Internal helper: strip trailing `.,;:!?)` from a detected URL
+/// A closing parenthesis is kept while it balances an opening one inside the URL
+fn trim_url_punctuation(url: &str) -> &str {
+    let mut url = url;
+    loop {
+        match url.chars().last() {
+            Some('.' | ',' | ';' | ':' | '!' | '?') => {}
+            Some(')') if url.matches('(').count() < url.matches(')').count() => {}
+            _ => return url,
+        }
+        url = &url[..url.len() - 1];
+    }
 }
 
-/// Batch-process procurements: compute progress, days left, and format currency in one pass
-/// Input: JSON array of procurements
-/// Output: JSON array with computed fields added
+/// Internal helper: word character for marker and mention boundaries
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Convert message text to a single line of plain text (for notifications and search)
+/// Strips *bold* / _italic_ markers, keeps URLs literal, collapses whitespace and newlines
 #[wasm_bindgen]
-pub fn batch_process_procurements(json_input: &str) -> String {
-    let procurements: Vec<Procurement> = match serde_json::from_str(json_input) {
-        Ok(p) => p,
-        Err(_) => return "[]".to_string(),
-    };
+pub fn message_text_to_plain(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut plain_start = 0;
+
+    for url in URL_RE.find_iter(text) {
+        let url_text = trim_url_punctuation(url.as_str());
+        stripped.push_str(&strip_inline_markup(&text[plain_start..url.start()]));
+        stripped.push_str(url_text);
+        plain_start = url.start() + url_text.len();
+    }
+    stripped.push_str(&strip_inline_markup(&text[plain_start..]));
 
-    let now_ms = js_sys::Date::now();
-    let results: Vec<serde_json::Value> = procurements.iter().map(|p| {
-        let current = p.current_amount.unwrap_or(0.0);
-        let target = p.target_amount.unwrap_or(0.0);
-        let progress = if target > 0.0 {
-            ((current / target * 100.0) as i32).min(100).max(0)
-        } else {
-            0
-        };
+    stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+}
 
-        let days_left = p.deadline.as_ref().map(|d| {
-            // Parse ISO date string to ms
-            let date = js_sys::Date::new(&JsValue::from_str(d));
-            let deadline_ms = date.get_time();
-            let diff_ms = deadline_ms - now_ms;
-            ((diff_ms / 86_400_000.0) as i32).max(0)
-        });
+/// One-line preview of a message for chat list rows
+/// Markup is stripped and whitespace (including newlines) collapsed as in
+/// `message_text_to_plain`, then the text is cut to `max_chars` graphemes with "…"
+/// (see `truncate_text`). System messages are free-form text and are previewed as is;
+/// image and file messages without text become "Фото" / "Файл"
+#[wasm_bindgen]
+pub fn message_preview(text: &str, max_chars: i32, message_type: &str) -> String {
+    let plain = message_text_to_plain(text);
+    let preview = match message_type {
+        "image" if plain.is_empty() => "Фото".to_string(),
+        "file" if plain.is_empty() => "Файл".to_string(),
+        _ => plain,
+    };
+    truncate_text(&preview, max_chars)
+}
 
-        let formatted_current = format_currency_value(current);
-        let formatted_target = format_currency_value(target);
+/// Internal helper: remove balanced `*` / `_` markers, keeping their content
+fn strip_inline_markup(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let marker = chars[i];
+        if marker == '*' || marker == '_' {
+            if let Some(close) = find_closing_marker(&chars, i) {
+                let inner: String = chars[i + 1..close].iter().collect();
+                result.push_str(&strip_inline_markup(&inner));
+                i = close + 1;
+                continue;
+            }
+        }
+        result.push(marker);
+        i += 1;
+    }
 
-        let mut obj = serde_json::json!({
-            "id": p.id,
-            "title": p.title,
-            "description": p.description,
-            "city": p.city,
-            "status": p.status,
-            "current_amount": current,
-            "target_amount": target,
-            "participant_count": p.participant_count,
-            "category": p.category,
-            "organizer_name": p.organizer_name,
-            "created_at": p.created_at,
-            "deadline": p.deadline,
-            "progress": progress,
-            "formatted_current": formatted_current,
-            "formatted_target": formatted_target,
-        });
+    result
+}
 
-        if let Some(days) = days_left {
-            obj["days_left"] = serde_json::json!(days);
+/// Render `*bold*` and `_italic_` in already-escaped text
+/// A marker only opens at a word start and closes at a word end on the same line,
+/// and must wrap non-whitespace content; anything else stays literal (`a*b*c`, lone `*`)
+fn render_inline_markup(escaped: &str) -> String {
+    let chars: Vec<char> = escaped.chars().collect();
+    let mut result = String::with_capacity(escaped.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let marker = chars[i];
+        if marker == '*' || marker == '_' {
+            if let Some(close) = find_closing_marker(&chars, i) {
+                let inner: String = chars[i + 1..close].iter().collect();
+                let (open_tag, close_tag) = if marker == '*' { ("<b>", "</b>") } else { ("<i>", "</i>") };
+                result.push_str(open_tag);
+                result.push_str(&render_inline_markup(&inner));
+                result.push_str(close_tag);
+                i = close + 1;
+                continue;
+            }
         }
+        result.push(marker);
+        i += 1;
+    }
 
-        obj
-    }).collect();
-
-    serde_json::to_string(&results).unwrap_or_else(|_| "[]".to_string())
+    result
 }
 
-/// Internal helper: format currency without the symbol for reuse
-fn format_currency_value(amount: f64) -> String {
-    let integer = amount.trunc() as i64;
-    let fraction = ((amount.fract() * 100.0).round() as i64).abs();
+/// Internal helper: index of the marker closing the one at `open`, if balanced
+fn find_closing_marker(chars: &[char], open: usize) -> Option<usize> {
+    let marker = chars[open];
 
-    let int_str = integer.to_string();
-    let mut formatted = String::new();
-    for (i, ch) in int_str.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 && ch != '-' {
-            formatted.push(' ');
-        }
-        formatted.push(ch);
+    if open > 0 && is_word_char(chars[open - 1]) {
+        return None;
+    }
+    let first = *chars.get(open + 1)?;
+    if first.is_whitespace() || first == marker {
+        return None;
     }
-    let formatted: String = formatted.chars().rev().collect();
 
-    if fraction > 0 {
-        format!("{},{:02} \u{20bd}", formatted, fraction)
-    } else {
-        format!("{} \u{20bd}", formatted)
+    for j in open + 2..chars.len() {
+        let c = chars[j];
+        if c == '\n' {
+            return None;
+        }
+        if c == marker
+            && !chars[j - 1].is_whitespace()
+            && chars.get(j + 1).is_none_or(|&next| !is_word_char(next))
+        {
+            return Some(j);
+        }
     }
+    None
 }
 
-/// Fuzzy search procurements by query string
-/// Returns JSON array of matching procurement IDs with relevance scores, sorted by relevance
+/// Render a procurement description written in a small Markdown subset to safe HTML
+/// Blocks: "# " / "## " headings (rendered as <h2> / <h3>, below the page title),
+/// "- " bullet lists, and paragraphs separated by blank lines (single newlines become <br>).
+/// Inline: **bold** (or *bold* as in chat), _italic_, and autolinked http(s) URLs.
+/// All text is HTML-escaped first; raw HTML, images and other Markdown stay literal text
 #[wasm_bindgen]
-pub fn search_procurements(json_input: &str, query: &str) -> String {
-    if query.trim().is_empty() {
-        return "[]".to_string();
-    }
-
-    let procurements: Vec<Procurement> = match serde_json::from_str(json_input) {
-        Ok(p) => p,
-        Err(_) => return "[]".to_string(),
+pub fn render_description_md(md: &str) -> String {
+    let mut html = String::with_capacity(md.len() + md.len() / 4);
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut list: Vec<&str> = Vec::new();
+
+    let flush_paragraph = |paragraph: &mut Vec<&str>, html: &mut String| {
+        if !paragraph.is_empty() {
+            let lines: Vec<String> = paragraph.drain(..).map(render_description_inline).collect();
+            html.push_str(&format!("<p>{}</p>", lines.join("<br>")));
+        }
     };
-
-    let query_lower = query.to_lowercase();
-    let query_words: Vec<&str> = query_lower.split_whitespace().collect();
-
-    let mut results: Vec<(i64, f64)> = procurements.iter().filter_map(|p| {
-        let mut score: f64 = 0.0;
-        let title_lower = p.title.to_lowercase();
-        let desc_lower = p.description.as_deref().unwrap_or("").to_lowercase();
-        let city_lower = p.city.as_deref().unwrap_or("").to_lowercase();
-        let org_lower = p.organizer_name.as_deref().unwrap_or("").to_lowercase();
-
-        for word in &query_words {
-            // Title matches (highest weight)
-            if title_lower.contains(word) {
-                score += 10.0;
-                if title_lower.starts_with(word) {
-                    score += 5.0; // prefix bonus
-                }
-            }
-            // City match
-            if city_lower.contains(word) {
-                score += 5.0;
-            }
-            // Organizer match
-            if org_lower.contains(word) {
-                score += 3.0;
-            }
-            // Description match (lower weight)
-            if desc_lower.contains(word) {
-                score += 2.0;
+    let flush_list = |list: &mut Vec<&str>, html: &mut String| {
+        if !list.is_empty() {
+            html.push_str("<ul>");
+            for item in list.drain(..) {
+                html.push_str(&format!("<li>{}</li>", render_description_inline(item)));
             }
+            html.push_str("</ul>");
         }
+    };
 
-        if score > 0.0 {
-            Some((p.id, score))
+    for line in md.lines() {
+        let trimmed = line.trim();
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            flush_paragraph(&mut paragraph, &mut html);
+            list.push(item.trim());
+            continue;
+        }
+        flush_list(&mut list, &mut html);
+
+        let heading = trimmed.strip_prefix("## ").map(|h| ("h3", h))
+            .or_else(|| trimmed.strip_prefix("# ").map(|h| ("h2", h)));
+        if let Some((tag, text)) = heading {
+            flush_paragraph(&mut paragraph, &mut html);
+            html.push_str(&format!("<{tag}>{}</{tag}>", render_description_inline(text.trim())));
+        } else if trimmed.is_empty() {
+            flush_paragraph(&mut paragraph, &mut html);
         } else {
-            None
+            paragraph.push(trimmed);
         }
-    }).collect();
-
-    // Sort by relevance score descending
-    results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-
-    let output: Vec<serde_json::Value> = results.iter().map(|(id, score)| {
-        serde_json::json!({"id": id, "score": score})
-    }).collect();
+    }
+    flush_list(&mut list, &mut html);
+    flush_paragraph(&mut paragraph, &mut html);
 
-    serde_json::to_string(&output).unwrap_or_else(|_| "[]".to_string())
+    html
 }
 
-/// Sort procurements by a specified field
-/// sort_by: "title", "amount", "progress", "deadline", "participants", "created"
-/// order: "asc" or "desc"
-/// Returns JSON array of sorted procurement IDs
-#[wasm_bindgen]
-pub fn sort_procurements(json_input: &str, sort_by: &str, order: &str) -> String {
-    let mut procurements: Vec<Procurement> = match serde_json::from_str(json_input) {
-        Ok(p) => p,
-        Err(_) => return "[]".to_string(),
-    };
-
-    let ascending = order != "desc";
+/// Internal helper: inline markup of one description line (escaped text, links, bold/italic)
+fn render_description_inline(text: &str) -> String {
+    let render_plain = |plain: &str| render_description_markup(&escape_html(plain));
+    let mut result = String::with_capacity(text.len());
+    let mut plain_start = 0;
+
+    for url in URL_RE.find_iter(text) {
+        let url_text = trim_url_punctuation(url.as_str());
+        result.push_str(&render_plain(&text[plain_start..url.start()]));
+        result.push_str(&format!("<a href=\"{}\" target=\"_blank\" rel=\"noopener\">{}</a>",
+            escape_href(url_text), escape_html(url_text)));
+        plain_start = url.start() + url_text.len();
+    }
+    result.push_str(&render_plain(&text[plain_start..]));
+    result
+}
 
-    procurements.sort_by(|a, b| {
-        let cmp = match sort_by {
-            "title" => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
-            "amount" => {
-                let a_val = a.current_amount.unwrap_or(0.0);
-                let b_val = b.current_amount.unwrap_or(0.0);
-                a_val.partial_cmp(&b_val).unwrap_or(std::cmp::Ordering::Equal)
-            }
-            "progress" => {
-                let a_target = a.target_amount.unwrap_or(1.0);
-                let b_target = b.target_amount.unwrap_or(1.0);
-                let a_prog = if a_target > 0.0 { a.current_amount.unwrap_or(0.0) / a_target } else { 0.0 };
-                let b_prog = if b_target > 0.0 { b.current_amount.unwrap_or(0.0) / b_target } else { 0.0 };
-                a_prog.partial_cmp(&b_prog).unwrap_or(std::cmp::Ordering::Equal)
-            }
-            "deadline" => {
-                let a_val = a.deadline.as_deref().unwrap_or("");
-                let b_val = b.deadline.as_deref().unwrap_or("");
-                a_val.cmp(b_val)
-            }
-            "participants" => {
-                let a_val = a.participant_count.unwrap_or(0);
-                let b_val = b.participant_count.unwrap_or(0);
-                a_val.cmp(&b_val)
+/// Internal helper: description bold/italic on already-escaped text
+/// `**bold**` is its own marker; single `*bold*` and `_italic_` follow the chat rules
+/// of `render_inline_markup`. Unbalanced or mid-word markers ("2**3") stay literal
+fn render_description_markup(escaped: &str) -> String {
+    let chars: Vec<char> = escaped.chars().collect();
+    let mut result = String::with_capacity(escaped.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let marker = chars[i];
+        if marker == '*' && chars.get(i + 1) == Some(&'*') {
+            if let Some(close) = find_closing_double_marker(&chars, i) {
+                let inner: String = chars[i + 2..close].iter().collect();
+                result.push_str("<b>");
+                result.push_str(&render_description_markup(&inner));
+                result.push_str("</b>");
+                i = close + 2;
+            } else {
+                result.push_str("**");
+                i += 2;
             }
-            "created" => {
-                let a_val = a.created_at.as_deref().unwrap_or("");
-                let b_val = b.created_at.as_deref().unwrap_or("");
-                a_val.cmp(b_val)
+            continue;
+        }
+        if marker == '*' || marker == '_' {
+            if let Some(close) = find_closing_marker(&chars, i) {
+                let inner: String = chars[i + 1..close].iter().collect();
+                let (open_tag, close_tag) = if marker == '*' { ("<b>", "</b>") } else { ("<i>", "</i>") };
+                result.push_str(open_tag);
+                result.push_str(&render_description_markup(&inner));
+                result.push_str(close_tag);
+                i = close + 1;
+                continue;
             }
-            _ => std::cmp::Ordering::Equal,
-        };
-        if ascending { cmp } else { cmp.reverse() }
-    });
+        }
+        result.push(marker);
+        i += 1;
+    }
 
-    let ids: Vec<i64> = procurements.iter().map(|p| p.id).collect();
-    serde_json::to_string(&ids).unwrap_or_else(|_| "[]".to_string())
+    result
 }
 
-/// Aggregate procurement statistics from a JSON array
-/// Returns JSON object with: total_count, active_count, total_amount, total_target,
-/// overall_progress, avg_participants, cities (unique), by_status counts
-#[wasm_bindgen]
-pub fn aggregate_procurement_stats(json_input: &str) -> String {
-    let procurements: Vec<Procurement> = match serde_json::from_str(json_input) {
-        Ok(p) => p,
-        Err(_) => return "{}".to_string(),
-    };
-
-    let total_count = procurements.len();
-    let mut active_count = 0;
-    let mut total_amount = 0.0_f64;
-    let mut total_target = 0.0_f64;
-    let mut total_participants = 0_i64;
-    let mut cities: Vec<String> = Vec::new();
-    let mut status_counts: std::collections::HashMap<String, i32> = std::collections::HashMap::new();
+/// Internal helper: index of the `**` closing the one at `open`, with the same
+/// flanking rules as `find_closing_marker`
+fn find_closing_double_marker(chars: &[char], open: usize) -> Option<usize> {
+    if open > 0 && is_word_char(chars[open - 1]) {
+        return None;
+    }
+    let first = *chars.get(open + 2)?;
+    if first.is_whitespace() || first == '*' {
+        return None;
+    }
 
-    for p in &procurements {
-        let status = p.status.as_deref().unwrap_or("unknown");
-        if status == "active" {
-            active_count += 1;
+    for j in open + 3..chars.len().saturating_sub(1) {
+        let c = chars[j];
+        if c == '\n' {
+            return None;
         }
-        *status_counts.entry(status.to_string()).or_insert(0) += 1;
+        if c == '*'
+            && chars[j + 1] == '*'
+            && !chars[j - 1].is_whitespace()
+            && chars.get(j + 2).is_none_or(|&next| !is_word_char(next))
+        {
+            return Some(j);
+        }
+    }
+    None
+}
 
-        total_amount += p.current_amount.unwrap_or(0.0);
-        total_target += p.target_amount.unwrap_or(0.0);
-        total_participants += p.participant_count.unwrap_or(0) as i64;
+/// Maximum length of a slug produced by `slugify`
+const SLUG_MAX_LEN: usize = 80;
 
-        if let Some(city) = &p.city {
-            if !city.is_empty() && !cities.contains(city) {
-                cities.push(city.clone());
+/// Build a URL slug from text (e.g. a procurement title)
+/// Lowercases, transliterates Cyrillic to Latin, replaces runs of other characters
+/// with a single hyphen and trims hyphens at the ends. Capped at 80 characters
+/// without cutting a transliterated letter in half
+#[wasm_bindgen]
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len().min(SLUG_MAX_LEN));
+    let mut pending_hyphen = false;
+
+    for ch in text.chars().flat_map(char::to_lowercase) {
+        let latin = transliterate_to_latin(&ch.to_string());
+        if latin.is_empty() {
+            // ъ / ь are dropped without splitting the word
+            continue;
+        }
+        if latin.chars().all(|c| c.is_ascii_alphanumeric()) {
+            let hyphen_len = usize::from(pending_hyphen && !slug.is_empty());
+            if slug.len() + hyphen_len + latin.len() > SLUG_MAX_LEN {
+                break;
+            }
+            if hyphen_len == 1 {
+                slug.push('-');
             }
+            slug.push_str(&latin);
+            pending_hyphen = false;
+        } else {
+            pending_hyphen = true;
         }
     }
 
-    let overall_progress = if total_target > 0.0 {
-        ((total_amount / total_target * 100.0) as i32).min(100).max(0)
-    } else {
-        0
-    };
-
-    let avg_participants = if total_count > 0 {
-        total_participants as f64 / total_count as f64
-    } else {
-        0.0
-    };
+    slug
+}
 
-    let result = serde_json::json!({
-        "total_count": total_count,
-        "active_count": active_count,
-        "total_amount": total_amount,
-        "total_target": total_target,
-        "overall_progress": overall_progress,
-        "avg_participants": (avg_participants * 10.0).round() / 10.0,
-        "cities": cities,
-        "by_status": status_counts,
-        "formatted_total_amount": format_currency_value(total_amount),
-        "formatted_total_target": format_currency_value(total_target),
-    });
+/// Truncate text to at most `max_chars` graphemes, ending with "…" when cut
+/// The ellipsis counts toward the limit; trailing whitespace before it is dropped.
+/// Text that already fits is returned unchanged; `max_chars <= 0` gives ""
+#[wasm_bindgen]
+pub fn truncate_text(text: &str, max_chars: i32) -> String {
+    if max_chars <= 0 {
+        return String::new();
+    }
+    let max_chars = max_chars as usize;
+    if text.graphemes(true).nth(max_chars).is_none() {
+        return text.to_string();
+    }
 
-    serde_json::to_string(&result).unwrap_or_else(|_| "{}".to_string())
+    let kept: String = text.graphemes(true).take(max_chars - 1).collect();
+    format!("{}…", kept.trim_end())
 }
 
-/// Message data for batch operations
-#[derive(Serialize, Deserialize, Clone)]
-struct Message {
-    id: Option<i64>,
-    text: Option<String>,
-    user: Option<MessageUser>,
-    message_type: Option<String>,
-    created_at: Option<String>,
+/// Message length as users perceive it: grapheme clusters, so a compound emoji
+/// (family, flag, skin tone) counts as 1
+#[wasm_bindgen]
+pub fn message_length(text: &str) -> i32 {
+    text.graphemes(true).count().min(i32::MAX as usize) as i32
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-struct MessageUser {
-    id: Option<i64>,
-    first_name: Option<String>,
-    last_name: Option<String>,
+/// Check that a message fits within `max` graphemes (see `message_length`)
+#[wasm_bindgen]
+pub fn message_length_ok(text: &str, max: i32) -> bool {
+    if max < 0 {
+        return false;
+    }
+    text.graphemes(true).nth(max as usize).is_none()
 }
 
-/// Batch-process messages: format text, compute date groups, format times
-/// Input: JSON array of messages, current user ID
-/// Output: JSON array with formatted fields
+/// Average reading speed used by `text_stats`, in words per minute
+const READING_WORDS_PER_MINUTE: usize = 180;
+
+/// Character count, word count and estimated reading time of a text
+/// chars counts graphemes, words are Unicode word segments; reading_minutes is
+/// rounded up at ~180 wpm and at least 1 for non-empty text
+/// Returns JSON: { "chars": n, "words": n, "reading_minutes": n }
 #[wasm_bindgen]
-pub fn batch_process_messages(json_input: &str, current_user_id: i64) -> String {
-    let messages: Vec<Message> = match serde_json::from_str(json_input) {
-        Ok(m) => m,
-        Err(_) => return "[]".to_string(),
+pub fn text_stats(text: &str) -> String {
+    if text.trim().is_empty() {
+        return serde_json::json!({ "chars": 0, "words": 0, "reading_minutes": 0 }).to_string();
+    }
+
+    let chars = text.graphemes(true).count();
+    let words = text.unicode_words().count();
+    let reading_minutes = words.div_ceil(READING_WORDS_PER_MINUTE).max(1);
+
+    serde_json::json!({
+        "chars": chars,
+        "words": words,
+        "reading_minutes": reading_minutes,
+    }).to_string()
+}
+
+/// HTML-escape text and wrap case-insensitive occurrences of each query word in <mark>
+/// Matching runs on the raw text, so entities such as `&amp;` are never split;
+/// overlapping or adjacent matches are merged into a single <mark>
+#[wasm_bindgen]
+pub fn highlight_terms(text: &str, query: &str) -> String {
+    // Lowercase char by char so offsets in `folded` line up with `text`
+    let fold = |s: &str| -> String {
+        s.chars().map(|c| c.to_lowercase().next().unwrap_or(c)).collect()
     };
+    let folded_query = fold(query);
+    let words: Vec<&str> = folded_query.split_whitespace().collect();
+    let ranges = match_ranges(&fold(text), &words);
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len() + ranges.len() * 13);
+    let mut pos = 0;
+    for [start, end] in ranges {
+        result.push_str(&escape_html(&chars[pos..start].iter().collect::<String>()));
+        result.push_str("<mark>");
+        result.push_str(&escape_html(&chars[start..end].iter().collect::<String>()));
+        result.push_str("</mark>");
+        pos = end;
+    }
+    result.push_str(&escape_html(&chars[pos..].iter().collect::<String>()));
 
-    let now_ms = js_sys::Date::now();
-    let now_date = js_sys::Date::new(&JsValue::from_f64(now_ms));
-    let today_str = format!("{}-{:02}-{:02}",
-        now_date.get_full_year(),
-        now_date.get_month() + 1,
-        now_date.get_date()
-    );
-
-    let yesterday_ms = now_ms - 86_400_000.0;
-    let yesterday_date = js_sys::Date::new(&JsValue::from_f64(yesterday_ms));
-    let yesterday_str = format!("{}-{:02}-{:02}",
-        yesterday_date.get_full_year(),
-        yesterday_date.get_month() + 1,
-        yesterday_date.get_date()
-    );
+    result
+}
 
-    let mut last_date_group = String::new();
-    let mut results: Vec<serde_json::Value> = Vec::with_capacity(messages.len());
+// ──────────────────────────────────────────────
+// Date and time helpers
+// ──────────────────────────────────────────────
 
-    for msg in &messages {
-        let text = msg.text.as_deref().unwrap_or("");
-        let msg_type = msg.message_type.as_deref().unwrap_or("text");
-        let is_system = msg_type == "system";
+/// Calendar date and time of a UTC millisecond timestamp (month is 0-based, like JS)
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct DateParts {
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+}
 
-        let is_own = msg.user.as_ref()
-            .and_then(|u| u.id)
-            .map(|uid| uid == current_user_id)
-            .unwrap_or(false);
+impl DateParts {
+    /// Split a millisecond timestamp into UTC calendar fields
+    /// Apply a timezone offset to `ms` beforehand to get local fields
+    fn from_ms(ms: f64) -> Self {
+        let total_minutes = (ms / 60_000.0).floor() as i64;
+        let days = total_minutes.div_euclid(1440);
+        let minute_of_day = total_minutes.rem_euclid(1440);
+        let (year, month, day) = civil_from_days(days);
+        DateParts {
+            year,
+            month: month - 1,
+            day,
+            hour: (minute_of_day / 60) as u32,
+            minute: (minute_of_day % 60) as u32,
+        }
+    }
 
-        // Format message text (only for non-system messages)
-        let formatted_text = if is_system {
-            text.to_string()
-        } else {
-            format_message_text(text)
-        };
+    fn same_day(&self, other: &DateParts) -> bool {
+        self.year == other.year && self.month == other.month && self.day == other.day
+    }
+}
 
-        // Compute date group
-        let date_group = if let Some(created_at) = &msg.created_at {
-            let date = js_sys::Date::new(&JsValue::from_str(created_at));
-            let date_str = format!("{}-{:02}-{:02}",
-                date.get_full_year(),
-                date.get_month() + 1,
-                date.get_date()
-            );
-            if date_str == today_str {
-                "Сегодня".to_string()
-            } else if date_str == yesterday_str {
-                "Вчера".to_string()
-            } else {
-                format_ru_date(date.get_date(), date.get_month(), date.get_full_year(),
-                    now_date.get_full_year())
+/// Convert days since 1970-01-01 to a (year, month 1-12, day) civil date
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month, day)
+}
+
+/// Convert a (year, month 1-12, day) civil date to days since 1970-01-01
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Parse an ISO 8601 timestamp to milliseconds since the epoch
+/// Accepts `YYYY-MM-DD` and `YYYY-MM-DDTHH:MM[:SS[.fff]][Z|±HH:MM]`
+/// (a space may replace `T`); timestamps without a zone are taken as UTC
+/// (`parse_timestamp_ms` reads them as local time in the browser, like JS Date)
+fn parse_iso_ms(input: &str) -> Option<f64> {
+    let input = input.trim();
+    let bytes = input.as_bytes();
+    let num = |range: std::ops::Range<usize>| -> Option<u32> {
+        let part = input.get(range)?;
+        if part.bytes().all(|b| b.is_ascii_digit()) { part.parse().ok() } else { None }
+    };
+
+    if bytes.len() < 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+    let year = num(0..4)? as i32;
+    let month = num(5..7)?;
+    let day = num(8..10)?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    let mut ms = days_from_civil(year, month, day) as f64 * 86_400_000.0;
+    if bytes.len() == 10 {
+        return Some(ms);
+    }
+
+    if !(bytes[10] == b'T' || bytes[10] == b' ') || bytes.len() < 16 || bytes[13] != b':' {
+        return None;
+    }
+    let hour = num(11..13)?;
+    let minute = num(14..16)?;
+    let mut pos = 16;
+    let mut second = 0.0;
+    if bytes.get(pos) == Some(&b':') {
+        let sec_end = input[pos + 1..].find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .map_or(input.len(), |i| pos + 1 + i);
+        second = input[pos + 1..sec_end].parse::<f64>().ok()?;
+        pos = sec_end;
+    }
+    if hour > 23 || minute > 59 || second >= 61.0 {
+        return None;
+    }
+    ms += (hour as f64 * 3600.0 + minute as f64 * 60.0 + second) * 1000.0;
+
+    match &input[pos..] {
+        "" | "Z" | "z" => Some(ms),
+        zone => {
+            let sign = match zone.as_bytes()[0] {
+                b'+' => 1.0,
+                b'-' => -1.0,
+                _ => return None,
+            };
+            let zone = zone[1..].replace(':', "");
+            if zone.len() != 4 || !zone.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
             }
-        } else {
-            String::new()
-        };
+            let zone_minutes = zone[..2].parse::<f64>().ok()? * 60.0 + zone[2..].parse::<f64>().ok()?;
+            Some(ms - sign * zone_minutes * 60_000.0)
+        }
+    }
+}
 
-        let show_date_divider = !date_group.is_empty() && date_group != last_date_group;
-        if show_date_divider {
-            last_date_group = date_group.clone();
+/// Parse a timestamp string to milliseconds, falling back to the JS Date parser
+/// (wasm only) for formats `parse_iso_ms` doesn't understand
+/// A date-time without a zone goes to JS Date too, which reads it as browser local
+/// time rather than UTC (outside wasm it stays UTC, as in `parse_iso_ms`)
+fn parse_timestamp_ms(input: &str) -> Option<f64> {
+    let js_parse = || {
+        if !cfg!(target_arch = "wasm32") {
+            return None;
         }
+        let ms = js_sys::Date::new(&JsValue::from_str(input)).get_time();
+        if ms.is_nan() { None } else { Some(ms) }
+    };
+    match parse_iso_ms(input) {
+        Some(ms) if !is_zoneless_date_time(input) || !cfg!(target_arch = "wasm32") => Some(ms),
+        Some(_) | None => js_parse(),
+    }
+}
 
-        // Format time
-        let formatted_time = if let Some(created_at) = &msg.created_at {
-            let date = js_sys::Date::new(&JsValue::from_str(created_at));
-            format!("{:02}:{:02}", date.get_hours(), date.get_minutes())
-        } else {
-            String::new()
-        };
+/// Internal helper: whether an ISO timestamp has a time of day but no zone designator
+/// ("2026-03-10T08:05:00", not "2026-03-10" or "2026-03-10T08:05:00Z")
+fn is_zoneless_date_time(input: &str) -> bool {
+    match input.trim().get(10..) {
+        Some(time) if !time.is_empty() => {
+            !time.ends_with(['Z', 'z']) && !time.contains(['+', '-'])
+        }
+        _ => false,
+    }
+}
 
-        // Sender info
-        let sender_name = if !is_own && !is_system {
-            msg.user.as_ref()
-                .and_then(|u| u.first_name.clone())
-                .unwrap_or_default()
-        } else {
-            String::new()
-        };
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
 
-        let mut obj = serde_json::json!({
-            "id": msg.id,
-            "text": text,
-            "formatted_text": formatted_text,
-            "is_own": is_own,
-            "is_system": is_system,
-            "formatted_time": formatted_time,
-            "sender_name": sender_name,
-        });
+// ──────────────────────────────────────────────
+// High-performance batch processing functions
+// ──────────────────────────────────────────────
 
-        if show_date_divider {
-            obj["date_divider"] = serde_json::json!(date_group);
+/// Procurement data for batch operations (internal)
+#[derive(Serialize, Deserialize, Clone)]
+struct Procurement {
+    id: i64,
+    title: String,
+    description: Option<String>,
+    city: Option<String>,
+    status: Option<String>,
+    current_amount: Option<f64>,
+    target_amount: Option<f64>,
+    deadline: Option<String>,
+    participant_count: Option<i32>,
+    category: Option<String>,
+    organizer_name: Option<String>,
+    created_at: Option<String>,
+    #[serde(default)]
+    progress: Option<f64>,
+}
+
+/// Borrowed view of a `Procurement` for read-only paths (search, sort)
+/// String fields borrow from the JSON input unless they contain escapes; the field
+/// set and types mirror `Procurement` so the same inputs are accepted and rejected
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct ProcurementRef<'a> {
+    id: i64,
+    #[serde(borrow)]
+    title: Cow<'a, str>,
+    #[serde(borrow, default, deserialize_with = "borrow_optional_str")]
+    description: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "borrow_optional_str")]
+    city: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "borrow_optional_str")]
+    status: Option<Cow<'a, str>>,
+    current_amount: Option<f64>,
+    target_amount: Option<f64>,
+    #[serde(borrow, default, deserialize_with = "borrow_optional_str")]
+    deadline: Option<Cow<'a, str>>,
+    participant_count: Option<i32>,
+    #[serde(borrow, default, deserialize_with = "borrow_optional_str")]
+    category: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "borrow_optional_str")]
+    organizer_name: Option<Cow<'a, str>>,
+    #[serde(borrow, default, deserialize_with = "borrow_optional_str")]
+    created_at: Option<Cow<'a, str>>,
+    #[serde(default)]
+    progress: Option<f64>,
+}
+
+/// Internal helper: deserialize an optional string, borrowing it from the input when possible
+/// (serde only borrows a bare `Cow<str>`, an `Option<Cow<str>>` would always allocate)
+fn borrow_optional_str<'de: 'a, 'a, D>(deserializer: D) -> Result<Option<Cow<'a, str>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Borrowed<'b>(#[serde(borrow)] Cow<'b, str>);
+
+    Ok(Option::<Borrowed>::deserialize(deserializer)?.map(|s| s.0))
+}
+
+impl Procurement {
+    /// Internal helper: borrow an owned procurement as a `ProcurementRef`
+    fn borrowed(&self) -> ProcurementRef<'_> {
+        ProcurementRef {
+            id: self.id,
+            title: Cow::Borrowed(&self.title),
+            description: self.description.as_deref().map(Cow::Borrowed),
+            city: self.city.as_deref().map(Cow::Borrowed),
+            status: self.status.as_deref().map(Cow::Borrowed),
+            current_amount: self.current_amount,
+            target_amount: self.target_amount,
+            deadline: self.deadline.as_deref().map(Cow::Borrowed),
+            participant_count: self.participant_count,
+            category: self.category.as_deref().map(Cow::Borrowed),
+            organizer_name: self.organizer_name.as_deref().map(Cow::Borrowed),
+            created_at: self.created_at.as_deref().map(Cow::Borrowed),
+            progress: self.progress,
         }
+    }
+}
 
-        results.push(obj);
+/// Expected JSON type of each `Procurement` field, with whether it is required
+const PROCUREMENT_FIELD_TYPES: [(&str, JsonFieldType, bool); 13] = [
+    ("id", JsonFieldType::Integer, true),
+    ("title", JsonFieldType::String, true),
+    ("description", JsonFieldType::String, false),
+    ("city", JsonFieldType::String, false),
+    ("status", JsonFieldType::String, false),
+    ("current_amount", JsonFieldType::Number, false),
+    ("target_amount", JsonFieldType::Number, false),
+    ("deadline", JsonFieldType::String, false),
+    ("participant_count", JsonFieldType::Integer32, false),
+    ("category", JsonFieldType::String, false),
+    ("organizer_name", JsonFieldType::String, false),
+    ("created_at", JsonFieldType::String, false),
+    ("progress", JsonFieldType::Number, false),
+];
+
+/// JSON value kinds accepted by procurement fields (optional fields also accept null)
+#[derive(Clone, Copy)]
+enum JsonFieldType {
+    String,
+    Number,
+    Integer,
+    Integer32,
+}
+
+impl JsonFieldType {
+    fn name(self) -> &'static str {
+        match self {
+            JsonFieldType::String => "string",
+            JsonFieldType::Number => "number",
+            JsonFieldType::Integer | JsonFieldType::Integer32 => "integer",
+        }
     }
 
-    serde_json::to_string(&results).unwrap_or_else(|_| "[]".to_string())
+    fn accepts(self, value: &serde_json::Value) -> bool {
+        match self {
+            JsonFieldType::String => value.is_string(),
+            JsonFieldType::Number => value.is_number(),
+            JsonFieldType::Integer => value.is_i64(),
+            JsonFieldType::Integer32 => value.as_i64().is_some_and(|n| i32::try_from(n).is_ok()),
+        }
+    }
 }
 
-/// Format a Russian date string
-fn format_ru_date(day: u32, month: u32, year: u32, current_year: u32) -> String {
-    let month_names = [
-        "января", "февраля", "марта", "апреля", "мая", "июня",
-        "июля", "августа", "сентября", "октября", "ноября", "декабря"
-    ];
-    let month_name = month_names.get(month as usize).unwrap_or(&"");
-    if year != current_year {
-        format!("{} {} {}", day, month_name, year)
-    } else {
-        format!("{} {}", day, month_name)
+/// Internal helper: JSON type name of a value for error messages
+fn json_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
     }
 }
 
-/// Search within messages by text content
-/// Returns JSON array of matching message indices
+/// Check procurement JSON against the shape the batch functions expect, before processing
+/// Reports missing required fields (id, title) and wrong types (e.g. target_amount as a
+/// string); unknown fields are ignored. An empty array means the input is valid
+/// Returns JSON array of { "index": n, "field": "...", "error": "..." }; input that is
+/// not a JSON array gives a single entry with null index and field
 #[wasm_bindgen]
-pub fn search_messages(json_input: &str, query: &str) -> String {
-    if query.trim().is_empty() {
-        return "[]".to_string();
+pub fn validate_procurement_json(json_input: &str) -> String {
+    let root_error = |error: String| {
+        serde_json::json!([{ "index": null, "field": null, "error": error }]).to_string()
+    };
+    let items = match serde_json::from_str::<serde_json::Value>(json_input) {
+        Ok(serde_json::Value::Array(items)) => items,
+        Ok(other) => return root_error(format!("expected an array of procurements, got {}", json_type_name(&other))),
+        Err(e) => return root_error(e.to_string()),
+    };
+
+    let mut errors: Vec<serde_json::Value> = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        let Some(object) = item.as_object() else {
+            errors.push(serde_json::json!({
+                "index": index,
+                "field": null,
+                "error": format!("expected an object, got {}", json_type_name(item)),
+            }));
+            continue;
+        };
+
+        for (field, expected, required) in PROCUREMENT_FIELD_TYPES {
+            let error = match object.get(field) {
+                None if required => "required field is missing".to_string(),
+                None => continue,
+                Some(serde_json::Value::Null) if !required => continue,
+                Some(value) if expected.accepts(value) => continue,
+                Some(value) if expected.name() == json_type_name(value) => {
+                    "integer out of range".to_string()
+                }
+                Some(value) => format!("expected {}, got {}", expected.name(), json_type_name(value)),
+            };
+            errors.push(serde_json::json!({ "index": index, "field": field, "error": error }));
+        }
     }
 
-    let messages: Vec<Message> = match serde_json::from_str(json_input) {
-        Ok(m) => m,
+    serde_json::to_string(&errors).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Batch-process procurements: compute progress, days left, and format currency in one pass
+/// Input: JSON array of procurements
+/// Output: JSON array with computed fields added (including `urgency`: "overdue", "soon"
+/// within 3 days, "week" within 7, "ok", or "none" without a deadline)
+#[wasm_bindgen]
+pub fn batch_process_procurements(json_input: &str) -> String {
+    batch_process_procurements_at(json_input, js_sys::Date::now())
+}
+
+/// Batch-process procurements with MessagePack input and output (`Uint8Array` in JS)
+/// The schema is identical to `batch_process_procurements`: an array of procurement
+/// maps in, an array of maps with the same computed fields out (field names as keys)
+#[wasm_bindgen]
+pub fn batch_process_procurements_msgpack(input: &[u8]) -> Vec<u8> {
+    batch_process_procurements_msgpack_at(input, js_sys::Date::now())
+}
+
+/// Batch-process one window of procurements, for requesting large catalogs progressively
+/// Output matches the `[offset, offset + limit)` slice of `batch_process_procurements`;
+/// `limit <= 0` means "to the end" and offsets past the end yield "[]".
+/// The whole input is re-parsed on every call, so this only bounds the work and
+/// output size per call; for real streaming use `batch_process_procurements_msgpack`
+#[wasm_bindgen]
+pub fn batch_process_procurements_chunk(json_input: &str, offset: i32, limit: i32) -> String {
+    batch_process_procurements_chunk_at(json_input, offset, limit, js_sys::Date::now())
+}
+
+/// Internal helper: chunked JSON batch processing against an explicit current time
+fn batch_process_procurements_chunk_at(json_input: &str, offset: i32, limit: i32, now_ms: f64) -> String {
+    let procurements: Vec<Procurement> = match serde_json::from_str(json_input) {
+        Ok(p) => p,
         Err(_) => return "[]".to_string(),
     };
 
-    let query_lower = query.to_lowercase();
-    let results: Vec<serde_json::Value> = messages.iter().enumerate().filter_map(|(i, msg)| {
-        let text = msg.text.as_deref().unwrap_or("");
-        if text.to_lowercase().contains(&query_lower) {
-            Some(serde_json::json!({
-                "index": i,
-                "id": msg.id,
-            }))
-        } else {
-            None
-        }
-    }).collect();
+    let start = (offset.max(0) as usize).min(procurements.len());
+    let end = if limit > 0 {
+        start.saturating_add(limit as usize).min(procurements.len())
+    } else {
+        procurements.len()
+    };
 
+    let results = process_procurements(&procurements[start..end], now_ms);
     serde_json::to_string(&results).unwrap_or_else(|_| "[]".to_string())
 }
 
-// ──────────────────────────────────────────────
-// Performance measurement utilities
-// ──────────────────────────────────────────────
+/// Internal helper: JSON batch processing against an explicit current time
+fn batch_process_procurements_at(json_input: &str, now_ms: f64) -> String {
+    let procurements: Vec<Procurement> = match serde_json::from_str(json_input) {
+        Ok(p) => p,
+        Err(_) => return "[]".to_string(),
+    };
 
-/// Run a performance benchmark for batch processing
-/// Generates N random procurements and processes them, returning elapsed time in ms
-#[wasm_bindgen]
-pub fn benchmark_batch_processing(count: i32) -> f64 {
-    let start = js_sys::Date::now();
+    let results = process_procurements(&procurements, now_ms);
+    serde_json::to_string(&results).unwrap_or_else(|_| "[]".to_string())
+}
 
-    let mut procurements: Vec<serde_json::Value> = Vec::with_capacity(count as usize);
-    for i in 0..count {
-        procurements.push(serde_json::json!({
-            "id": i,
-            "title": format!("Закупка тестовая #{}", i),
-            "description": format!("Описание закупки #{} для бенчмарка", i),
-            "city": if i % 3 == 0 { "Москва" } else if i % 3 == 1 { "Санкт-Петербург" } else { "Новосибирск" },
-            "status": if i % 4 == 0 { "active" } else if i % 4 == 1 { "draft" } else if i % 4 == 2 { "completed" } else { "payment" },
-            "current_amount": (i as f64) * 150.0 + 500.0,
-            "target_amount": (i as f64) * 200.0 + 10000.0,
-            "deadline": "2026-12-31T23:59:59Z",
-            "participant_count": (i % 50) + 1,
-            "category": format!("Категория {}", i % 5),
-            "organizer_name": format!("Организатор {}", i % 10),
-            "created_at": "2026-01-15T10:00:00Z",
-        }));
+/// Internal helper: MessagePack batch processing against an explicit current time
+/// Invalid input yields an empty array, mirroring the JSON version
+fn batch_process_procurements_msgpack_at(input: &[u8], now_ms: f64) -> Vec<u8> {
+    let results = match rmp_serde::from_slice::<Vec<Procurement>>(input) {
+        Ok(procurements) => process_procurements(&procurements, now_ms),
+        Err(_) => Vec::new(),
+    };
+    rmp_serde::to_vec_named(&results).unwrap_or_default()
+}
+
+/// Internal helper: compute progress, days left and formatted amounts for each procurement
+fn process_procurements(procurements: &[Procurement], now_ms: f64) -> Vec<serde_json::Value> {
+    procurements.iter().map(|p| {
+        let current = p.current_amount.unwrap_or(0.0);
+        let target = p.target_amount.unwrap_or(0.0);
+        let progress = calculate_progress(current, target);
+
+        // Parse the deadline once; an unparseable one counts as 0 days left but has no urgency
+        let deadline_ms = p.deadline.as_deref().map(|d| parse_timestamp_ms(d).unwrap_or(f64::NAN));
+        let days_left = deadline_ms.map(|ms| (((ms - now_ms) / 86_400_000.0) as i32).max(0));
+        let urgency = urgency_bucket(deadline_ms.filter(|ms| !ms.is_nan()), now_ms);
+
+        let formatted_current = format_currency_value(current);
+        let formatted_target = format_currency_value(target);
+
+        let mut obj = serde_json::json!({
+            "id": p.id,
+            "title": p.title,
+            "description": p.description,
+            "city": p.city,
+            "status": p.status,
+            "current_amount": current,
+            "target_amount": target,
+            "participant_count": p.participant_count,
+            "category": p.category,
+            "organizer_name": p.organizer_name,
+            "created_at": p.created_at,
+            "deadline": p.deadline,
+            "progress": progress,
+            "formatted_current": formatted_current,
+            "formatted_target": formatted_target,
+            "urgency": urgency,
+        });
+
+        if let Some(days) = days_left {
+            obj["days_left"] = serde_json::json!(days);
+        }
+
+        obj
+    }).collect()
+}
+
+/// Internal helper: urgency bucket of a deadline for card color-coding
+/// "overdue" (passed), "soon" (within 3 days), "week" (within 7 days), "ok", or "none"
+/// without a (parseable) deadline
+fn urgency_bucket(deadline_ms: Option<f64>, now_ms: f64) -> &'static str {
+    const DAY_MS: f64 = 86_400_000.0;
+    match deadline_ms.map(|ms| ms - now_ms) {
+        None => "none",
+        Some(diff) if diff < 0.0 => "overdue",
+        Some(diff) if diff <= 3.0 * DAY_MS => "soon",
+        Some(diff) if diff <= 7.0 * DAY_MS => "week",
+        Some(_) => "ok",
     }
+}
 
-    let json = serde_json::to_string(&procurements).unwrap_or_default();
+/// Symbol placement and separators used to format an amount in one currency
+struct MoneyFormat<'a> {
+    symbol: &'a str,
+    symbol_first: bool,
+    group_separator: char,
+    decimal_mark: char,
+}
 
-    // Run batch processing
-    let _ = batch_process_procurements(&json);
-    let _ = search_procurements(&json, "тестовая Москва");
-    let _ = sort_procurements(&json, "amount", "desc");
-    let _ = aggregate_procurement_stats(&json);
+/// Ruble formatting used by `format_currency`
+const RUB_FORMAT: MoneyFormat<'static> = MoneyFormat {
+    symbol: "\u{20bd}",
+    symbol_first: false,
+    group_separator: ' ',
+    decimal_mark: ',',
+};
+
+/// Internal helper: formatting rules for an ISO 4217 code (case-insensitive)
+/// Unknown codes use the ruble layout with the code itself as the symbol
+fn money_format(currency: &str) -> MoneyFormat<'_> {
+    let suffix = |symbol| MoneyFormat { symbol, ..RUB_FORMAT };
+    match currency.trim().to_ascii_uppercase().as_str() {
+        "RUB" => RUB_FORMAT,
+        "KZT" => suffix("\u{20b8}"),
+        "BYN" => suffix("Br"),
+        "EUR" => suffix("\u{20ac}"),
+        "USD" => MoneyFormat { symbol: "$", symbol_first: true, group_separator: ',', decimal_mark: '.' },
+        _ => suffix(currency.trim()),
+    }
+}
 
-    let end = js_sys::Date::now();
-    end - start
+/// Format a money amount for a currency code: RUB (1 234,50 ₽), KZT (1 234,50 ₸),
+/// BYN (1 234,50 Br), EUR (1 234,50 €) and USD ($1,234.50); other codes are shown
+/// after the amount in the ruble layout. Kopecks/cents are omitted when zero
+#[wasm_bindgen]
+pub fn format_money(amount: f64, currency: &str) -> String {
+    format_money_value(amount, &money_format(currency))
 }
 
-// ──────────────────────────────────────────────
-// Unit tests (run with `cargo test`)
-// ──────────────────────────────────────────────
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Internal helper: format currency for reuse in batch functions
+fn format_currency_value(amount: f64) -> String {
+    format_money_value(amount, &RUB_FORMAT)
+}
+
+/// Largest absolute amount the money formatters will render; beyond it (or for
+/// NaN / infinity) they return `MONEY_SENTINEL`
+const MAX_MONEY_AMOUNT: f64 = 1e21;
+
+/// Placeholder shown instead of a non-finite or out-of-range amount
+const MONEY_SENTINEL: &str = "\u{2014}";
+
+/// Internal helper: format an amount with the given currency layout
+/// The sign is detected up front and the absolute value is formatted, so negative
+/// fractions keep their sign and -0.0 renders as plain "0 ₽".
+/// Amounts of 2^53 and above have no fractional part in f64; their integer digits are
+/// taken from the exact decimal expansion rather than an i64 cast
+fn format_money_value(amount: f64, format: &MoneyFormat) -> String {
+    if !amount.is_finite() || amount.abs() > MAX_MONEY_AMOUNT {
+        return MONEY_SENTINEL.to_string();
+    }
+    let negative = amount.is_sign_negative();
+    let abs = amount.abs();
+    let (int_str, fraction) = if abs < 9_007_199_254_740_992.0 {
+        let mut integer = abs.trunc() as i64;
+        let mut fraction = (abs.fract() * 100.0).round() as i64;
+        // Rounding the fraction up to 100 (e.g. 9.999) carries into the integer part
+        if fraction == 100 {
+            integer += 1;
+            fraction = 0;
+        }
+        (integer.to_string(), fraction)
+    } else {
+        (format!("{:.0}", abs), 0)
+    };
+
+    // Format with thousands separator
+    let mut digits = String::new();
+    for (i, ch) in int_str.chars().rev().enumerate() {
+        if i > 0 && i % 3 == 0 {
+            digits.push(format.group_separator);
+        }
+        digits.push(ch);
+    }
+    let mut number: String = digits.chars().rev().collect();
+    if fraction > 0 {
+        number.push(format.decimal_mark);
+        number.push_str(&format!("{:02}", fraction));
+    }
+
+    let sign = if negative && (int_str != "0" || fraction > 0) { "-" } else { "" };
+    if format.symbol_first {
+        format!("{}{}{}", sign, format.symbol, number)
+    } else {
+        format!("{}{} {}", sign, number, format.symbol)
+    }
+}
+
+/// Fuzzy search procurements by query string
+/// max_distance: Levenshtein distance up to which a query word still matches a title
+/// word when it isn't a substring of the title (0 disables fuzzy matching, 2 is typical);
+/// capped at a third of the query word's length
+/// Matching ignores case and diacritics, and treats "ё" as "е" ("cafe" finds "Café");
+/// "й" stays distinct from "и". Case folding is Unicode's default (locale-specific
+/// rules such as Turkish dotted/dotless I are out of scope)
+/// Scoring weights are described on `procurement_match_score`
+/// offset/limit: page of results to return (limit <= 0 means no limit)
+/// translit: also match Cyrillic and Latin spellings of the same word ("moskva" / "москва")
+/// top_n: when > 0, only the best top_n results are kept (selected with a bounded heap
+/// instead of sorting every match); offset/limit then page within them
+/// Returns JSON array of matching procurement IDs with relevance scores, sorted by relevance;
+/// equal scores are ordered by descending id (newer first), so output is reproducible
+#[wasm_bindgen]
+pub fn search_procurements(json_input: &str, query: &str, max_distance: u32, offset: i32, limit: i32, translit: bool, top_n: i32) -> String {
+    if query.trim().is_empty() {
+        return "[]".to_string();
+    }
+
+    let procurements: Vec<ProcurementRef> = match serde_json::from_str(json_input) {
+        Ok(p) => p,
+        Err(_) => return "[]".to_string(),
+    };
+
+    let output = search_results(&procurements, query, max_distance, offset, limit, translit, top_n);
+    serde_json::to_string(&output).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Internal helper: `{id, score}` entries of a search, sorted by relevance and paginated
+fn search_results(procurements: &[ProcurementRef], query: &str, max_distance: u32, offset: i32, limit: i32, translit: bool, top_n: i32) -> Vec<serde_json::Value> {
+    let search = SearchQuery::new(query, translit);
+    if search.is_empty() {
+        return Vec::new();
+    }
+
+    let scored = procurements.iter().enumerate().filter_map(|(index, p)| {
+        let score = procurement_match_score(p, &search, max_distance as usize);
+        (score > 0.0).then_some(SearchHit { id: p.id, score, index })
+    });
+
+    let results = if top_n > 0 {
+        top_search_hits(scored, top_n as usize)
+    } else {
+        let mut all: Vec<SearchHit> = scored.collect();
+        // Best first: relevance score descending
+        all.sort_by(|a, b| b.cmp(a));
+        all
+    };
+
+    paginate(results, offset, limit).iter().map(|hit| {
+        serde_json::json!({"id": hit.id, "score": hit.score})
+    }).collect()
+}
+
+/// A scored search match; ordered so that a better match compares greater
+/// (higher score, then higher id, then earlier position in the input)
+struct SearchHit {
+    id: i64,
+    score: f64,
+    index: usize,
+}
+
+impl Ord for SearchHit {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.score.total_cmp(&other.score)
+            .then(self.id.cmp(&other.id))
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for SearchHit {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for SearchHit {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for SearchHit {}
+
+/// Internal helper: the best `n` hits, best first, keeping a bounded min-heap of size `n`
+/// instead of sorting every hit (O(m log n) for m hits)
+fn top_search_hits(hits: impl Iterator<Item = SearchHit>, n: usize) -> Vec<SearchHit> {
+    let mut heap: std::collections::BinaryHeap<std::cmp::Reverse<SearchHit>> =
+        std::collections::BinaryHeap::with_capacity(n + 1);
+    for hit in hits {
+        if heap.len() < n {
+            heap.push(std::cmp::Reverse(hit));
+        } else if heap.peek().is_some_and(|worst| hit > worst.0) {
+            heap.pop();
+            heap.push(std::cmp::Reverse(hit));
+        }
+    }
+    // Ascending order of Reverse is descending order of hits
+    heap.into_sorted_vec().into_iter().map(|std::cmp::Reverse(hit)| hit).collect()
+}
+
+/// Search procurements like `search_procurements` (exact matching, no typo tolerance)
+/// and report where query words matched in the title
+/// Returns JSON array of {id, score, title_ranges}, where title_ranges is a list of
+/// `[start, end)` pairs in char (Unicode scalar) offsets into the title, sorted and merged
+#[wasm_bindgen]
+pub fn search_procurements_highlighted(json_input: &str, query: &str) -> String {
+    if query.trim().is_empty() {
+        return "[]".to_string();
+    }
+
+    let procurements: Vec<Procurement> = match serde_json::from_str(json_input) {
+        Ok(p) => p,
+        Err(_) => return "[]".to_string(),
+    };
+
+    let search = SearchQuery::new(query, false);
+    let query_words: Vec<&str> = search.words.iter().map(String::as_str).collect();
+
+    let mut results: Vec<(&Procurement, f64)> = procurements.iter().filter_map(|p| {
+        let score = procurement_match_score(&p.borrowed(), &search, 0);
+        if score > 0.0 {
+            Some((p, score))
+        } else {
+            None
+        }
+    }).collect();
+
+    // Sort by relevance score descending, ties by descending id as in `search_procurements`
+    results.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.0.id.cmp(&a.0.id)));
+
+    let output: Vec<serde_json::Value> = results.iter().map(|(p, score)| {
+        serde_json::json!({
+            "id": p.id,
+            "score": score,
+            "title_ranges": title_match_ranges(&p.title, &query_words),
+        })
+    }).collect();
+
+    serde_json::to_string(&output).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Weight factor applied to matches found only after transliteration
+const TRANSLIT_MATCH_FACTOR: f64 = 0.8;
+
+/// Search query split into normalized words, prepared once per search rather than per item
+struct SearchQuery {
+    /// Query words in search form (see `search_normalize`)
+    words: Vec<String>,
+    /// Latin transliteration of each word; empty when transliteration is off
+    words_tr: Vec<String>,
+    translit: bool,
+}
+
+impl SearchQuery {
+    fn new(query: &str, translit: bool) -> Self {
+        let words: Vec<String> = search_normalize(query).split_whitespace().map(str::to_string).collect();
+        let words_tr = if translit {
+            words.iter().map(|w| transliterate_to_latin(w)).collect()
+        } else {
+            vec![String::new(); words.len()]
+        };
+        SearchQuery { words, words_tr, translit }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Internal helper: relevance score of a procurement for a prepared query
+/// Weights per word: title 10 (+5 prefix bonus, or up to 10 for a fuzzy title match),
+/// city 5, category 4 (+2 prefix bonus), organizer 3, description 2.
+/// With transliteration on, a word that only matches after transliterating both sides
+/// to Latin scores `TRANSLIT_MATCH_FACTOR` of the weight.
+fn procurement_match_score(p: &ProcurementRef, search: &SearchQuery, max_distance: usize) -> f64 {
+    let translit = search.translit;
+    let mut score: f64 = 0.0;
+    let title_lower = search_normalize(&p.title);
+    let desc_lower = search_normalize(p.description.as_deref().unwrap_or(""));
+    let city_lower = search_normalize(p.city.as_deref().unwrap_or(""));
+    let category_lower = search_normalize(p.category.as_deref().unwrap_or(""));
+    let org_lower = search_normalize(p.organizer_name.as_deref().unwrap_or(""));
+
+    let translit_of = |text: &str| if translit { transliterate_to_latin(text) } else { String::new() };
+    let title_tr = translit_of(&title_lower);
+    let desc_tr = translit_of(&desc_lower);
+    let city_tr = translit_of(&city_lower);
+    let category_tr = translit_of(&category_lower);
+    let org_tr = translit_of(&org_lower);
+
+    for (word, word_tr) in search.words.iter().zip(&search.words_tr) {
+        // Title matches (highest weight)
+        if let Some((factor, prefix)) = field_match(&title_lower, &title_tr, word, word_tr) {
+            score += 10.0 * factor;
+            if prefix {
+                score += 5.0 * factor; // prefix bonus
+            }
+        } else if max_distance > 0 {
+            // Typo tolerance: partial score for the closest title word, always below an exact match
+            score += fuzzy_word_score(word, &title_lower, max_distance) * 10.0;
+        }
+        // City match
+        if let Some((factor, _)) = field_match(&city_lower, &city_tr, word, word_tr) {
+            score += 5.0 * factor;
+        }
+        // Category match
+        if let Some((factor, prefix)) = field_match(&category_lower, &category_tr, word, word_tr) {
+            score += 4.0 * factor;
+            if prefix {
+                score += 2.0 * factor; // prefix bonus
+            }
+        }
+        // Organizer match
+        if let Some((factor, _)) = field_match(&org_lower, &org_tr, word, word_tr) {
+            score += 3.0 * factor;
+        }
+        // Description match (lower weight)
+        if let Some((factor, _)) = field_match(&desc_lower, &desc_tr, word, word_tr) {
+            score += 2.0 * factor;
+        }
+    }
+
+    score
+}
+
+/// Internal helper: match a query word against a field, directly or via transliteration
+/// Returns the weight factor and whether the field starts with the word
+fn field_match(field: &str, field_tr: &str, word: &str, word_tr: &str) -> Option<(f64, bool)> {
+    if field.contains(word) {
+        Some((1.0, field.starts_with(word)))
+    } else if !word_tr.is_empty() && field_tr.contains(word_tr) {
+        Some((TRANSLIT_MATCH_FACTOR, field_tr.starts_with(word_tr)))
+    } else {
+        None
+    }
+}
+
+/// Strip diacritics: "café" -> "cafe", "naïve" -> "naive"
+/// Text is NFC-composed, each character reduced to the base of its canonical
+/// decomposition, and leftover combining marks dropped. "й" is a letter of its own
+/// and kept; "ё" is folded to "е" only when `fold_yo` is set
+#[wasm_bindgen]
+pub fn fold_diacritics(text: &str, fold_yo: bool) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.nfc() {
+        let keep = matches!(ch, 'й' | 'Й') || (!fold_yo && matches!(ch, 'ё' | 'Ё'));
+        if keep {
+            result.push(ch);
+            continue;
+        }
+        let mut base = None;
+        unicode_normalization::char::decompose_canonical(ch, |part| {
+            if base.is_none() && !unicode_normalization::char::is_combining_mark(part) {
+                base = Some(part);
+            }
+        });
+        if let Some(base) = base {
+            result.push(base);
+        }
+    }
+    result
+}
+
+/// Internal helper: search form of text (lowercase, diacritics and ё folded)
+fn search_normalize(text: &str) -> String {
+    fold_diacritics(&text.to_lowercase(), true)
+}
+
+/// Transliterate lowercase Cyrillic to Latin (GOST 7.79-2000 system B, simplified:
+/// й -> y, х -> kh, ц -> ts, щ -> shch, ъ/ь dropped); other characters pass through
+fn transliterate_to_latin(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        let latin = match ch {
+            'а' => "a", 'б' => "b", 'в' => "v", 'г' => "g", 'д' => "d",
+            'е' | 'ё' | 'э' => "e", 'ж' => "zh", 'з' => "z", 'и' => "i", 'й' => "y",
+            'к' => "k", 'л' => "l", 'м' => "m", 'н' => "n", 'о' => "o",
+            'п' => "p", 'р' => "r", 'с' => "s", 'т' => "t", 'у' => "u",
+            'ф' => "f", 'х' => "kh", 'ц' => "ts", 'ч' => "ch", 'ш' => "sh",
+            'щ' => "shch", 'ъ' | 'ь' => "", 'ы' => "y", 'ю' => "yu", 'я' => "ya",
+            _ => {
+                result.push(ch);
+                continue;
+            }
+        };
+        result.push_str(latin);
+    }
+    result
+}
+
+/// Internal helper: `search_normalize` applied per base char and its combining marks,
+/// with the `[start, end)` char range of `text` that each output char came from
+/// (folding changes the char count, e.g. decomposed "e\u{301}" becomes "e")
+fn search_normalize_mapped(text: &str) -> (String, Vec<[usize; 2]>) {
+    let chars: Vec<char> = text.chars().collect();
+    let mut folded = String::with_capacity(text.len());
+    let mut spans = Vec::with_capacity(chars.len());
+    let mut start = 0;
+    while start < chars.len() {
+        let mut end = start + 1;
+        while end < chars.len() && unicode_normalization::char::is_combining_mark(chars[end]) {
+            end += 1;
+        }
+        let cluster: String = chars[start..end].iter().collect();
+        for ch in search_normalize(&cluster).chars() {
+            folded.push(ch);
+            spans.push([start, end]);
+        }
+        start = end;
+    }
+    (folded, spans)
+}
+
+/// Internal helper: `match_ranges` of `words` in the search form of `title`, mapped
+/// back to char offsets into the original title
+fn title_match_ranges(title: &str, words: &[&str]) -> Vec<[usize; 2]> {
+    let (folded, spans) = search_normalize_mapped(title);
+    match_ranges(&folded, words).into_iter()
+        .map(|[start, end]| [spans[start][0], spans[end - 1][1]])
+        .collect()
+}
+
+/// Internal helper: char-offset `[start, end)` ranges of every occurrence of `words`
+/// in `text`, sorted with overlapping ranges merged
+fn match_ranges(text: &str, words: &[&str]) -> Vec<[usize; 2]> {
+    let mut ranges: Vec<[usize; 2]> = Vec::new();
+    for word in words.iter().filter(|w| !w.is_empty()) {
+        for (byte_pos, _) in text.match_indices(word) {
+            let start = text[..byte_pos].chars().count();
+            ranges.push([start, start + word.chars().count()]);
+        }
+    }
+    ranges.sort();
+
+    let mut merged: Vec<[usize; 2]> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range[0] <= last[1] => last[1] = last[1].max(range[1]),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Internal helper: best `1 - distance / len` similarity of `word` against the words
+/// of `text`, or 0.0 when no word is within `max_distance` edits
+/// The threshold is capped at a third of the word's length, so short words need
+/// (nearly) exact matches: "сок" tolerates one edit and never matches "сыр"
+fn fuzzy_word_score(word: &str, text: &str, max_distance: usize) -> f64 {
+    let word_len = word.chars().count();
+    let max_distance = max_distance.min(word_len / 3);
+    if max_distance == 0 {
+        return 0.0;
+    }
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|candidate| !candidate.is_empty())
+        .filter_map(|candidate| {
+            let distance = levenshtein_distance(word, candidate);
+            if distance > max_distance {
+                return None;
+            }
+            let len = word_len.max(candidate.chars().count());
+            Some(1.0 - distance as f64 / len as f64)
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Levenshtein edit distance between two strings, counted in characters (not bytes)
+#[wasm_bindgen]
+pub fn levenshtein(a: &str, b: &str) -> i32 {
+    levenshtein_distance(a, b) as i32
+}
+
+/// Internal helper: Levenshtein edit distance between two strings, counted in chars.
+/// Two-row DP over the shorter string, so memory is O(min(a, b))
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let (a, b) = if a.chars().count() < b.chars().count() { (b, a) } else { (a, b) };
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut curr = vec![0; b_chars.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b_chars.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b_chars.len()]
+}
+
+/// Sort procurements by a specified field
+/// sort_by: "title", "amount", "progress", "deadline", "participants", "created", "urgency"
+/// order: "asc" or "desc"
+/// then_by: tiebreak field from the same list ("created" orders ties newest first,
+/// other fields ascending; "created" is the recommended default). Remaining ties,
+/// or all ties when then_by is empty, are ordered by id ascending.
+/// "deadline" and "created" are compared as parsed timestamps; missing or
+/// unparseable values sort last in either order.
+/// "urgency" puts upcoming deadlines first (soonest first for "asc"), then overdue
+/// ones, then procurements without a (parseable) deadline; these groups keep their
+/// position regardless of order, which only applies within a group.
+/// offset/limit: page of results to return (limit <= 0 means no limit)
+/// Returns JSON array of sorted procurement IDs
+#[wasm_bindgen]
+pub fn sort_procurements(json_input: &str, sort_by: &str, order: &str, then_by: &str, offset: i32, limit: i32) -> String {
+    sort_procurements_at(json_input, sort_by, order, then_by, offset, limit, js_sys::Date::now())
+}
+
+/// Internal helper: sort procurements against an explicit current time
+fn sort_procurements_at(json_input: &str, sort_by: &str, order: &str, then_by: &str, offset: i32, limit: i32, now_ms: f64) -> String {
+    let procurements: Vec<ProcurementRef> = match serde_json::from_str(json_input) {
+        Ok(p) => p,
+        Err(_) => return "[]".to_string(),
+    };
+
+    let ids = sorted_procurement_ids(procurements.iter(), sort_by, order, then_by, now_ms);
+    serde_json::to_string(&paginate(ids, offset, limit)).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Internal helper: ids of the given procurements in sorted order (see `sort_procurements`)
+fn sorted_procurement_ids<'a>(
+    procurements: impl Iterator<Item = &'a ProcurementRef<'a>>,
+    sort_by: &str,
+    order: &str,
+    then_by: &str,
+    now_ms: f64,
+) -> Vec<i64> {
+    let descending = order == "desc";
+
+    // Parse timestamps once up front instead of on every comparison
+    let mut entries: Vec<SortEntry> = procurements.map(|p| {
+        let deadline_ms = p.deadline.as_deref().and_then(parse_timestamp_ms);
+        SortEntry {
+            p,
+            deadline_ms,
+            created_ms: p.created_at.as_deref().and_then(parse_timestamp_ms),
+            overdue: deadline_ms.is_some_and(|ms| ms < now_ms),
+        }
+    }).collect();
+
+    entries.sort_by(|a, b| {
+        compare_procurements(a, b, sort_by, descending)
+            .then_with(|| compare_procurements(a, b, then_by, then_by == "created"))
+            .then(a.p.id.cmp(&b.p.id))
+    });
+
+    entries.iter().map(|e| e.p.id).collect()
+}
+
+/// Procurement with its timestamps pre-parsed for sorting
+struct SortEntry<'a> {
+    p: &'a ProcurementRef<'a>,
+    deadline_ms: Option<f64>,
+    created_ms: Option<f64>,
+    overdue: bool,
+}
+
+impl SortEntry<'_> {
+    /// Urgency group: upcoming deadline, overdue, no deadline
+    fn urgency_group(&self) -> u8 {
+        match self.deadline_ms {
+            Some(_) if !self.overdue => 0,
+            Some(_) => 1,
+            None => 2,
+        }
+    }
+}
+
+/// Internal helper: compare two procurements by a sort field
+/// Unknown or empty fields compare equal; missing timestamps sort last in either direction
+fn compare_procurements(a_entry: &SortEntry, b_entry: &SortEntry, field: &str, descending: bool) -> std::cmp::Ordering {
+    let (a, b) = (a_entry.p, b_entry.p);
+    let cmp = match field {
+        "title" => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+        "amount" => {
+            let a_val = a.current_amount.unwrap_or(0.0);
+            let b_val = b.current_amount.unwrap_or(0.0);
+            a_val.partial_cmp(&b_val).unwrap_or(std::cmp::Ordering::Equal)
+        }
+        "progress" => {
+            let a_target = a.target_amount.unwrap_or(1.0);
+            let b_target = b.target_amount.unwrap_or(1.0);
+            let a_prog = if a_target > 0.0 { a.current_amount.unwrap_or(0.0) / a_target } else { 0.0 };
+            let b_prog = if b_target > 0.0 { b.current_amount.unwrap_or(0.0) / b_target } else { 0.0 };
+            a_prog.partial_cmp(&b_prog).unwrap_or(std::cmp::Ordering::Equal)
+        }
+        "deadline" => return compare_timestamps(a_entry.deadline_ms, b_entry.deadline_ms, descending),
+        "participants" => {
+            let a_val = a.participant_count.unwrap_or(0);
+            let b_val = b.participant_count.unwrap_or(0);
+            a_val.cmp(&b_val)
+        }
+        "created" => return compare_timestamps(a_entry.created_ms, b_entry.created_ms, descending),
+        "urgency" => {
+            return a_entry.urgency_group().cmp(&b_entry.urgency_group())
+                .then_with(|| compare_timestamps(a_entry.deadline_ms, b_entry.deadline_ms, descending));
+        }
+        _ => std::cmp::Ordering::Equal,
+    };
+    if descending { cmp.reverse() } else { cmp }
+}
+
+/// Internal helper: compare optional timestamps, keeping missing values last
+fn compare_timestamps(a: Option<f64>, b: Option<f64>, descending: bool) -> std::cmp::Ordering {
+    match (a, b) {
+        (Some(a_ms), Some(b_ms)) => {
+            let cmp = a_ms.partial_cmp(&b_ms).unwrap_or(std::cmp::Ordering::Equal);
+            if descending { cmp.reverse() } else { cmp }
+        }
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
+}
+
+/// Internal helper: slice a result list to a page
+/// Negative offsets count as 0, offsets past the end give an empty page, limit <= 0 keeps the rest
+fn paginate<T>(items: Vec<T>, offset: i32, limit: i32) -> Vec<T> {
+    let iter = items.into_iter().skip(offset.max(0) as usize);
+    if limit > 0 {
+        iter.take(limit as usize).collect()
+    } else {
+        iter.collect()
+    }
+}
+
+/// Filter criteria for `filter_procurements`; omitted fields are ignored
+#[derive(Deserialize, Default)]
+struct ProcurementFilters {
+    status: Option<String>,
+    city: Option<String>,
+    category: Option<String>,
+    min_amount: Option<f64>,
+    max_amount: Option<f64>,
+    min_progress: Option<i32>,
+    max_progress: Option<i32>,
+}
+
+/// Filter procurements by a JSON filter object
+/// Filters: status (exact), city and category (case-insensitive), min_amount / max_amount
+/// (current amount, inclusive), min_progress / max_progress (percent as in
+/// `batch_process_procurements`, inclusive). All provided filters must match.
+/// Returns JSON array of matching procurement IDs in input order
+#[wasm_bindgen]
+pub fn filter_procurements(json_input: &str, filters_json: &str) -> String {
+    let procurements: Vec<ProcurementRef> = match serde_json::from_str(json_input) {
+        Ok(p) => p,
+        Err(_) => return "[]".to_string(),
+    };
+    let filters: ProcurementFilters = match serde_json::from_str(filters_json) {
+        Ok(f) => f,
+        Err(_) => return "[]".to_string(),
+    };
+
+    let ids: Vec<i64> = procurements.iter()
+        .filter(|p| procurement_matches_filters(p, &filters))
+        .map(|p| p.id)
+        .collect();
+
+    serde_json::to_string(&ids).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Internal helper: check a procurement against every provided filter
+fn procurement_matches_filters(p: &ProcurementRef, filters: &ProcurementFilters) -> bool {
+    let text_matches = |filter: &Option<String>, value: &Option<Cow<str>>| match filter {
+        Some(expected) => value.as_deref()
+            .is_some_and(|v| v.trim().to_lowercase() == expected.trim().to_lowercase()),
+        None => true,
+    };
+
+    if let Some(status) = &filters.status {
+        if p.status.as_deref() != Some(status.as_str()) {
+            return false;
+        }
+    }
+    if !text_matches(&filters.city, &p.city) || !text_matches(&filters.category, &p.category) {
+        return false;
+    }
+
+    let amount = p.current_amount.unwrap_or(0.0);
+    if filters.min_amount.is_some_and(|min| amount < min)
+        || filters.max_amount.is_some_and(|max| amount > max)
+    {
+        return false;
+    }
+
+    let progress = calculate_progress(amount, p.target_amount.unwrap_or(0.0));
+    !(filters.min_progress.is_some_and(|min| progress < min)
+        || filters.max_progress.is_some_and(|max| progress > max))
+}
+
+/// Parameters for `query_procurements`; every field is optional
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ProcurementQuery {
+    query: String,
+    filters: ProcurementFilters,
+    sort_by: String,
+    order: String,
+    offset: i32,
+    limit: i32,
+}
+
+/// Search, filter, sort and paginate procurements in one call (a list view)
+/// Params JSON: { query, filters, sort_by, order, offset, limit }, all optional.
+/// A non-blank query keeps only matching procurements (as `search_procurements`, exact
+/// matching) and, without sort_by, orders them by relevance; filters are those of
+/// `filter_procurements`; sort_by/order are those of `sort_procurements`, otherwise input
+/// order is kept. total counts matches before pagination
+/// Returns JSON: { "total": n, "ids": [...] }
+#[wasm_bindgen]
+pub fn query_procurements(json_input: &str, params_json: &str) -> String {
+    query_procurements_at(json_input, params_json, js_sys::Date::now())
+}
+
+/// Internal helper: run a list-view query against an explicit current time
+fn query_procurements_at(json_input: &str, params_json: &str, now_ms: f64) -> String {
+    let parsed = serde_json::from_str::<Vec<ProcurementRef>>(json_input).ok()
+        .zip(serde_json::from_str::<ProcurementQuery>(params_json).ok());
+    let Some((procurements, params)) = parsed else {
+        return serde_json::json!({ "total": 0, "ids": [] }).to_string();
+    };
+
+    let search = SearchQuery::new(&params.query, false);
+
+    let mut matches: Vec<(&ProcurementRef, f64)> = procurements.iter()
+        .filter(|p| procurement_matches_filters(p, &params.filters))
+        .filter_map(|p| {
+            if search.is_empty() {
+                return Some((p, 0.0));
+            }
+            let score = procurement_match_score(p, &search, 0);
+            (score > 0.0).then_some((p, score))
+        })
+        .collect();
+
+    let ids: Vec<i64> = if !params.sort_by.is_empty() {
+        sorted_procurement_ids(matches.iter().map(|(p, _)| *p), &params.sort_by, &params.order, "", now_ms)
+    } else {
+        // Relevance order with ties by descending id, as in `search_procurements`;
+        // without a query the input order is kept
+        if !search.is_empty() {
+            matches.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.0.id.cmp(&a.0.id)));
+        }
+        matches.iter().map(|(p, _)| p.id).collect()
+    };
+
+    serde_json::json!({
+        "total": ids.len(),
+        "ids": paginate(ids, params.offset, params.limit),
+    }).to_string()
+}
+
+/// Compare two procurement lists by id for incremental UI updates
+/// "changed" lists ids present in both whose title, status, current_amount,
+/// target_amount, participant_count or deadline differ
+/// Returns JSON: { "added": [ids], "removed": [ids], "changed": [ids] }
+/// (added/changed in new-list order, removed in old-list order)
+#[wasm_bindgen]
+pub fn diff_procurements(old_json: &str, new_json: &str) -> String {
+    let parse = |json: &str| serde_json::from_str::<Vec<Procurement>>(json).ok();
+    let (Some(old), Some(new)) = (parse(old_json), parse(new_json)) else {
+        return serde_json::json!({ "added": [], "removed": [], "changed": [] }).to_string();
+    };
+
+    let old_by_id: std::collections::HashMap<i64, &Procurement> = old.iter().map(|p| (p.id, p)).collect();
+    let new_ids: std::collections::HashSet<i64> = new.iter().map(|p| p.id).collect();
+
+    let mut added: Vec<i64> = Vec::new();
+    let mut changed: Vec<i64> = Vec::new();
+    for p in &new {
+        match old_by_id.get(&p.id) {
+            None => added.push(p.id),
+            Some(before) if procurement_changed(before, p) => changed.push(p.id),
+            Some(_) => {}
+        }
+    }
+    let removed: Vec<i64> = old.iter().map(|p| p.id).filter(|id| !new_ids.contains(id)).collect();
+
+    serde_json::json!({ "added": added, "removed": removed, "changed": changed }).to_string()
+}
+
+/// Internal helper: whether any field tracked by `diff_procurements` differs
+fn procurement_changed(before: &Procurement, after: &Procurement) -> bool {
+    before.title != after.title
+        || before.status != after.status
+        || before.current_amount != after.current_amount
+        || before.target_amount != after.target_amount
+        || before.participant_count != after.participant_count
+        || before.deadline != after.deadline
+}
+
+/// Apply a patch to a procurement list, matching objects by `id`
+/// Base objects with an id in the patch are replaced in place, patch objects with new
+/// ids are appended in patch order, and everything else is kept untouched. Objects are
+/// copied as-is, so fields unknown to this crate survive; patch items without a numeric
+/// id are ignored (for a repeated id the last patch object wins)
+/// Returns the merged JSON array ([] if either input is invalid)
+#[wasm_bindgen]
+pub fn merge_procurements(base_json: &str, patch_json: &str) -> String {
+    let parse = |json: &str| serde_json::from_str::<Vec<serde_json::Value>>(json).ok();
+    let (Some(base), Some(patch)) = (parse(base_json), parse(patch_json)) else {
+        return "[]".to_string();
+    };
+    let id_of = |item: &serde_json::Value| item.get("id").and_then(serde_json::Value::as_i64);
+
+    let mut patch_order: Vec<i64> = Vec::new();
+    let mut patch_by_id: std::collections::HashMap<i64, serde_json::Value> = std::collections::HashMap::new();
+    for item in patch {
+        if let Some(id) = id_of(&item) {
+            if patch_by_id.insert(id, item).is_none() {
+                patch_order.push(id);
+            }
+        }
+    }
+
+    let mut merged: Vec<serde_json::Value> = base.into_iter()
+        .map(|item| match id_of(&item).and_then(|id| patch_by_id.remove(&id)) {
+            Some(patched) => patched,
+            None => item,
+        })
+        .collect();
+    merged.extend(patch_order.into_iter().filter_map(|id| patch_by_id.remove(&id)));
+
+    serde_json::to_string(&merged).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Find procurements similar to the one with `target_id`
+/// Score: 6 for the same category, 2 for the same city, plus up to 3 for title
+/// overlap (Jaccard similarity of title words, normalized as in search), so a shared
+/// category always outranks a shared city alone
+/// limit <= 0 means no limit
+/// Returns JSON array of {id, score} sorted by score (ties by id), without the target;
+/// [] if the target isn't in the list
+#[wasm_bindgen]
+pub fn related_procurements(json_input: &str, target_id: i64, limit: i32) -> String {
+    let procurements: Vec<Procurement> = match serde_json::from_str(json_input) {
+        Ok(p) => p,
+        Err(_) => return "[]".to_string(),
+    };
+    let Some(target) = procurements.iter().find(|p| p.id == target_id) else {
+        return "[]".to_string();
+    };
+
+    let same = |a: &Option<String>, b: &Option<String>| match (a, b) {
+        (Some(a), Some(b)) => {
+            let a = search_normalize(a.trim());
+            !a.is_empty() && a == search_normalize(b.trim())
+        }
+        _ => false,
+    };
+    let target_words = title_words(&target.title);
+
+    let mut results: Vec<(i64, f64)> = procurements.iter()
+        .filter(|p| p.id != target_id)
+        .filter_map(|p| {
+            let mut score = 0.0;
+            if same(&p.category, &target.category) {
+                score += 6.0;
+            }
+            if same(&p.city, &target.city) {
+                score += 2.0;
+            }
+            let words = title_words(&p.title);
+            let union = target_words.union(&words).count();
+            if union > 0 {
+                score += 3.0 * target_words.intersection(&words).count() as f64 / union as f64;
+            }
+            (score > 0.0).then_some((p.id, score))
+        })
+        .collect();
+
+    results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(&b.0)));
+
+    let output: Vec<serde_json::Value> = paginate(results, 0, limit).iter().map(|(id, score)| {
+        serde_json::json!({"id": id, "score": score})
+    }).collect();
+
+    serde_json::to_string(&output).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Internal helper: set of normalized title words (as in search), ignoring 1-2 letter words
+fn title_words(title: &str) -> std::collections::HashSet<String> {
+    search_normalize(title)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 2)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Fingerprint of a procurement for duplicate detection: hex SHA-256 of the lowercased,
+/// whitespace-collapsed title, city and organizer plus the target rounded to kopecks
+/// Differences only in case or spacing give the same fingerprint
+#[wasm_bindgen]
+pub fn procurement_fingerprint(title: &str, city: &str, organizer: &str, target: f64) -> String {
+    let normalize = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let target = if target.is_finite() { format!("{:.2}", target) } else { String::new() };
+
+    // Unit separator between fields so "ab" + "c" and "a" + "bc" differ
+    let key = [normalize(title), normalize(city), normalize(organizer), target].join("\u{1f}");
+    Sha256::digest(key.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Find procurements sharing a `procurement_fingerprint` (title, city, organizer, target)
+/// Returns JSON array of id groups, e.g. [[1, 4], [2, 7, 9]]: ids ascending within a
+/// group, groups ordered by their first id; procurements without a duplicate are omitted
+#[wasm_bindgen]
+pub fn find_duplicate_procurements(json_input: &str) -> String {
+    let procurements: Vec<ProcurementRef> = match serde_json::from_str(json_input) {
+        Ok(p) => p,
+        Err(_) => return "[]".to_string(),
+    };
+
+    let mut groups: std::collections::HashMap<String, Vec<i64>> = std::collections::HashMap::new();
+    for p in &procurements {
+        let fingerprint = procurement_fingerprint(
+            &p.title,
+            p.city.as_deref().unwrap_or(""),
+            p.organizer_name.as_deref().unwrap_or(""),
+            p.target_amount.unwrap_or(0.0),
+        );
+        groups.entry(fingerprint).or_default().push(p.id);
+    }
+
+    let mut duplicates: Vec<Vec<i64>> = groups.into_values()
+        .filter(|ids| ids.len() > 1)
+        .map(|mut ids| {
+            ids.sort_unstable();
+            ids
+        })
+        .collect();
+    duplicates.sort_unstable();
+
+    serde_json::to_string(&duplicates).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Aggregate procurement statistics from a JSON array
+/// Returns JSON object with: total_count, active_count, total_amount, total_target,
+/// overall_progress, avg_participants, cities (unique), by_status counts,
+/// median_target, median_participants, p90_target,
+/// by_city / by_category ({name: {count, total_amount}}, missing names under "unknown";
+/// categories are merged by `canonicalize_category`),
+/// completed_count, funded_count (reached a positive target), funding_rate (% of total_count),
+/// overfunded_count (raised more than a positive target), overfunded_total (sum of the excess)
+/// Legacy string API: prefer `aggregate_procurement_stats_js`, which returns a JS object directly
+#[wasm_bindgen]
+pub fn aggregate_procurement_stats(json_input: &str) -> String {
+    let procurements: Vec<Procurement> = match serde_json::from_str(json_input) {
+        Ok(p) => p,
+        Err(_) => return "{}".to_string(),
+    };
+
+    serde_json::to_string(&procurement_stats(&procurements)).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Aggregate procurement statistics, returned as a plain JS object
+/// Same fields as `aggregate_procurement_stats` (numbers as JS numbers, maps as objects);
+/// invalid input yields an empty object
+#[wasm_bindgen]
+pub fn aggregate_procurement_stats_js(json_input: &str) -> JsValue {
+    let stats = match serde_json::from_str::<Vec<Procurement>>(json_input) {
+        Ok(procurements) => procurement_stats(&procurements),
+        Err(_) => serde_json::json!({}),
+    };
+    stats.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or_else(|_| js_sys::Object::new().into())
+}
+
+/// Internal helper: statistics object shared by the string and JS object APIs
+fn procurement_stats(procurements: &[Procurement]) -> serde_json::Value {
+    let total_count = procurements.len();
+    let mut active_count = 0;
+    let mut completed_count = 0;
+    let mut funded_count = 0;
+    let mut overfunded_count = 0;
+    let mut overfunded_total = 0.0_f64;
+    let mut total_amount = 0.0_f64;
+    let mut total_target = 0.0_f64;
+    let mut total_participants = 0_i64;
+    let mut cities: Vec<String> = Vec::new();
+    let mut status_counts: std::collections::HashMap<String, i32> = std::collections::HashMap::new();
+    let mut targets: Vec<f64> = Vec::with_capacity(total_count);
+    let mut participants: Vec<f64> = Vec::with_capacity(total_count);
+    let mut by_city: std::collections::HashMap<String, GroupStats> = std::collections::HashMap::new();
+    let mut by_category: std::collections::HashMap<String, GroupStats> = std::collections::HashMap::new();
+
+    for p in procurements {
+        let status = p.status.as_deref().unwrap_or("unknown");
+        if status == "active" {
+            active_count += 1;
+        } else if status == "completed" {
+            completed_count += 1;
+        }
+        *status_counts.entry(status.to_string()).or_insert(0) += 1;
+
+        total_amount += p.current_amount.unwrap_or(0.0);
+        total_target += p.target_amount.unwrap_or(0.0);
+        total_participants += p.participant_count.unwrap_or(0) as i64;
+        targets.push(p.target_amount.unwrap_or(0.0));
+        participants.push(p.participant_count.unwrap_or(0) as f64);
+
+        let amount = p.current_amount.unwrap_or(0.0);
+        let target = p.target_amount.unwrap_or(0.0);
+        if target > 0.0 && amount >= target {
+            funded_count += 1;
+        }
+        if target > 0.0 && amount > target {
+            overfunded_count += 1;
+            overfunded_total += amount - target;
+        }
+
+        by_city.entry(group_key(p.city.as_deref())).or_default().add(amount);
+        let category = p.category.as_deref().map(canonicalize_category);
+        by_category.entry(group_key(category.as_deref())).or_default().add(amount);
+
+        if let Some(city) = &p.city {
+            if !city.is_empty() && !cities.contains(city) {
+                cities.push(city.clone());
+            }
+        }
+    }
+
+    let overall_progress = calculate_progress(total_amount, total_target);
+
+    let avg_participants = if total_count > 0 {
+        total_participants as f64 / total_count as f64
+    } else {
+        0.0
+    };
+
+    let funding_rate = if total_count > 0 {
+        (funded_count as f64 / total_count as f64 * 100.0).round() as i32
+    } else {
+        0
+    };
+
+    targets.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    participants.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    serde_json::json!({
+        "total_count": total_count,
+        "active_count": active_count,
+        "total_amount": total_amount,
+        "total_target": total_target,
+        "overall_progress": overall_progress,
+        "avg_participants": (avg_participants * 10.0).round() / 10.0,
+        "cities": cities,
+        "by_status": status_counts,
+        "formatted_total_amount": format_currency_value(total_amount),
+        "formatted_total_target": format_currency_value(total_target),
+        "median_target": median(&targets),
+        "median_participants": median(&participants),
+        "p90_target": percentile(&targets, 90.0),
+        "by_city": by_city,
+        "by_category": by_category,
+        "completed_count": completed_count,
+        "funded_count": funded_count,
+        "funding_rate": funding_rate,
+        "overfunded_count": overfunded_count,
+        "overfunded_total": overfunded_total,
+    })
+}
+
+/// Count and summed current amount of a group of procurements
+#[derive(Serialize, Default)]
+struct GroupStats {
+    count: i32,
+    total_amount: f64,
+}
+
+impl GroupStats {
+    fn add(&mut self, amount: f64) {
+        self.count += 1;
+        self.total_amount += amount;
+    }
+}
+
+/// Canonical category labels (top-level catalog categories) with known synonyms.
+/// Matching is case-, ё- and diacritic-insensitive; Latin transliterations of the
+/// labels and Russian synonyms are matched automatically
+const CATEGORY_SYNONYMS: [(&str, &[&str]); 12] = [
+    ("Продукты питания", &["продукты", "еда", "продовольствие", "food", "groceries"]),
+    ("Мёд и пчеловодство", &["мёд", "пчеловодство", "honey"]),
+    ("Чай и кофе", &["чай", "кофе", "tea", "coffee"]),
+    ("Товары для дома", &["для дома", "дом", "хозтовары", "home", "household"]),
+    ("Одежда и обувь", &["одежда", "обувь", "clothes", "clothing", "apparel"]),
+    ("Электроника", &["техника", "гаджеты", "electronics", "gadgets"]),
+    ("Косметика и здоровье", &["косметика", "здоровье", "cosmetics", "beauty", "health"]),
+    ("Детские товары", &["для детей", "детям", "kids", "children", "baby"]),
+    ("Сад и огород", &["сад", "огород", "дача", "garden"]),
+    ("Строительство и ремонт", &["стройка", "ремонт", "стройматериалы", "construction", "diy"]),
+    ("Автотовары", &["авто", "автомобили", "auto", "car", "cars"]),
+    ("Прочее", &["другое", "разное", "other", "misc"]),
+];
+
+/// Lookup of normalized synonym -> canonical category label
+static CATEGORY_LOOKUP: Lazy<std::collections::HashMap<String, &'static str>> = Lazy::new(|| {
+    let mut lookup = std::collections::HashMap::new();
+    for (canonical, synonyms) in CATEGORY_SYNONYMS {
+        for name in std::iter::once(&canonical).chain(synonyms.iter()) {
+            lookup.insert(category_key(name), canonical);
+        }
+    }
+    lookup
+});
+
+/// Lookup of transliterated synonym -> canonical category label, consulted only when
+/// `CATEGORY_LOOKUP` has no exact hit so a Latin alias never shadows a real synonym
+static CATEGORY_TRANSLIT_LOOKUP: Lazy<std::collections::HashMap<String, &'static str>> = Lazy::new(|| {
+    let mut lookup = std::collections::HashMap::new();
+    for (canonical, synonyms) in CATEGORY_SYNONYMS {
+        for name in std::iter::once(&canonical).chain(synonyms.iter()) {
+            lookup.insert(transliterate_to_latin(&category_key(name)), canonical);
+        }
+    }
+    lookup
+});
+
+/// Internal helper: lookup form of a category name (search form, whitespace collapsed)
+fn category_key(name: &str) -> String {
+    search_normalize(name).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Map a free-text category to its canonical Russian label
+/// Known synonyms ("электроника", "Electronics", "elektronika") map to the catalog label
+/// ("Электроника"), with exact synonyms taking precedence over transliterations;
+/// unknown names are trimmed, whitespace-collapsed and title-cased ("ручная работа" ->
+/// "Ручная Работа"). Blank input gives ""
+#[wasm_bindgen]
+pub fn canonicalize_category(raw: &str) -> String {
+    let key = category_key(raw);
+    if key.is_empty() {
+        return String::new();
+    }
+    if let Some(canonical) = CATEGORY_LOOKUP.get(&key).or_else(|| CATEGORY_TRANSLIT_LOOKUP.get(&key)) {
+        return canonical.to_string();
+    }
+
+    raw.split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Internal helper: breakdown key for an optional name ("unknown" when missing or blank)
+fn group_key(name: Option<&str>) -> String {
+    match name.map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Internal helper: median of sorted values (mean of the two middle ones for even counts, 0 if empty)
+fn median(sorted: &[f64]) -> f64 {
+    let n = sorted.len();
+    if n == 0 {
+        0.0
+    } else if n % 2 == 1 {
+        sorted[n / 2]
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    }
+}
+
+/// Internal helper: nearest-rank percentile of sorted values (0 if empty)
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (pct / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Column order of procurement CSV export and import
+const PROCUREMENT_CSV_HEADER: [&str; 9] = [
+    "id", "title", "city", "status", "current_amount", "target_amount",
+    "progress", "participant_count", "deadline",
+];
+
+/// Export procurements as CSV (RFC 4180: comma-separated, CRLF line endings)
+/// Columns: id, title, city, status, current_amount, target_amount, progress,
+/// participant_count, deadline. Amounts are plain numbers; missing values are empty
+#[wasm_bindgen]
+pub fn procurements_to_csv(json_input: &str) -> String {
+    let procurements: Vec<Procurement> = match serde_json::from_str(json_input) {
+        Ok(p) => p,
+        Err(_) => return String::new(),
+    };
+
+    let mut csv = PROCUREMENT_CSV_HEADER.join(",");
+    csv.push_str("\r\n");
+
+    for p in &procurements {
+        let progress = calculate_progress(p.current_amount.unwrap_or(0.0), p.target_amount.unwrap_or(0.0));
+        let row = [
+            p.id.to_string(),
+            csv_field(&p.title),
+            csv_field(p.city.as_deref().unwrap_or("")),
+            csv_field(p.status.as_deref().unwrap_or("")),
+            p.current_amount.map(|a| a.to_string()).unwrap_or_default(),
+            p.target_amount.map(|a| a.to_string()).unwrap_or_default(),
+            progress.to_string(),
+            p.participant_count.map(|c| c.to_string()).unwrap_or_default(),
+            csv_field(p.deadline.as_deref().unwrap_or("")),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+    }
+
+    csv
+}
+
+/// Internal helper: quote a CSV field if it contains a comma, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Parse procurements from CSV (the `procurements_to_csv` format, header row required)
+/// Columns are matched by header name, so extra columns (e.g. description) are allowed
+/// and missing ones read as empty. Quoted fields and CRLF line endings are supported.
+/// Each row is checked with the procurement form rules (min_target and max_days disabled;
+/// description only when the column is present); non-empty id, current_amount and
+/// participant_count cells that aren't numbers are errors too, empty ones read as null.
+/// Returns JSON: { "procurements": [...], "errors": [...] } with one entry per data row
+/// in both arrays; a row with the wrong column count yields a null procurement and a
+/// "row" error, valid rows an empty error object
+#[wasm_bindgen]
+pub fn parse_procurements_csv(csv: &str) -> String {
+    parse_procurements_csv_at(csv, js_sys::Date::now())
+}
+
+/// Internal helper: parse procurement CSV against an explicit current time
+fn parse_procurements_csv_at(csv: &str, now_ms: f64) -> String {
+    let mut records = parse_csv_records(csv.trim_start_matches('\u{feff}')).into_iter();
+    let header: Vec<String> = match records.next() {
+        Some(header) => header.iter().map(|h| h.trim().to_lowercase()).collect(),
+        None => return serde_json::json!({ "procurements": [], "errors": [] }).to_string(),
+    };
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let has_description = column("description").is_some();
+
+    let mut procurements: Vec<serde_json::Value> = Vec::new();
+    let mut errors: Vec<serde_json::Value> = Vec::new();
+
+    for record in records {
+        if record.len() != header.len() {
+            procurements.push(serde_json::Value::Null);
+            errors.push(serde_json::json!({
+                "row": format!("Неверное число столбцов: ожидается {}, получено {}", header.len(), record.len()),
+            }));
+            continue;
+        }
+
+        let field = |name: &str| column(name).map_or("", |i| record[i].trim());
+        let number = |name: &str| field(name).replace(',', ".").parse::<f64>().ok();
+
+        let title = field("title");
+        let city = field("city");
+        let target_amount = number("target_amount");
+        let deadline = field("deadline");
+        let deadline_ms = parse_deadline(deadline);
+
+        // An unparsable deadline is NaN, which the form rules leave unflagged
+        let mut row_errors = procurement_form_errors(
+            title,
+            field("description"),
+            city,
+            target_amount.unwrap_or(0.0),
+            deadline_ms,
+            0.0,
+            0,
+            now_ms,
+        );
+        if !has_description {
+            row_errors.remove("description");
+        }
+        if let Some(error) = deadline_format_error(deadline, deadline_ms) {
+            row_errors.insert("deadline".to_string(), serde_json::Value::String(error.to_string()));
+        }
+
+        // Empty numeric cells read as null; anything else that doesn't parse is a row error
+        let id = field("id").parse::<i64>().ok();
+        let current_amount = number("current_amount");
+        let participant_count = field("participant_count").parse::<i32>().ok();
+        let numeric_errors = [
+            ("id", id.is_none(), "Некорректный идентификатор"),
+            ("current_amount", current_amount.is_none(), "Некорректная сумма"),
+            ("participant_count", participant_count.is_none(), "Некорректное число участников"),
+        ];
+        for (name, invalid, message) in numeric_errors {
+            if invalid && !field(name).is_empty() {
+                row_errors.insert(name.to_string(), serde_json::Value::String(message.to_string()));
+            }
+        }
+
+        let mut procurement = serde_json::json!({
+            "id": id,
+            "title": title,
+            "city": city,
+            "status": field("status"),
+            "current_amount": current_amount,
+            "target_amount": target_amount,
+            "participant_count": participant_count,
+            "deadline": deadline,
+        });
+        if has_description {
+            procurement["description"] = serde_json::Value::String(field("description").to_string());
+        }
+
+        procurements.push(procurement);
+        errors.push(serde_json::Value::Object(row_errors));
+    }
+
+    serde_json::json!({ "procurements": procurements, "errors": errors }).to_string()
+}
+
+/// Internal helper: split CSV text into records of fields (RFC 4180 quoting, LF or CRLF)
+/// Blank lines are skipped
+fn parse_csv_records(csv: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = csv.chars().peekable();
+
+    let mut end_record = |record: &mut Vec<String>, field: &mut String| {
+        record.push(std::mem::take(field));
+        if !(record.len() == 1 && record[0].is_empty()) {
+            records.push(std::mem::take(record));
+        }
+        record.clear();
+    };
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => end_record(&mut record, &mut field),
+            _ => field.push(c),
+        }
+    }
+    if !record.is_empty() || !field.is_empty() {
+        end_record(&mut record, &mut field);
+    }
+
+    records
+}
+
+/// Message data for batch operations
+#[derive(Serialize, Deserialize, Clone)]
+struct Message {
+    id: Option<i64>,
+    text: Option<String>,
+    user: Option<MessageUser>,
+    message_type: Option<String>,
+    created_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct MessageUser {
+    id: Option<i64>,
+    first_name: Option<String>,
+    last_name: Option<String>,
+}
+
+/// Batch-process messages: format text, compute date groups, format times
+/// Input: JSON array of messages, current user ID, timezone offset in minutes east of UTC
+/// (0 = UTC, 180 = Moscow; pass `-new Date().getTimezoneOffset()` for browser local time)
+/// Output: JSON array with formatted fields
+#[wasm_bindgen]
+pub fn batch_process_messages(json_input: &str, current_user_id: i64, tz_offset_minutes: i32) -> String {
+    batch_process_messages_at(json_input, current_user_id, tz_offset_minutes, js_sys::Date::now())
+}
+
+/// Internal helper: batch-process messages against an explicit current time
+fn batch_process_messages_at(json_input: &str, current_user_id: i64, tz_offset_minutes: i32, now_ms: f64) -> String {
+    let messages: Vec<Message> = match serde_json::from_str(json_input) {
+        Ok(m) => m,
+        Err(_) => return "[]".to_string(),
+    };
+
+    let results = process_messages(&messages, current_user_id, tz_offset_minutes, now_ms);
+    serde_json::to_string(&results).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Internal helper: formatted fields, date dividers and sender runs for each message
+fn process_messages(messages: &[Message], current_user_id: i64, tz_offset_minutes: i32, now_ms: f64) -> Vec<serde_json::Value> {
+    let offset_ms = tz_offset_minutes as f64 * 60_000.0;
+    let now = DateParts::from_ms(now_ms + offset_ms);
+    let yesterday = DateParts::from_ms(now_ms + offset_ms - 86_400_000.0);
+
+    let mut last_date_group = String::new();
+    let mut last_sender_id: Option<i64> = None;
+    let mut results: Vec<serde_json::Value> = Vec::with_capacity(messages.len());
+
+    for msg in messages {
+        let text = msg.text.as_deref().unwrap_or("");
+        let msg_type = msg.message_type.as_deref().unwrap_or("text");
+        let is_system = msg_type == "system";
+
+        let is_own = msg.user.as_ref()
+            .and_then(|u| u.id)
+            .map(|uid| uid == current_user_id)
+            .unwrap_or(false);
+
+        // Format message text (only for non-system messages)
+        let formatted_text = if is_system {
+            text.to_string()
+        } else {
+            format_message_text(text)
+        };
+
+        // Local (offset-adjusted) date and time of the message
+        let local = msg.created_at.as_deref()
+            .and_then(parse_timestamp_ms)
+            .map(|ms| DateParts::from_ms(ms + offset_ms));
+
+        // Compute date group
+        let date_group = local
+            .map(|date| message_date_label(&date, &now, &yesterday))
+            .unwrap_or_default();
+
+        let show_date_divider = !date_group.is_empty() && date_group != last_date_group;
+        if show_date_divider {
+            last_date_group = date_group.clone();
+        }
+
+        // Format time
+        let formatted_time = local
+            .map(|date| format!("{:02}:{:02}", date.hour, date.minute))
+            .unwrap_or_default();
+
+        // Emoji-only messages (up to 3) are rendered large
+        let big_emoji = !is_system && is_big_emoji(text);
+
+        // Sender info is shown once per run of consecutive messages by the same user;
+        // system messages and date dividers break a run
+        let sender_id = msg.user.as_ref().and_then(|u| u.id);
+        let show_sender = !is_system
+            && (show_date_divider || sender_id.is_none() || sender_id != last_sender_id);
+        last_sender_id = if is_system { None } else { sender_id };
+
+        let sender_name = if show_sender && !is_own {
+            msg.user.as_ref()
+                .and_then(|u| u.first_name.clone())
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+
+        let mut obj = serde_json::json!({
+            "id": msg.id,
+            "text": text,
+            "formatted_text": formatted_text,
+            "is_own": is_own,
+            "is_system": is_system,
+            "formatted_time": formatted_time,
+            "sender_name": sender_name,
+            "show_sender": show_sender,
+            "is_big_emoji": big_emoji,
+        });
+
+        if show_date_divider {
+            obj["date_divider"] = serde_json::json!(date_group);
+        }
+
+        results.push(obj);
+    }
+
+    results
+}
+
+/// Internal helper: date group label for a local message date ("Сегодня", "Вчера" or a Russian date)
+fn message_date_label(date: &DateParts, now: &DateParts, yesterday: &DateParts) -> String {
+    if date.same_day(now) {
+        "Сегодня".to_string()
+    } else if date.same_day(yesterday) {
+        "Вчера".to_string()
+    } else {
+        format_ru_date(date.day, date.month, date.year as u32, now.year as u32)
+    }
+}
+
+/// Count messages per local calendar day
+/// Input: JSON array of messages, timezone offset in minutes east of UTC (as in `batch_process_messages`)
+/// Output: JSON array of `{date: "YYYY-MM-DD", label, count}` in chronological order;
+/// messages without a parseable `created_at` are counted in a trailing `date: null` bucket
+#[wasm_bindgen]
+pub fn messages_by_date(json_input: &str, tz_offset_minutes: i32) -> String {
+    messages_by_date_at(json_input, tz_offset_minutes, js_sys::Date::now())
+}
+
+/// Internal helper: group messages by date against an explicit current time
+fn messages_by_date_at(json_input: &str, tz_offset_minutes: i32, now_ms: f64) -> String {
+    let messages: Vec<Message> = match serde_json::from_str(json_input) {
+        Ok(m) => m,
+        Err(_) => return "[]".to_string(),
+    };
+
+    let offset_ms = tz_offset_minutes as f64 * 60_000.0;
+    let now = DateParts::from_ms(now_ms + offset_ms);
+    let yesterday = DateParts::from_ms(now_ms + offset_ms - 86_400_000.0);
+
+    let mut days: std::collections::BTreeMap<(i32, u32, u32), (DateParts, usize)> =
+        std::collections::BTreeMap::new();
+    let mut undated = 0usize;
+
+    for msg in &messages {
+        let local = msg.created_at.as_deref()
+            .and_then(parse_timestamp_ms)
+            .map(|ms| DateParts::from_ms(ms + offset_ms));
+        match local {
+            Some(date) => days.entry((date.year, date.month, date.day)).or_insert((date, 0)).1 += 1,
+            None => undated += 1,
+        }
+    }
+
+    let mut results: Vec<serde_json::Value> = days.values().map(|(date, count)| {
+        serde_json::json!({
+            "date": format!("{:04}-{:02}-{:02}", date.year, date.month + 1, date.day),
+            "label": message_date_label(date, &now, &yesterday),
+            "count": count,
+        })
+    }).collect();
+
+    if undated > 0 {
+        results.push(serde_json::json!({
+            "date": null,
+            "label": null,
+            "count": undated,
+        }));
+    }
+
+    serde_json::to_string(&results).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Check whether trimmed text consists solely of 1 to 3 emoji grapheme clusters
+fn is_big_emoji(text: &str) -> bool {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return false;
+    }
+    let mut count = 0;
+    for grapheme in trimmed.graphemes(true) {
+        if !is_emoji_grapheme(grapheme) {
+            return false;
+        }
+        count += 1;
+        if count > 3 {
+            return false;
+        }
+    }
+    true
+}
+
+/// Internal helper: emoji property check for a single grapheme cluster
+/// Covers pictographic emoji, flags, ZWJ sequences, skin tones and keycaps.
+/// Symbols shown as text by default ("©", "™", "↔", "❤") only count when followed
+/// by the emoji presentation selector U+FE0F or a skin tone modifier
+fn is_emoji_grapheme(grapheme: &str) -> bool {
+    let mut chars = grapheme.chars();
+    let first = match chars.next() {
+        Some(c) => c,
+        None => return false,
+    };
+
+    // Keycap sequences: digit, '#' or '*' followed by U+20E3
+    if (first.is_ascii_digit() || first == '#' || first == '*') && grapheme.contains('\u{20e3}') {
+        return true;
+    }
+    if !is_pictographic(first) {
+        return false;
+    }
+    if !has_emoji_presentation(first)
+        && !grapheme.chars().any(|c| matches!(c, '\u{fe0f}' | '\u{1f3fb}'..='\u{1f3ff}'))
+    {
+        return false;
+    }
+    chars.all(|c| is_pictographic(c) || matches!(c,
+        '\u{200d}' | '\u{fe0e}' | '\u{fe0f}' | '\u{20e3}' | '\u{e0020}'..='\u{e007f}'))
+}
+
+/// Internal helper: approximation of the Extended_Pictographic Unicode property
+fn is_pictographic(c: char) -> bool {
+    matches!(c,
+        '\u{a9}' | '\u{ae}' | '\u{203c}' | '\u{2049}' | '\u{2122}' | '\u{2139}'
+        | '\u{2194}'..='\u{21aa}'
+        | '\u{2300}'..='\u{23ff}'
+        | '\u{24c2}'
+        | '\u{25aa}'..='\u{25fe}'
+        | '\u{2600}'..='\u{27bf}'
+        | '\u{2934}' | '\u{2935}'
+        | '\u{2b00}'..='\u{2bff}'
+        | '\u{3030}' | '\u{303d}' | '\u{3297}' | '\u{3299}'
+        | '\u{1f000}'..='\u{1faff}')
+}
+
+/// Internal helper: approximation of the Emoji_Presentation Unicode property: pictographs
+/// drawn as emoji without a variation selector. Most of U+1F000.. qualifies (except
+/// enclosed letters such as "🅰"); in the BMP only the listed symbols do
+fn has_emoji_presentation(c: char) -> bool {
+    match c {
+        '\u{1f100}'..='\u{1f1ff}' => matches!(c,
+            '\u{1f18e}' | '\u{1f191}'..='\u{1f19a}' | '\u{1f1e6}'..='\u{1f1ff}'),
+        '\u{1f000}'..='\u{1faff}' => true,
+        _ => matches!(c,
+            '\u{231a}' | '\u{231b}' | '\u{23e9}'..='\u{23ec}' | '\u{23f0}' | '\u{23f3}'
+            | '\u{25fd}' | '\u{25fe}' | '\u{2614}' | '\u{2615}' | '\u{2648}'..='\u{2653}'
+            | '\u{267f}' | '\u{2693}' | '\u{26a1}' | '\u{26aa}' | '\u{26ab}' | '\u{26bd}'
+            | '\u{26be}' | '\u{26c4}' | '\u{26c5}' | '\u{26ce}' | '\u{26d4}' | '\u{26ea}'
+            | '\u{26f2}' | '\u{26f3}' | '\u{26f5}' | '\u{26fa}' | '\u{26fd}' | '\u{2705}'
+            | '\u{270a}' | '\u{270b}' | '\u{2728}' | '\u{274c}' | '\u{274e}'
+            | '\u{2753}'..='\u{2755}' | '\u{2757}' | '\u{2795}'..='\u{2797}' | '\u{27b0}'
+            | '\u{27bf}' | '\u{2b1b}' | '\u{2b1c}' | '\u{2b50}' | '\u{2b55}'),
+    }
+}
+
+/// Format a Russian date string
+fn format_ru_date(day: u32, month: u32, year: u32, current_year: u32) -> String {
+    let month_names = [
+        "января", "февраля", "марта", "апреля", "мая", "июня",
+        "июля", "августа", "сентября", "октября", "ноября", "декабря"
+    ];
+    let month_name = month_names.get(month as usize).unwrap_or(&"");
+    if year != current_year {
+        format!("{} {} {}", day, month_name, year)
+    } else {
+        format!("{} {}", day, month_name)
+    }
+}
+
+/// Search within messages by text content
+/// Returns JSON array of matching message indices
+#[wasm_bindgen]
+pub fn search_messages(json_input: &str, query: &str) -> String {
+    if query.trim().is_empty() {
+        return "[]".to_string();
+    }
+
+    let messages: Vec<Message> = match serde_json::from_str(json_input) {
+        Ok(m) => m,
+        Err(_) => return "[]".to_string(),
+    };
+
+    matching_messages(&messages, query, |_| true)
+}
+
+/// Search messages sent by one user, optionally narrowed by text
+/// Returns JSON array of matching message indices; an empty query matches every
+/// message of the user. System messages never match
+#[wasm_bindgen]
+pub fn search_messages_by_user(json_input: &str, user_id: i64, query: &str) -> String {
+    let messages: Vec<Message> = match serde_json::from_str(json_input) {
+        Ok(m) => m,
+        Err(_) => return "[]".to_string(),
+    };
+
+    matching_messages(&messages, query, |msg| {
+        msg.message_type.as_deref() != Some("system")
+            && msg.user.as_ref().and_then(|u| u.id) == Some(user_id)
+    })
+}
+
+/// Search messages sent within a time window, optionally narrowed by text
+/// Returns JSON array of indices of messages whose `created_at` falls in
+/// `[start_ms, end_ms]` (inclusive); messages without a parseable date are skipped.
+/// An inverted or NaN window yields "[]"
+#[wasm_bindgen]
+pub fn search_messages_in_range(json_input: &str, start_ms: f64, end_ms: f64, query: &str) -> String {
+    if start_ms.is_nan() || end_ms.is_nan() || start_ms > end_ms {
+        return "[]".to_string();
+    }
+
+    let messages: Vec<Message> = match serde_json::from_str(json_input) {
+        Ok(m) => m,
+        Err(_) => return "[]".to_string(),
+    };
+
+    matching_messages(&messages, query, |msg| {
+        msg.created_at.as_deref()
+            .and_then(parse_timestamp_ms)
+            .is_some_and(|ms| ms >= start_ms && ms <= end_ms)
+    })
+}
+
+/// Internal helper: `{index, id}` entries of messages accepted by `filter` whose text
+/// contains `query` case-insensitively (a blank query matches any text)
+fn matching_messages(messages: &[Message], query: &str, filter: impl Fn(&Message) -> bool) -> String {
+    let match_all = query.trim().is_empty();
+    let query_lower = query.to_lowercase();
+    let results: Vec<serde_json::Value> = messages.iter().enumerate().filter_map(|(i, msg)| {
+        if !filter(msg) {
+            return None;
+        }
+        let text = msg.text.as_deref().unwrap_or("");
+        if match_all || text.to_lowercase().contains(&query_lower) {
+            Some(serde_json::json!({
+                "index": i,
+                "id": msg.id,
+            }))
+        } else {
+            None
+        }
+    }).collect();
+
+    serde_json::to_string(&results).unwrap_or_else(|_| "[]".to_string())
+}
+
+// ──────────────────────────────────────────────
+// Content moderation
+// ──────────────────────────────────────────────
+
+/// Best-effort profanity list: (normalized word, also match as a word prefix).
+/// Deliberately short and conservative; it catches common words and simple
+/// substitutions, not every spelling
+const PROFANITY_WORDS: [(&str, bool); 22] = [
+    ("asshole", false),
+    ("bitch", true),
+    ("bullshit", false),
+    ("cunt", true),
+    ("dick", false),
+    ("fuck", true),
+    ("motherfucker", false),
+    ("shit", true),
+    ("бля", false),
+    ("бляд", true),
+    ("блят", true),
+    ("ебан", true),
+    ("ебат", true),
+    ("ебал", true),
+    ("муда", true),
+    ("пизд", true),
+    ("сука", false),
+    ("суки", false),
+    ("суку", false),
+    ("хуе", true),
+    ("хуй", true),
+    ("хуя", true),
+];
+
+/// Mask profanity in text, keeping the first letter of each matched word (e.g. "f***")
+/// Words are matched whole (or by stem) after undoing simple substitutions such as
+/// `@` -> a, `0` -> o and Latin look-alikes in Cyrillic words. Best-effort only
+#[wasm_bindgen]
+pub fn censor_profanity(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for (word, is_word) in profanity_tokens(text) {
+        if is_word && is_profane_word(word) {
+            let mut graphemes = word.graphemes(true);
+            result.push_str(graphemes.next().unwrap_or(""));
+            result.extend(graphemes.map(|_| '*'));
+        } else {
+            result.push_str(word);
+        }
+    }
+    result
+}
+
+/// Check whether text contains profanity (same matching as `censor_profanity`)
+#[wasm_bindgen]
+pub fn contains_profanity(text: &str) -> bool {
+    profanity_tokens(text).any(|(word, is_word)| is_word && is_profane_word(word))
+}
+
+/// Internal helper: split text into alternating (slice, is_word) runs, where word
+/// characters include the substitution symbols `@` and `$`
+fn profanity_tokens(text: &str) -> impl Iterator<Item = (&str, bool)> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '@' || c == '$';
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let word = is_word(first);
+        let len = rest.find(|c: char| is_word(c) != word).unwrap_or(rest.len());
+        let (token, tail) = rest.split_at(len);
+        rest = tail;
+        Some((token, word))
+    })
+}
+
+/// Internal helper: whether a single word matches the profanity list
+fn is_profane_word(word: &str) -> bool {
+    let normalized = normalize_substitutions(word);
+    PROFANITY_WORDS.iter().any(|(entry, prefix)| {
+        if *prefix { normalized.starts_with(entry) } else { normalized == *entry }
+    })
+}
+
+/// Internal helper: lowercase a word and undo common letter substitutions;
+/// words containing Cyrillic map digits and Latin look-alikes to Cyrillic letters
+fn normalize_substitutions(word: &str) -> String {
+    let lower = word.to_lowercase();
+    let cyrillic = lower.chars().any(|c| matches!(c, 'а'..='я' | 'ё'));
+    lower.chars().map(|c| match (c, cyrillic) {
+        ('@' | '4', false) => 'a',
+        ('0', false) => 'o',
+        ('1', false) => 'i',
+        ('3', false) => 'e',
+        ('$' | '5', false) => 's',
+        ('@' | '4' | 'a', true) => 'а',
+        ('0' | 'o', true) => 'о',
+        ('3' | 'e' | 'ё', true) => 'е',
+        ('$' | 'c', true) => 'с',
+        ('x', true) => 'х',
+        ('y', true) => 'у',
+        ('p', true) => 'р',
+        ('k', true) => 'к',
+        ('b', true) => 'в',
+        ('m', true) => 'м',
+        ('h', true) => 'н',
+        ('t', true) => 'т',
+        _ => c,
+    }).collect()
+}
+
+/// Promo keywords (lowercase word stems) counted by `message_spam_score`
+const SPAM_KEYWORDS: [&str; 14] = [
+    "бесплатн", "заработ", "казино", "кредит", "подпишись", "переходи", "промокод",
+    "ставк", "bonus", "casino", "click", "free", "promo", "winner",
+];
+
+/// Heuristic spam score of a chat message, from 0.0 (normal) to 1.0 (spam)
+/// Weighted sum of saturating features:
+/// - links: 0.4 * min(urls / 3, 1)
+/// - shouting: 0.25 * share of uppercase letters (messages with 8+ letters only)
+/// - repeated characters: 0.15 * min(chars in runs of 4+ identical chars / 10, 1)
+/// - promo keywords: 0.2 * min(words starting with a keyword / 2, 1)
+///
+/// Each feature only grows with its input, so e.g. more links never lower the score
+#[wasm_bindgen]
+pub fn message_spam_score(text: &str) -> f64 {
+    let url_count = URL_RE.find_iter(text).count() as f64;
+
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+    let caps_ratio = if letters.len() >= 8 {
+        letters.iter().filter(|c| c.is_uppercase()).count() as f64 / letters.len() as f64
+    } else {
+        0.0
+    };
+
+    let mut repeated = 0;
+    let mut run = 0;
+    let mut prev: Option<char> = None;
+    for c in text.chars().chain(std::iter::once('\0')) {
+        if Some(c) == prev && !c.is_whitespace() {
+            run += 1;
+        } else {
+            if run >= 4 {
+                repeated += run;
+            }
+            run = 1;
+        }
+        prev = Some(c);
+    }
+
+    let lower = text.to_lowercase();
+    let keyword_hits = lower.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| SPAM_KEYWORDS.iter().any(|k| word.starts_with(k)))
+        .count();
+
+    let score = 0.4 * (url_count / 3.0).min(1.0)
+        + 0.25 * caps_ratio
+        + 0.15 * (repeated as f64 / 10.0).min(1.0)
+        + 0.2 * (keyword_hits as f64 / 2.0).min(1.0);
+    score.clamp(0.0, 1.0)
+}
+
+// ──────────────────────────────────────────────
+// Payments
+// ──────────────────────────────────────────────
+
+/// Build the payment QR payload for a procurement contribution
+/// Canonical query string with keys in alphabetical order:
+/// `amount=<2 decimals>&currency=<upper-case>&procurement_id=<id>&purpose=<percent-encoded>`
+/// Returns "" for a non-positive or non-finite amount
+#[wasm_bindgen]
+pub fn build_payment_payload(procurement_id: i64, amount: f64, currency: &str, purpose: &str) -> String {
+    if !amount.is_finite() || amount <= 0.0 {
+        return String::new();
+    }
+    format!(
+        "amount={:.2}&currency={}&procurement_id={}&purpose={}",
+        amount,
+        percent_encode(&currency.trim().to_uppercase()),
+        procurement_id,
+        percent_encode(purpose.trim()),
+    )
+}
+
+/// Internal helper: percent-encode UTF-8 text, leaving only RFC 3986 unreserved
+/// characters (`A-Z a-z 0-9 - . _ ~`) literal; spaces become `%20`
+fn percent_encode(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            result.push(byte as char);
+        } else {
+            result.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    result
+}
+
+// ──────────────────────────────────────────────
+// Signature verification
+// ──────────────────────────────────────────────
+
+/// Verify a hex HMAC-SHA256 signature of a payload (e.g. a webhook `X-Signature` header)
+/// Hex may be upper- or lowercase; the comparison is constant-time.
+/// Returns false for malformed or wrong-length signatures
+#[wasm_bindgen]
+pub fn verify_hmac_sha256(payload: &str, signature_hex: &str, secret: &str) -> bool {
+    let Some(signature) = hex_decode(signature_hex.trim()) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(payload.as_bytes());
+    // verify_slice checks the length first and compares the bytes in constant time
+    mac.verify_slice(&signature).is_ok()
+}
+
+// ──────────────────────────────────────────────
+// Telegram integration
+// ──────────────────────────────────────────────
+
+/// Prefix of the bot /start payload that opens a procurement
+const START_PARAM_PROCUREMENT_PREFIX: &str = "proc_";
+
+/// Build a Telegram deep link that opens a procurement in the bot
+/// Returns `https://t.me/<bot>?start=<payload>` where the payload is `proc_<id>`
+/// base64url-encoded (a leading '@' in the bot username is ignored)
+#[wasm_bindgen]
+pub fn build_share_link(bot_username: &str, procurement_id: i64) -> String {
+    let bot = bot_username.trim().trim_start_matches('@');
+    let payload = format!("{}{}", START_PARAM_PROCUREMENT_PREFIX, procurement_id);
+    format!("https://t.me/{}?start={}", bot, base64url_encode(payload.as_bytes()))
+}
+
+/// Decode a /start payload produced by `build_share_link`
+/// Returns the procurement ID, or -1 if the payload is malformed
+#[wasm_bindgen]
+pub fn parse_start_param(param: &str) -> i64 {
+    try_base64url_decode(param.trim())
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .and_then(|payload| {
+            let id = payload.strip_prefix(START_PARAM_PROCUREMENT_PREFIX)?;
+            if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            id.parse::<i64>().ok()
+        })
+        .unwrap_or(-1)
+}
+
+/// Verify Telegram Mini App `initData` against the bot token
+/// Implements the documented scheme: the data-check-string is every field except
+/// `hash`, sorted by key as `key=value` lines; secret = HMAC-SHA256("WebAppData", bot_token)
+/// and `hash` must equal hex(HMAC-SHA256(secret, data-check-string)).
+/// Returns false on a missing or mismatching hash, or malformed input
+#[wasm_bindgen]
+pub fn verify_telegram_init_data(init_data: &str, bot_token: &str) -> bool {
+    let mut hash: Option<Vec<u8>> = None;
+    let mut fields: Vec<(String, String)> = Vec::new();
+
+    for pair in init_data.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let (Some(key), Some(value)) = (percent_decode(key), percent_decode(value)) else {
+            return false;
+        };
+        if key == "hash" {
+            hash = hex_decode(&value);
+            if hash.is_none() {
+                return false;
+            }
+        } else {
+            fields.push((key, value));
+        }
+    }
+    let Some(hash) = hash else {
+        return false;
+    };
+
+    fields.sort();
+    let data_check_string = fields.iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let Ok(mut secret_mac) = Hmac::<Sha256>::new_from_slice(b"WebAppData") else {
+        return false;
+    };
+    secret_mac.update(bot_token.as_bytes());
+    let secret = secret_mac.finalize().into_bytes();
+
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(&secret) else {
+        return false;
+    };
+    mac.update(data_check_string.as_bytes());
+    mac.verify_slice(&hash).is_ok()
+}
+
+/// Internal helper: decode a URL query component (`%XX` escapes, `+` as space)
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = input.get(i + 1..i + 3)?;
+                result.push(hex_byte(hex)?);
+                i += 3;
+            }
+            b'+' => {
+                result.push(b' ');
+                i += 1;
+            }
+            b => {
+                result.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(result).ok()
+}
+
+/// Internal helper: decode a hex string (either case); None on odd length or bad digits
+fn hex_decode(input: &str) -> Option<Vec<u8>> {
+    if !input.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..input.len()).step_by(2)
+        .map(|i| hex_byte(input.get(i..i + 2)?))
+        .collect()
+}
+
+/// Internal helper: byte from exactly two hex digits
+/// (`u8::from_str_radix` alone would also accept a sign, as in "+f")
+fn hex_byte(digits: &str) -> Option<u8> {
+    if digits.len() != 2 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u8::from_str_radix(digits, 16).ok()
+}
+
+// ──────────────────────────────────────────────
+// Compression and encoding
+// ──────────────────────────────────────────────
+
+/// Gzip-compress a string (e.g. procurement JSON before caching it in IndexedDB)
+/// Returns the gzip bytes (`Uint8Array` in JS)
+#[wasm_bindgen]
+pub fn gzip_compress(data: &str) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    if encoder.write_all(data.as_bytes()).is_err() {
+        return Vec::new();
+    }
+    encoder.finish().unwrap_or_default()
+}
+
+/// Decompress gzip bytes produced by `gzip_compress` back into a string
+/// Invalid, truncated or non-UTF-8 data gives ""
+#[wasm_bindgen]
+pub fn gzip_decompress(data: &[u8]) -> String {
+    use std::io::Read;
+
+    let mut text = String::new();
+    match flate2::read::GzDecoder::new(data).read_to_string(&mut text) {
+        Ok(_) => text,
+        Err(_) => String::new(),
+    }
+}
+
+/// URL-safe base64 (RFC 4648 §5): no padding when encoding, padding optional when decoding
+const BASE64URL: base64::engine::GeneralPurpose = base64::engine::GeneralPurpose::new(
+    &base64::alphabet::URL_SAFE,
+    base64::engine::GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
+);
+
+/// Encode bytes as unpadded base64url (safe in URLs and Telegram start params)
+#[wasm_bindgen]
+pub fn base64url_encode(data: &[u8]) -> String {
+    BASE64URL.encode(data)
+}
+
+/// Decode base64url (padded or not) into bytes; invalid input gives an empty array
+#[wasm_bindgen]
+pub fn base64url_decode(s: &str) -> Vec<u8> {
+    try_base64url_decode(s).unwrap_or_default()
+}
+
+/// Internal helper: decode base64url, None on invalid input
+fn try_base64url_decode(input: &str) -> Option<Vec<u8>> {
+    BASE64URL.decode(input).ok()
+}
+
+// ──────────────────────────────────────────────
+// Client-side rate limiting
+// ──────────────────────────────────────────────
+
+/// Token-bucket rate limiter for throttling client actions (message send, search)
+/// Holds up to `capacity` tokens, refilled continuously at `refill_per_sec`;
+/// each allowed action spends one token. Refill is computed lazily on each call
+#[wasm_bindgen]
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    tokens: f64,
+    last_refill_ms: f64,
+}
+
+#[wasm_bindgen]
+impl RateLimiter {
+    /// Create a limiter that starts full
+    #[wasm_bindgen(constructor)]
+    pub fn new(capacity: f64, refill_per_sec: f64) -> RateLimiter {
+        RateLimiter::new_at(capacity, refill_per_sec, js_sys::Date::now())
+    }
+
+    /// Spend one token if available; returns false when the action should be throttled
+    pub fn try_acquire(&mut self) -> bool {
+        self.try_acquire_at(js_sys::Date::now())
+    }
+
+    /// Tokens currently available (fractional while refilling)
+    pub fn tokens_remaining(&mut self) -> f64 {
+        self.tokens_remaining_at(js_sys::Date::now())
+    }
+}
+
+impl RateLimiter {
+    /// Internal helper: create a limiter against an explicit current time
+    fn new_at(capacity: f64, refill_per_sec: f64, now_ms: f64) -> RateLimiter {
+        let capacity = if capacity.is_finite() { capacity.max(0.0) } else { 0.0 };
+        let refill_per_sec = if refill_per_sec.is_finite() { refill_per_sec.max(0.0) } else { 0.0 };
+        RateLimiter { capacity, refill_per_sec, tokens: capacity, last_refill_ms: now_ms }
+    }
+
+    /// Internal helper: add tokens for the time elapsed since the last refill
+    fn refill(&mut self, now_ms: f64) {
+        // A clock going backwards must not drain the bucket
+        let elapsed_sec = ((now_ms - self.last_refill_ms) / 1000.0).max(0.0);
+        self.tokens = (self.tokens + elapsed_sec * self.refill_per_sec).min(self.capacity);
+        self.last_refill_ms = self.last_refill_ms.max(now_ms);
+    }
+
+    fn try_acquire_at(&mut self, now_ms: f64) -> bool {
+        self.refill(now_ms);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn tokens_remaining_at(&mut self, now_ms: f64) -> f64 {
+        self.refill(now_ms);
+        self.tokens
+    }
+}
+
+// ──────────────────────────────────────────────
+// Checked variants (explicit parse errors)
+// ──────────────────────────────────────────────
+
+/// Internal helper: `{ok: true, data}` for parsed input, `{ok: false, error}` with the
+/// serde message otherwise
+fn checked_result<T, E: std::fmt::Display>(parsed: Result<T, E>, run: impl FnOnce(T) -> serde_json::Value) -> String {
+    match parsed {
+        Ok(input) => serde_json::json!({ "ok": true, "data": run(input) }).to_string(),
+        Err(e) => serde_json::json!({ "ok": false, "error": e.to_string() }).to_string(),
+    }
+}
+
+/// `batch_process_procurements` reporting malformed input instead of returning "[]"
+/// Returns JSON: { "ok": true, "data": [...] } or { "ok": false, "error": "..." }
+#[wasm_bindgen]
+pub fn batch_process_procurements_checked(json_input: &str) -> String {
+    batch_process_procurements_checked_at(json_input, js_sys::Date::now())
+}
+
+/// Internal helper: checked batch processing against an explicit current time
+fn batch_process_procurements_checked_at(json_input: &str, now_ms: f64) -> String {
+    checked_result(serde_json::from_str::<Vec<Procurement>>(json_input), |procurements| {
+        serde_json::Value::Array(process_procurements(&procurements, now_ms))
+    })
+}
+
+/// `batch_process_messages` reporting malformed input instead of returning "[]"
+/// Returns JSON: { "ok": true, "data": [...] } or { "ok": false, "error": "..." }
+#[wasm_bindgen]
+pub fn batch_process_messages_checked(json_input: &str, current_user_id: i64, tz_offset_minutes: i32) -> String {
+    batch_process_messages_checked_at(json_input, current_user_id, tz_offset_minutes, js_sys::Date::now())
+}
+
+/// Internal helper: checked message processing against an explicit current time
+fn batch_process_messages_checked_at(json_input: &str, current_user_id: i64, tz_offset_minutes: i32, now_ms: f64) -> String {
+    checked_result(serde_json::from_str::<Vec<Message>>(json_input), |messages| {
+        serde_json::Value::Array(process_messages(&messages, current_user_id, tz_offset_minutes, now_ms))
+    })
+}
+
+/// `search_procurements` reporting malformed input instead of returning "[]"
+/// Returns JSON: { "ok": true, "data": [...] } or { "ok": false, "error": "..." }
+#[wasm_bindgen]
+pub fn search_procurements_checked(json_input: &str, query: &str, max_distance: u32, offset: i32, limit: i32, translit: bool, top_n: i32) -> String {
+    checked_result(serde_json::from_str::<Vec<ProcurementRef>>(json_input), |procurements| {
+        serde_json::Value::Array(search_results(&procurements, query, max_distance, offset, limit, translit, top_n))
+    })
+}
+
+/// `sort_procurements` reporting malformed input instead of returning "[]"
+/// Returns JSON: { "ok": true, "data": [ids] } or { "ok": false, "error": "..." }
+#[wasm_bindgen]
+pub fn sort_procurements_checked(json_input: &str, sort_by: &str, order: &str, then_by: &str, offset: i32, limit: i32) -> String {
+    sort_procurements_checked_at(json_input, sort_by, order, then_by, offset, limit, js_sys::Date::now())
+}
+
+/// Internal helper: checked sorting against an explicit current time
+fn sort_procurements_checked_at(json_input: &str, sort_by: &str, order: &str, then_by: &str, offset: i32, limit: i32, now_ms: f64) -> String {
+    checked_result(serde_json::from_str::<Vec<ProcurementRef>>(json_input), |procurements| {
+        let ids = sorted_procurement_ids(procurements.iter(), sort_by, order, then_by, now_ms);
+        serde_json::json!(paginate(ids, offset, limit))
+    })
+}
+
+/// `aggregate_procurement_stats` reporting malformed input instead of returning "{}"
+/// Returns JSON: { "ok": true, "data": {...} } or { "ok": false, "error": "..." }
+#[wasm_bindgen]
+pub fn aggregate_procurement_stats_checked(json_input: &str) -> String {
+    checked_result(serde_json::from_str::<Vec<Procurement>>(json_input), |procurements| {
+        procurement_stats(&procurements)
+    })
+}
+
+// ──────────────────────────────────────────────
+// Performance measurement utilities
+// ──────────────────────────────────────────────
+
+/// Run a performance benchmark for batch processing
+/// Generates N procurements from `seed` (same seed, same dataset) and runs batch
+/// processing, search, sort and aggregation on them
+/// Returns JSON with elapsed ms per step:
+/// { generate_ms, batch_ms, search_ms, sort_ms, aggregate_ms, total_ms }
+#[wasm_bindgen]
+pub fn benchmark_batch_processing(count: i32, seed: u32) -> String {
+    benchmark_batch_processing_with(count, seed, &js_sys::Date::now)
+}
+
+/// Internal helper: run the benchmark with an explicit millisecond clock
+fn benchmark_batch_processing_with(count: i32, seed: u32, now: &dyn Fn() -> f64) -> String {
+    let start = now();
+    let json = generate_benchmark_procurements(count, seed);
+    let generated = now();
+
+    let _ = batch_process_procurements_at(&json, generated);
+    let batched = now();
+    let _ = search_procurements(&json, "тестовая Москва", 2, 0, 0, true, 0);
+    let searched = now();
+    let _ = sort_procurements_at(&json, "amount", "desc", "", 0, 0, searched);
+    let sorted = now();
+    let _ = aggregate_procurement_stats(&json);
+    let end = now();
+
+    serde_json::json!({
+        "generate_ms": generated - start,
+        "batch_ms": batched - generated,
+        "search_ms": searched - batched,
+        "sort_ms": sorted - searched,
+        "aggregate_ms": end - sorted,
+        "total_ms": end - start,
+    }).to_string()
+}
+
+/// Internal helper: JSON array of N procurements generated from a seed for benchmarking
+fn generate_benchmark_procurements(count: i32, seed: u32) -> String {
+    const CITIES: [&str; 3] = ["Москва", "Санкт-Петербург", "Новосибирск"];
+    const STATUSES: [&str; 4] = ["active", "draft", "completed", "payment"];
+
+    let mut rng = XorShift32::new(seed);
+    let mut procurements: Vec<serde_json::Value> = Vec::with_capacity(count.max(0) as usize);
+    for i in 0..count {
+        let target_amount = (rng.below(1000) as f64 + 10.0) * 1000.0;
+        let current_amount = (target_amount * rng.below(121) as f64 / 100.0).round();
+        let deadline_day = rng.below(28) + 1;
+        procurements.push(serde_json::json!({
+            "id": i,
+            "title": format!("Закупка тестовая #{}", rng.below(100_000)),
+            "description": format!("Описание закупки #{} для бенчмарка", i),
+            "city": CITIES[rng.below(CITIES.len() as u32) as usize],
+            "status": STATUSES[rng.below(STATUSES.len() as u32) as usize],
+            "current_amount": current_amount,
+            "target_amount": target_amount,
+            "deadline": format!("2026-{:02}-{:02}T23:59:59Z", rng.below(12) + 1, deadline_day),
+            "participant_count": rng.below(50) + 1,
+            "category": format!("Категория {}", rng.below(5)),
+            "organizer_name": format!("Организатор {}", rng.below(10)),
+            "created_at": format!("2026-01-{:02}T10:00:00Z", rng.below(28) + 1),
+        }));
+    }
+
+    serde_json::to_string(&procurements).unwrap_or_default()
+}
+
+/// Small deterministic PRNG (xorshift32) for reproducible benchmark data
+struct XorShift32 {
+    state: u32,
+}
+
+impl XorShift32 {
+    fn new(seed: u32) -> Self {
+        // xorshift never leaves the all-zero state, so substitute a fixed non-zero seed
+        Self { state: if seed == 0 { 0x9E37_79B9 } else { seed } }
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Value in `0..bound` (bound must be non-zero)
+    fn below(&mut self, bound: u32) -> u32 {
+        self.next_u32() % bound
+    }
+}
+
+// ──────────────────────────────────────────────
+// Unit tests (run with `cargo test`)
+// ──────────────────────────────────────────────
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── Validation tests ──
+
+    #[test]
+    fn test_validate_phone_valid() {
+        assert!(validate_phone("+79991234567"));
+        assert!(validate_phone("+7 999 123 4567"));
+        assert!(validate_phone("")); // empty is valid (optional)
+    }
+
+    #[test]
+    fn test_validate_phone_invalid() {
+        assert!(!validate_phone("123")); // too short, no +
+        assert!(!validate_phone("+123")); // too short even with +
+        assert!(!validate_phone("+00000000000")); // no country code starts with 0
+    }
+
+    #[test]
+    fn test_validate_phone_country_codes() {
+        assert!(validate_phone("+7 999 123-45-67")); // Russia / Kazakhstan
+        assert!(validate_phone("+375 29 123 45 67")); // Belarus
+        assert!(validate_phone("+380 50 123 45 67")); // Ukraine
+        assert!(validate_phone("+996 555 123 456")); // Kyrgyzstan
+        assert!(validate_phone("+998 90 123 45 67")); // Uzbekistan
+
+        // National number length must match the detected country
+        assert!(!validate_phone("+7999123456")); // 9 digits after +7
+        assert!(!validate_phone("+799912345678")); // 11 digits after +7
+        assert!(!validate_phone("+37529123456")); // 8 digits after +375
+        assert!(!validate_phone("+3805012345678")); // 10 digits after +380
+        assert!(!validate_phone("+99655512345")); // 8 digits after +996
+        assert!(!validate_phone("+9989012345678")); // 10 digits after +998
+    }
+
+    #[test]
+    fn test_validate_phone_misplaced_plus() {
+        assert!(!validate_phone("+7+79991234567"));
+        assert!(!validate_phone("7+9991234567"));
+        assert!(!validate_phone("79991234567+"));
+    }
+
+    #[test]
+    fn test_normalize_phone_e164() {
+        assert_eq!(normalize_phone_e164("8 (900) 123-45-67"), "+79001234567");
+        assert_eq!(normalize_phone_e164("+7 900 123 45 67"), "+79001234567");
+        assert_eq!(normalize_phone_e164("89001234567"), "+79001234567");
+        assert_eq!(normalize_phone_e164("7-900-123-45-67"), "+79001234567");
+        assert_eq!(normalize_phone_e164("+375 29 123-45-67"), "+375291234567");
+
+        assert_eq!(normalize_phone_e164(""), "");
+        assert_eq!(normalize_phone_e164("+7 900 123"), "");
+        assert_eq!(normalize_phone_e164("+7900+1234567"), "");
+        assert_eq!(normalize_phone_e164("звоните"), "");
+    }
+
+    #[test]
+    fn test_validate_email_valid() {
+        assert!(validate_email("user@example.com"));
+        assert!(validate_email("test@mail.ru"));
+        assert!(validate_email("")); // empty is valid (optional)
+    }
+
+    #[test]
+    fn test_validate_email_invalid() {
+        assert!(!validate_email("notanemail"));
+        assert!(!validate_email("@domain.com"));
+        assert!(!validate_email("user@"));
+    }
+
+    #[test]
+    fn test_validate_procurement_form_valid() {
+        let now = 1_700_000_000_000.0;
+        let result = validate_procurement_form_at("Мед", "Свежий мед", "Москва", 1000.0, now + 86_400_000.0, 0.0, 0, now);
+        assert_eq!(result, "{}");
+    }
+
+    #[test]
+    fn test_validate_procurement_form_errors() {
+        let now = 1_700_000_000_000.0;
+        let long_description = "а".repeat(5001);
+        let result = validate_procurement_form_at("", &long_description, " ", 0.0, now - 1.0, 0.0, 0, now);
+        let errors: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(errors["title"], "Название обязательно");
+        assert_eq!(errors["description"], "Описание не должно превышать 5000 символов");
+        assert_eq!(errors["city"], "Город обязателен");
+        assert_eq!(errors["target_amount"], "Целевая сумма должна быть положительной");
+        assert_eq!(errors["deadline"], "Дедлайн должен быть в будущем");
+    }
+
+    #[test]
+    fn test_validate_procurement_form_title_length_in_chars() {
+        let now = 1_700_000_000_000.0;
+        let deadline = now + 86_400_000.0;
+
+        // 150 Cyrillic chars are 300 bytes but within the 200-character limit
+        let title = "м".repeat(150);
+        let result = validate_procurement_form_at(&title, "Свежий мед", "Москва", 1000.0, deadline, 0.0, 0, now);
+        assert_eq!(result, "{}");
+        let result = validate_procurement_form_at(&"м".repeat(200), "Свежий мед", "Москва", 1000.0, deadline, 0.0, 0, now);
+        assert_eq!(result, "{}");
+
+        let result = validate_procurement_form_at(&"м".repeat(201), "Свежий мед", "Москва", 1000.0, deadline, 0.0, 0, now);
+        let errors: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(errors["title"], "Название не должно превышать 200 символов");
+    }
+
+    #[test]
+    fn test_validate_procurement_form_min_target() {
+        let now = 1_700_000_000_000.0;
+        let deadline = now + 86_400_000.0;
+
+        let result = validate_procurement_form_at("Мед", "Свежий мед", "Москва", 5.0, deadline, 1000.0, 0, now);
+        let errors: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(errors["target_amount"], "Целевая сумма должна быть не меньше 1 000 ₽");
+
+        let result = validate_procurement_form_at("Мед", "Свежий мед", "Москва", 1000.0, deadline, 1000.0, 0, now);
+        assert_eq!(result, "{}");
+
+        // A zero minimum keeps the plain "must be positive" rule
+        let result = validate_procurement_form_at("Мед", "Свежий мед", "Москва", 5.0, deadline, 0.0, 0, now);
+        assert_eq!(result, "{}");
+        let result = validate_procurement_form_at("Мед", "Свежий мед", "Москва", -5.0, deadline, 0.0, 0, now);
+        let errors: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(errors["target_amount"], "Целевая сумма должна быть положительной");
+    }
+
+    #[test]
+    fn test_validate_procurement_form_max_days() {
+        let now = 1_700_000_000_000.0;
+        let day = 86_400_000.0;
+
+        let result = validate_procurement_form_at("Мед", "Свежий мед", "Москва", 1000.0, now + 365.0 * day, 0.0, 365, now);
+        assert_eq!(result, "{}");
+
+        let result = validate_procurement_form_at("Мед", "Свежий мед", "Москва", 1000.0, now + 366.0 * day, 0.0, 365, now);
+        let errors: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(errors["deadline"], "Дедлайн не может быть позже чем через 365 дн.");
+
+        // Past deadline reports the past-date message first
+        let result = validate_procurement_form_at("Мед", "Свежий мед", "Москва", 1000.0, now - day, 0.0, 365, now);
+        let errors: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(errors["deadline"], "Дедлайн должен быть в будущем");
+
+        // Zero disables the horizon check
+        let result = validate_procurement_form_at("Мед", "Свежий мед", "Москва", 1000.0, now + 3650.0 * day, 0.0, 0, now);
+        assert_eq!(result, "{}");
+    }
+
+    #[test]
+    fn test_validate_procurement_form_escaping() {
+        let now = 1_700_000_000_000.0;
+        let title = format!("Мед \"{}\" \\", "x".repeat(200));
+        let result = validate_procurement_form_at(&title, "", "Москва", 1000.0, now + 86_400_000.0, 0.0, 0, now);
+        let errors: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(errors["title"], "Название не должно превышать 200 символов");
+        assert_eq!(errors["description"], "Описание обязательно");
+    }
+
+    // ── Formatting tests ──
+
+    #[test]
+    fn test_calculate_progress() {
+        assert_eq!(calculate_progress(500.0, 1000.0), 50);
+        assert_eq!(calculate_progress(0.0, 1000.0), 0);
+        assert_eq!(calculate_progress(1500.0, 1000.0), 100); // capped at 100
+        assert_eq!(calculate_progress(100.0, 0.0), 0); // division by zero guard
+    }
+
+    #[test]
+    fn test_calculate_progress_uncapped() {
+        assert_eq!(calculate_progress_uncapped(1500.0, 1000.0), 150);
+        assert_eq!(calculate_progress(1500.0, 1000.0), 100); // default stays clamped
+        assert_eq!(calculate_progress_uncapped(996.0, 1000.0), 100);
+        assert_eq!(calculate_progress_uncapped(500.0, 1000.0), 50);
+        assert_eq!(calculate_progress_uncapped(-5.0, 1000.0), 0);
+        assert_eq!(calculate_progress_uncapped(100.0, 0.0), 0);
+    }
+
+    #[test]
+    fn test_calculate_progress_rounding() {
+        assert_eq!(calculate_progress(996.0, 1000.0), 100); // 99.6% rounds up
+        assert_eq!(calculate_progress(994.0, 1000.0), 99);
+        assert_eq!(calculate_progress(4.0, 1000.0), 0); // 0.4% rounds down
+        assert_eq!(calculate_progress(5.0, 1000.0), 1);
+        assert_eq!(calculate_progress(-50.0, 1000.0), 0);
+
+        assert_eq!(calculate_progress_f64(996.0, 1000.0), 99.6);
+        assert_eq!(calculate_progress_f64(4.0, 1000.0), 0.4);
+        assert_eq!(calculate_progress_f64(2500.0, 1000.0), 100.0); // over-funded
+        assert_eq!(calculate_progress_f64(-1.0, 1000.0), 0.0);
+        assert_eq!(calculate_progress_f64(100.0, 0.0), 0.0);
+        assert_eq!(calculate_progress_f64(f64::NAN, 1000.0), 0.0);
+    }
+
+    #[test]
+    fn test_format_phone() {
+        assert_eq!(format_phone("+79991234567"), "+7 (999) 123-45-67");
+        assert_eq!(format_phone("89991234567"), "+7 (999) 123-45-67");
+        assert_eq!(format_phone("8 (999) 123-45-67"), format_phone("+7 999 123 45 67"));
+        assert_eq!(format_phone("+375291234567"), "+375291234567"); // not Russian, left as is
+        assert_eq!(format_phone("12345"), "+12345");
+        assert_eq!(format_phone(""), "");
+    }
+
+    #[test]
+    fn test_format_currency() {
+        assert_eq!(format_currency(1000.0), "1 000 ₽");
+        assert_eq!(format_currency(0.0), "0 ₽");
+        assert_eq!(format_currency(1234567.89), "1 234 567,89 ₽");
+        assert_eq!(format_currency(99.5), "99,50 ₽");
+    }
+
+    #[test]
+    fn test_format_money() {
+        assert_eq!(format_money(1234567.89, "USD"), "$1,234,567.89");
+        assert_eq!(format_money(1000.0, "usd"), "$1,000");
+        assert_eq!(format_money(-1234.5, "USD"), "-$1,234.50");
+        assert_eq!(format_money(1234567.89, "KZT"), "1 234 567,89 ₸");
+        assert_eq!(format_money(500.0, "KZT"), "500 ₸");
+        assert_eq!(format_money(12.5, "BYN"), "12,50 Br");
+        assert_eq!(format_money(1000.0, "EUR"), "1 000 €");
+        assert_eq!(format_money(1000.0, "RUB"), format_currency(1000.0));
+        assert_eq!(format_money(1000.0, "GBP"), "1 000 GBP");
+    }
+
+    #[test]
+    fn test_format_currency_huge_and_invalid() {
+        assert_eq!(format_currency(f64::INFINITY), "—");
+        assert_eq!(format_currency(f64::NEG_INFINITY), "—");
+        assert_eq!(format_currency(f64::NAN), "—");
+        assert_eq!(format_currency(1e22), "—");
+        assert_eq!(format_money(f64::NAN, "USD"), "—");
+
+        assert_eq!(format_currency(1e18), "1 000 000 000 000 000 000 ₽");
+        assert_eq!(format_currency(-1e18), "-1 000 000 000 000 000 000 ₽");
+        assert_eq!(format_currency(9_007_199_254_740_993.0), "9 007 199 254 740 992 ₽");
+        assert_eq!(format_currency(1e21), "1 000 000 000 000 000 000 000 ₽");
+        assert_eq!(format_money(1e18, "USD"), "$1,000,000,000,000,000,000");
+    }
+
+    #[test]
+    fn test_format_currency_negative() {
+        assert_eq!(format_currency(-1234.5), "-1 234,50 ₽");
+        assert_eq!(format_currency(-0.5), "-0,50 ₽");
+        assert_eq!(format_currency(-1000.0), "-1 000 ₽");
+        assert_eq!(format_currency(-100.0), "-100 ₽");
+        assert_eq!(format_currency(-0.0), "0 ₽");
+        assert_eq!(format_currency_value(-999999.0), "-999 999 ₽");
+    }
+
+    #[test]
+    fn test_format_currency_rounding_carry() {
+        assert_eq!(format_currency(9.999), "10 ₽");
+        assert_eq!(format_currency(0.995), "1 ₽");
+        assert_eq!(format_currency(999999.999), "1 000 000 ₽");
+        assert_eq!(format_currency(-9.999), "-10 ₽");
+        assert_eq!(format_currency_value(999999.999), "1 000 000 ₽");
+    }
+
+    #[test]
+    fn test_format_relative_time() {
+        let now = 1_700_000_000_000.0;
+        let ago = |sec: f64| format_relative_time_at(now - sec * 1000.0, now);
+
+        assert_eq!(ago(10.0), "только что");
+        assert_eq!(ago(5.0 * 60.0), "5 мин. назад");
+        assert_eq!(ago(3.0 * 3600.0), "3 ч. назад");
+        assert_eq!(ago(86400.0), "вчера");
+        assert_eq!(ago(4.0 * 86400.0), "4 дн. назад");
+    }
+
+    #[test]
+    fn test_format_relative_time_long_ranges() {
+        let now = 1_700_000_000_000.0;
+        let days_ago = |days: f64| format_relative_time_at(now - days * 86_400_000.0, now);
+
+        assert_eq!(days_ago(6.0), "6 дн. назад");
+        assert_eq!(days_ago(8.0), "1 нед. назад");
+        assert_eq!(days_ago(29.0), "4 нед. назад");
+        assert_eq!(days_ago(45.0), "1 мес. назад");
+        assert_eq!(days_ago(364.0), "12 мес. назад");
+        assert_eq!(days_ago(400.0), "1 год назад");
+        assert_eq!(days_ago(800.0), "2 года назад");
+        assert_eq!(days_ago(2000.0), "5 лет назад");
+    }
+
+    #[test]
+    fn test_format_relative_time_future() {
+        let now = 1_700_000_000_000.0;
+        let ahead = |sec: f64| format_relative_time_at(now + sec * 1000.0, now);
+
+        assert_eq!(ahead(30.0), "только что"); // clock skew
+        assert_eq!(ahead(10.0 * 60.0), "через 10 мин.");
+        assert_eq!(ahead(2.0 * 3600.0), "через 2 ч.");
+        assert_eq!(ahead(3.0 * 86400.0), "через 3 дн.");
+    }
+
+    #[test]
+    fn test_ru_plural() {
+        let forms = |n| ru_plural(n, "минуту", "минуты", "минут");
+        assert_eq!(forms(1), "минуту");
+        assert_eq!(forms(2), "минуты");
+        assert_eq!(forms(5), "минут");
+        assert_eq!(forms(11), "минут");
+        assert_eq!(forms(12), "минут");
+        assert_eq!(forms(14), "минут");
+        assert_eq!(forms(21), "минуту");
+        assert_eq!(forms(25), "минут");
+        assert_eq!(forms(111), "минут");
+        assert_eq!(ru_plural(1, "день", "дня", "дней"), "день");
+    }
+
+    #[test]
+    fn test_format_ordinal_ru() {
+        assert_eq!(format_ordinal_ru(3, "m"), "3-й");
+        assert_eq!(format_ordinal_ru(3, "masculine"), "3-й");
+        assert_eq!(format_ordinal_ru(1, "f"), "1-я");
+        assert_eq!(format_ordinal_ru(2, "Feminine"), "2-я");
+        assert_eq!(format_ordinal_ru(10, "n"), "10-е");
+        assert_eq!(format_ordinal_ru(21, "neuter"), "21-е");
+        assert_eq!(format_ordinal_ru(5, ""), "5-й");
+    }
+
+    #[test]
+    fn test_format_count_ru() {
+        let forms = r#"["место", "места", "мест"]"#;
+        assert_eq!(format_count_ru(1, forms), "1 место");
+        assert_eq!(format_count_ru(2, forms), "2 места");
+        assert_eq!(format_count_ru(5, forms), "5 мест");
+        assert_eq!(format_count_ru(11, forms), "11 мест");
+        assert_eq!(format_count_ru(21, forms), "21 место");
+        assert_eq!(format_count_ru(0, forms), "0 мест");
+        assert_eq!(format_count_ru(5, r#"["место"]"#), "5");
+        assert_eq!(format_count_ru(5, "bad"), "5");
+    }
+
+    #[test]
+    fn test_format_relative_time_full() {
+        let now = 1_700_000_000_000.0;
+        let ago = |sec: f64| format_relative_time_full_at(now - sec * 1000.0, now);
+
+        assert_eq!(ago(10.0), "только что");
+
+        assert_eq!(ago(60.0), "1 минуту назад");
+        assert_eq!(ago(2.0 * 60.0), "2 минуты назад");
+        assert_eq!(ago(5.0 * 60.0), "5 минут назад");
+        assert_eq!(ago(11.0 * 60.0), "11 минут назад");
+        assert_eq!(ago(21.0 * 60.0), "21 минуту назад");
+        assert_eq!(ago(25.0 * 60.0), "25 минут назад");
+
+        assert_eq!(ago(3600.0), "1 час назад");
+        assert_eq!(ago(2.0 * 3600.0), "2 часа назад");
+        assert_eq!(ago(5.0 * 3600.0), "5 часов назад");
+        assert_eq!(ago(11.0 * 3600.0), "11 часов назад");
+        assert_eq!(ago(21.0 * 3600.0), "21 час назад");
+
+        assert_eq!(ago(86400.0), "вчера");
+        assert_eq!(ago(2.0 * 86400.0), "2 дня назад");
+        assert_eq!(ago(5.0 * 86400.0), "5 дней назад");
+        assert_eq!(ago(11.0 * 86400.0), "11 дней назад");
+        assert_eq!(ago(21.0 * 86400.0), "21 день назад");
+        assert_eq!(ago(25.0 * 86400.0), "25 дней назад");
+    }
+
+    #[test]
+    fn test_generate_platform_user_id_unique() {
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..10000 {
+            let id = generate_platform_user_id();
+            assert!(id.starts_with("web_"));
+            assert_eq!(id.len(), 4 + 36);
+            assert!(!id.contains(' '));
+            assert!(seen.insert(id));
+        }
+    }
+
+    #[test]
+    fn test_get_avatar_color() {
+        let color = get_avatar_color("Тест");
+        assert!(color.starts_with('#'));
+        assert_eq!(color.len(), 7); // #RRGGBB format
+
+        // Same name should always produce same color
+        assert_eq!(get_avatar_color("Иван"), get_avatar_color("Иван"));
+    }
+
+    #[test]
+    fn test_name_hash_pinned() {
+        assert_eq!(name_hash(""), 0);
+        assert_eq!(name_hash("A"), 65);
+        assert_eq!(name_hash("Anna"), 2045632);
+        assert_eq!(name_hash("Иван"), 32287399);
+        assert_eq!(name_hash("Тест"), 32588724);
+        // Classic string whose wrapping hash is exactly i32::MIN
+        assert_eq!(name_hash("polygenelubricants"), 2_147_483_648);
+
+        assert_eq!(get_avatar_color(""), "#e17076");
+        assert_eq!(get_avatar_color("A"), "#faa774");
+        assert_eq!(get_avatar_color("Anna"), "#e17076");
+        assert_eq!(get_avatar_color("Иван"), "#f5a623");
+        assert_eq!(get_avatar_color("Тест"), "#6ec9cb");
+        assert_eq!(get_avatar_color("polygenelubricants"), "#e17076");
+    }
+
+    #[test]
+    fn test_get_avatar_color_themed() {
+        for name in ["Иван", "Anna", "Тест", "", "Организатор 7"] {
+            let index = avatar_color_index(name);
+            assert_eq!(get_avatar_color_themed(name, "light"), AVATAR_COLORS_LIGHT[index]);
+            assert_eq!(get_avatar_color_themed(name, "dark"), AVATAR_COLORS_DARK[index]);
+            assert_eq!(get_avatar_color_themed(name, "dark"), get_avatar_color(name));
+        }
+        assert_eq!(get_avatar_color_themed("Иван", "unknown"), get_avatar_color("Иван"));
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((contrast_ratio("#000000", "#ffffff") - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio("#FFFFFF", "#000000") - 21.0).abs() < 1e-9);
+        assert_eq!(contrast_ratio("#777777", "#777777"), 1.0);
+
+        // Mid-gray #777777 has luminance ~0.184: ~4.48 on white, ~4.69 on black
+        let on_white = contrast_ratio("#777777", "#ffffff");
+        let on_black = contrast_ratio("#777777", "#000000");
+        assert!((on_white - 4.48).abs() < 0.01, "{}", on_white);
+        assert!((on_black - 4.69).abs() < 0.01, "{}", on_black);
+        assert_eq!(pick_readable_text("#777777"), "#000000");
+        assert_eq!(pick_readable_text("#555555"), "#ffffff");
+
+        assert_eq!(contrast_ratio("#12345", "#ffffff"), 0.0);
+        assert_eq!(contrast_ratio("#ffffff", "white"), 0.0);
+        assert_eq!(contrast_ratio("#gggggg", "#000000"), 0.0);
+        assert_eq!(pick_readable_text("nope"), "#000000");
+    }
+
+    #[test]
+    fn test_avatar_spec() {
+        let spec: serde_json::Value = serde_json::from_str(&avatar_spec("иван", "петров", "light")).unwrap();
+        assert_eq!(spec["initials"], "ИП");
+        assert_eq!(spec["bg"], get_avatar_color_themed("иван", "light"));
+        // Light-theme palette is dark enough for white text
+        assert_eq!(spec["fg"], "#ffffff");
+
+        // Contrast choice flips between a light and a dark background
+        assert_eq!(readable_text_color("#faa774"), "#000000");
+        assert_eq!(readable_text_color("#1565c0"), "#ffffff");
+        assert_eq!(readable_text_color("#ffffff"), "#000000");
+        assert_eq!(readable_text_color("#000000"), "#ffffff");
+        assert_eq!(readable_text_color("oops"), "#000000");
+
+        for name in ["Иван", "Мария", "Alex", ""] {
+            let spec: serde_json::Value = serde_json::from_str(&avatar_spec(name, "", "dark")).unwrap();
+            assert_eq!(spec["initials"], get_initials(name, ""));
+            assert_eq!(spec["bg"], get_avatar_color(name));
+        }
+    }
+
+    #[test]
+    fn test_get_initials() {
+        assert_eq!(get_initials("Иван", "Петров"), "ИП");
+        assert_eq!(get_initials("Anna", ""), "A");
+        assert_eq!(get_initials("", ""), "?");
+    }
+
+    #[test]
+    fn test_get_initials_graphemes() {
+        assert_eq!(get_initials("🇷🇺", ""), "🇷🇺");
+        assert_eq!(get_initials("🇷🇺", "Петров"), "🇷🇺П");
+        assert_eq!(get_initials("e\u{301}mile", "zola"), "E\u{301}Z");
+        assert_eq!(get_initials("Иван", "e\u{301}"), "ИE\u{301}");
+    }
+
+    #[test]
+    fn test_get_initials_v2() {
+        assert_eq!(get_initials_v2("Иван", "Петров"), "ИП");
+        assert_eq!(get_initials_v2("Александр", ""), "АЛ");
+        assert_eq!(get_initials_v2("anna", ""), "AN");
+        assert_eq!(get_initials_v2("Я", ""), "Я");
+        assert_eq!(get_initials_v2("👨\u{200d}👩\u{200d}👧Маша", ""), "👨\u{200d}👩\u{200d}👧М");
+        assert_eq!(get_initials_v2("e\u{301}va", ""), "E\u{301}V");
+        assert_eq!(get_initials_v2(" ", " "), "?");
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(escape_html("<script>alert('xss')</script>"),
+            "&lt;script&gt;alert(&#x27;xss&#x27;)&lt;/script&gt;");
+        assert_eq!(escape_html("Hello & World"), "Hello &amp; World");
+        assert_eq!(escape_html("\"quoted\""), "&quot;quoted&quot;");
+        assert_eq!(escape_html("normal text"), "normal text");
+    }
+
+    #[test]
+    fn test_format_message_text_basic() {
+        assert_eq!(format_message_text(""), "");
+        assert_eq!(format_message_text("Hello"), "Hello");
+    }
+
+    #[test]
+    fn test_format_message_text_xss() {
+        let result = format_message_text("<script>alert(1)</script>");
+        assert!(!result.contains("<script>"));
+        assert!(result.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn test_format_message_text_urls() {
+        let result = format_message_text("Visit https://example.com please");
+        assert!(result.contains("<a href=\"https://example.com\""));
+        assert!(result.contains("target=\"_blank\""));
+    }
+
+    #[test]
+    fn test_format_message_text_url_output_unchanged() {
+        let link = |url: &str| format!("<a href=\"{}\" target=\"_blank\" rel=\"noopener\">{}</a>", url, url);
+        assert_eq!(format_message_text("Visit https://example.com please"),
+            format!("Visit {} please", link("https://example.com")));
+        assert_eq!(format_message_text("http://a.b/c?d=1, ok."), format!("{}, ok.", link("http://a.b/c?d=1")));
+        assert_eq!(format_message_text("(см. https://ru.wikipedia.org/wiki/A_(b))."),
+            format!("(см. {}).", link("https://ru.wikipedia.org/wiki/A_(b)")));
+        assert_eq!(format_message_text("xhttps://y.z"), format!("x{}", link("https://y.z")));
+        assert_eq!(format_message_text("a#https://x.com"), format!("a#{}", link("https://x.com")));
+        assert_eq!(format_message_text("https://a.b/#frag\nhttp://c.d"),
+            format!("{}<br>{}", link("https://a.b/#frag"), link("http://c.d")));
+        assert_eq!(format_message_text("https://x.com/?q=<script>"),
+            format!("{}&lt;script&gt;", link("https://x.com/?q=")));
+        assert_eq!(format_message_text("https://x.com/?a=1&b=2"),
+            "<a href=\"https://x.com/?a=1&b=2\" target=\"_blank\" rel=\"noopener\">https://x.com/?a=1&amp;b=2</a>");
+    }
+
+    #[test]
+    fn test_format_message_text_markup() {
+        assert_eq!(format_message_text("*bold*"), "<b>bold</b>");
+        assert_eq!(format_message_text("_italic_"), "<i>italic</i>");
+        assert_eq!(format_message_text("*a* *b*"), "<b>a</b> <b>b</b>");
+        assert_eq!(format_message_text("*a*, _b_!"), "<b>a</b>, <i>b</i>!");
+        assert_eq!(format_message_text("*_both_*"), "<b><i>both</i></b>");
+        assert_eq!(format_message_text("*two words*"), "<b>two words</b>");
+    }
+
+    #[test]
+    fn test_format_message_text_markup_literal() {
+        assert_eq!(format_message_text("a*b*c"), "a*b*c"); // intra-word
+        assert_eq!(format_message_text("snake_case_name"), "snake_case_name");
+        assert_eq!(format_message_text("2 * 3"), "2 * 3"); // lone marker
+        assert_eq!(format_message_text("* not bold *"), "* not bold *");
+        assert_eq!(format_message_text("**"), "**");
+        assert_eq!(format_message_text("*line\nbreak*"), "*line<br>break*");
+        assert_eq!(format_message_text("*<b>*"), "<b>&lt;b&gt;</b>");
+    }
+
+    #[test]
+    fn test_format_message_text_markup_skips_urls() {
+        let result = format_message_text("_see_ https://example.com/a_b_c");
+        assert!(result.starts_with("<i>see</i> "));
+        assert!(result.contains("<a href=\"https://example.com/a_b_c\""));
+    }
+
+    #[test]
+    fn test_format_message_text_mentions() {
+        assert_eq!(format_message_text("hey @ivan check this"),
+            "hey <a href=\"/u/ivan\" class=\"mention\">@ivan</a> check this");
+        assert_eq!(format_message_text("@ivan_petrov, hi"),
+            "<a href=\"/u/ivan_petrov\" class=\"mention\">@ivan_petrov</a>, hi");
+        assert_eq!(format_message_text("*@ivan*"),
+            "*<a href=\"/u/ivan\" class=\"mention\">@ivan</a>*");
+        assert_eq!(format_message_text("foo@bar.com"), "foo@bar.com"); // email, not a mention
+        assert_eq!(format_message_text("@ alone"), "@ alone");
+        assert_eq!(format_message_text("@Иван"), "@Иван"); // non-ASCII username
+        let too_long = format!("@{}", "a".repeat(33));
+        assert_eq!(format_message_text(&too_long), too_long);
+    }
+
+    #[test]
+    fn test_format_message_text_hashtags() {
+        assert_eq!(format_message_text("#электроника"),
+            "<a href=\"/tag/электроника\" class=\"hashtag\">#электроника</a>");
+        assert_eq!(format_message_text("Скидки на #мед2026!"),
+            "Скидки на <a href=\"/tag/мед2026\" class=\"hashtag\">#мед2026</a>!");
+        assert_eq!(format_message_text("color#fff"), "color#fff");
+        assert_eq!(format_message_text("# heading"), "# heading");
+        assert_eq!(format_message_text("'#tag'"),
+            "&#x27;<a href=\"/tag/tag\" class=\"hashtag\">#tag</a>&#x27;");
+
+        let result = format_message_text("see https://example.com/page#section now");
+        assert!(result.contains("<a href=\"https://example.com/page#section\""));
+        assert!(!result.contains("class=\"hashtag\""));
+    }
+
+    #[test]
+    fn test_format_message_text_url_punctuation() {
+        assert_eq!(format_message_text("see https://example.com/page."),
+            "see <a href=\"https://example.com/page\" target=\"_blank\" rel=\"noopener\">https://example.com/page</a>.");
+        assert_eq!(format_message_text("https://x.com/a?!, ok"),
+            "<a href=\"https://x.com/a\" target=\"_blank\" rel=\"noopener\">https://x.com/a</a>?!, ok");
+
+        let wiki = format_message_text("https://en.wikipedia.org/wiki/Rust_(programming_language)");
+        assert!(wiki.contains("href=\"https://en.wikipedia.org/wiki/Rust_(programming_language)\""));
+        assert!(wiki.ends_with("</a>"));
+
+        assert_eq!(format_message_text("(https://x.com)"),
+            "(<a href=\"https://x.com\" target=\"_blank\" rel=\"noopener\">https://x.com</a>)");
+        assert_eq!(format_message_text("(see https://x.com/a_(b)).").matches("</a>).").count(), 1);
+    }
+
+    #[test]
+    fn test_format_message_text_url_query_string() {
+        let result = format_message_text("https://shop.ru/item?a=1&b=2&c=3");
+        assert_eq!(result,
+            "<a href=\"https://shop.ru/item?a=1&b=2&c=3\" target=\"_blank\" rel=\"noopener\">https://shop.ru/item?a=1&amp;b=2&amp;c=3</a>");
+
+        let quoted = format_message_text("https://x.com/it's");
+        assert!(quoted.contains("href=\"https://x.com/it%27s\""));
+        assert!(quoted.contains(">https://x.com/it&#x27;s</a>"));
+    }
+
+    #[test]
+    fn test_message_text_to_plain() {
+        assert_eq!(message_text_to_plain("*bold*\nline2"), "bold line2");
+        assert_eq!(message_text_to_plain("  _hi_   *there*  \n\n "), "hi there");
+        assert_eq!(message_text_to_plain("*_both_*"), "both");
+        assert_eq!(message_text_to_plain("a*b*c 2 * 3"), "a*b*c 2 * 3");
+        assert_eq!(message_text_to_plain("see https://example.com/a_b_c"), "see https://example.com/a_b_c");
+        assert_eq!(message_text_to_plain("<b>&amp;</b>"), "<b>&amp;</b>"); // no escaping or decoding
+        assert_eq!(message_text_to_plain(""), "");
+    }
+
+    #[test]
+    fn test_format_message_text_newlines() {
+        let result = format_message_text("Line 1\nLine 2");
+        assert!(result.contains("<br>"));
+    }
+
+    // ── Search and sorting tests ──
+
+    #[test]
+    fn test_search_procurements() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Мед натуральный", "description": "Свежий мед", "city": "Москва"},
+            {"id": 2, "title": "Масло оливковое", "description": "Из Греции", "city": "Санкт-Петербург"},
+            {"id": 3, "title": "Чай зеленый", "description": "Японский чай", "city": "Москва"},
+        ]).to_string();
+
+        let result = search_procurements(&json, "Москва", 0, 0, 0, false, 0);
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed.len(), 2); // two procurements in Moscow
+
+        let result_empty = search_procurements(&json, "", 0, 0, 0, false, 0);
+        assert_eq!(result_empty, "[]");
+
+        let result_none = search_procurements(&json, "Несуществующий", 0, 0, 0, false, 0);
+        let parsed_none: Vec<serde_json::Value> = serde_json::from_str(&result_none).unwrap();
+        assert_eq!(parsed_none.len(), 0);
+    }
+
+    #[test]
+    fn test_sort_procurements_tiebreak() {
+        let json = serde_json::json!([
+            {"id": 4, "title": "Г", "current_amount": 50.0, "target_amount": 100.0, "created_at": "2026-01-02T00:00:00Z"},
+            {"id": 2, "title": "Б", "current_amount": 50.0, "target_amount": 100.0, "created_at": "2026-01-03T00:00:00Z"},
+            {"id": 1, "title": "А", "current_amount": 90.0, "target_amount": 100.0, "created_at": "2026-01-01T00:00:00Z"},
+            {"id": 3, "title": "В", "current_amount": 25.0, "target_amount": 50.0, "created_at": "2026-01-01T00:00:00Z"},
+        ]).to_string();
+
+        // Ties on 50% progress: newest created first
+        let by_created: Vec<i64> = serde_json::from_str(&sort_procurements_at(&json, "progress", "desc", "created", 0, 0, 0.0)).unwrap();
+        assert_eq!(by_created, vec![1, 2, 4, 3]);
+
+        // Title tiebreak is ascending
+        let by_title: Vec<i64> = serde_json::from_str(&sort_procurements_at(&json, "progress", "desc", "title", 0, 0, 0.0)).unwrap();
+        assert_eq!(by_title, vec![1, 2, 3, 4]);
+
+        // No tiebreak field: id ascending
+        let by_id: Vec<i64> = serde_json::from_str(&sort_procurements_at(&json, "progress", "desc", "", 0, 0, 0.0)).unwrap();
+        assert_eq!(by_id, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_sort_procurements_parses_timestamps() {
+        // Lexically "2026-05-01T01:00:00+03:00" > "2026-04-30T23:00:00Z", but it is 22:00 UTC on April 30
+        let json = serde_json::json!([
+            {"id": 1, "title": "A", "deadline": "2026-04-30T23:00:00Z"},
+            {"id": 2, "title": "B", "deadline": "2026-05-01T01:00:00+03:00"},
+            {"id": 3, "title": "C", "deadline": "2026-05-02"},
+            {"id": 4, "title": "D", "deadline": "скоро"},
+            {"id": 5, "title": "E"},
+        ]).to_string();
+
+        let asc: Vec<i64> = serde_json::from_str(&sort_procurements_at(&json, "deadline", "asc", "", 0, 0, 0.0)).unwrap();
+        assert_eq!(asc, vec![2, 1, 3, 4, 5]);
+
+        let desc: Vec<i64> = serde_json::from_str(&sort_procurements_at(&json, "deadline", "desc", "", 0, 0, 0.0)).unwrap();
+        assert_eq!(desc, vec![3, 1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn test_sort_procurements_urgency() {
+        let now = parse_iso_ms("2026-06-10T12:00:00Z").unwrap();
+        let json = serde_json::json!([
+            {"id": 1, "title": "Overdue long ago", "deadline": "2026-06-01T00:00:00Z"},
+            {"id": 2, "title": "Next week", "deadline": "2026-06-17T00:00:00Z"},
+            {"id": 3, "title": "No deadline"},
+            {"id": 4, "title": "Later today", "deadline": "2026-06-10T18:00:00Z"},
+            {"id": 5, "title": "Overdue this morning", "deadline": "2026-06-10T08:00:00Z"},
+            {"id": 6, "title": "Tomorrow", "deadline": "2026-06-11T12:00:00Z"},
+        ]).to_string();
+
+        let asc: Vec<i64> = serde_json::from_str(&sort_procurements_at(&json, "urgency", "asc", "", 0, 0, now)).unwrap();
+        assert_eq!(asc, vec![4, 6, 2, 1, 5, 3]);
+
+        // Order only applies within groups: overdue and missing deadlines stay at the end
+        let desc: Vec<i64> = serde_json::from_str(&sort_procurements_at(&json, "urgency", "desc", "", 0, 0, now)).unwrap();
+        assert_eq!(desc, vec![2, 6, 4, 5, 1, 3]);
+    }
+
+    #[test]
+    fn test_pagination() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Мед 1", "current_amount": 100.0},
+            {"id": 2, "title": "Мед 2", "current_amount": 200.0},
+            {"id": 3, "title": "Мед 3", "current_amount": 300.0},
+            {"id": 4, "title": "Мед 4", "current_amount": 400.0},
+            {"id": 5, "title": "Мед 5", "current_amount": 500.0},
+        ]).to_string();
+
+        let page: Vec<i64> = serde_json::from_str(&sort_procurements_at(&json, "amount", "asc", "", 1, 2, 0.0)).unwrap();
+        assert_eq!(page, vec![2, 3]);
+        let rest: Vec<i64> = serde_json::from_str(&sort_procurements_at(&json, "amount", "asc", "", 3, 0, 0.0)).unwrap();
+        assert_eq!(rest, vec![4, 5]);
+        assert_eq!(sort_procurements_at(&json, "amount", "asc", "", 10, 2, 0.0), "[]");
+
+        let found: Vec<serde_json::Value> = serde_json::from_str(&search_procurements(&json, "мед", 0, 2, 2, false, 0)).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(search_procurements(&json, "мед", 0, 5, 0, false, 0), "[]");
+    }
+
+    #[test]
+    fn test_search_procurements_fuzzy() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Мед из Москва", "city": "Москва"},
+            {"id": 2, "title": "Чай зеленый", "city": "Казань"},
+        ]).to_string();
+
+        let exact: Vec<serde_json::Value> = serde_json::from_str(&search_procurements(&json, "Москва", 2, 0, 0, false, 0)).unwrap();
+        let typo: Vec<serde_json::Value> = serde_json::from_str(&search_procurements(&json, "Масква", 2, 0, 0, false, 0)).unwrap();
+        assert_eq!(typo.len(), 1);
+        assert_eq!(typo[0]["id"], 1);
+        assert!(typo[0]["score"].as_f64().unwrap() < exact[0]["score"].as_f64().unwrap());
+
+        // Disabled or too strict threshold finds nothing
+        assert_eq!(search_procurements(&json, "Масква", 0, 0, 0, false, 0), "[]");
+        assert_eq!(search_procurements(&json, "Мааскваа", 2, 0, 0, false, 0), "[]");
+
+        // Short words don't fuzzy-match unrelated short titles
+        let short = serde_json::json!([
+            {"id": 1, "title": "Сыр"},
+            {"id": 2, "title": "Мак пищевой"},
+        ]).to_string();
+        assert_eq!(search_procurements(&short, "сок", 2, 0, 0, false, 0), "[]");
+    }
+
+    #[test]
+    fn test_search_procurements_category() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Наушники", "category": "Бытовая электроника"},
+            {"id": 2, "title": "Мед", "category": "Продукты"},
+            {"id": 3, "title": "Телевизор", "category": "Электроника"},
+        ]).to_string();
+
+        let result = search_procurements(&json, "электроника", 0, 0, 0, false, 0);
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0]["id"], 3);
+        assert_eq!(parsed[0]["score"], 6.0); // category weight + prefix bonus
+        assert_eq!(parsed[1]["id"], 1);
+        assert_eq!(parsed[1]["score"], 4.0); // category weight
+    }
+
+    #[test]
+    fn test_search_procurements_translit() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Телевизоры", "city": "Москва", "category": "Электроника"},
+            {"id": 2, "title": "Olive oil", "city": "Kazan", "category": "Food"},
+        ]).to_string();
+
+        // Latin query finds Cyrillic fields
+        let moskva: Vec<serde_json::Value> = serde_json::from_str(&search_procurements(&json, "moskva", 0, 0, 0, true, 0)).unwrap();
+        assert_eq!(moskva.len(), 1);
+        assert_eq!(moskva[0]["id"], 1);
+        let exact: Vec<serde_json::Value> = serde_json::from_str(&search_procurements(&json, "москва", 0, 0, 0, true, 0)).unwrap();
+        assert!(moskva[0]["score"].as_f64().unwrap() < exact[0]["score"].as_f64().unwrap());
+
+        let elektronika: Vec<serde_json::Value> = serde_json::from_str(&search_procurements(&json, "elektronika", 0, 0, 0, true, 0)).unwrap();
+        assert_eq!(elektronika[0]["id"], 1);
+
+        // Cyrillic query finds Latin fields
+        let kazan: Vec<serde_json::Value> = serde_json::from_str(&search_procurements(&json, "казань", 0, 0, 0, true, 0)).unwrap();
+        assert_eq!(kazan[0]["id"], 2);
+
+        // Disabled flag keeps plain matching only
+        assert_eq!(search_procurements(&json, "moskva", 0, 0, 0, false, 0), "[]");
+    }
+
+    #[test]
+    fn test_transliterate_to_latin() {
+        assert_eq!(transliterate_to_latin("москва"), "moskva");
+        assert_eq!(transliterate_to_latin("электроника"), "elektronika");
+        assert_eq!(transliterate_to_latin("щука и ёж"), "shchuka i ezh");
+        assert_eq!(transliterate_to_latin("abc 123"), "abc 123");
+    }
+
+    #[test]
+    fn test_search_procurements_highlighted() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Мед натуральный алтайский", "city": "Барнаул"},
+            {"id": 2, "title": "Чай зеленый", "city": "Москва"},
+        ]).to_string();
+
+        let result = search_procurements_highlighted(&json, "алтайский мед");
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0]["id"], 1);
+        assert_eq!(parsed[0]["title_ranges"], serde_json::json!([[0, 3], [16, 25]]));
+
+        // City-only match has no title ranges
+        let city: Vec<serde_json::Value> = serde_json::from_str(&search_procurements_highlighted(&json, "москва")).unwrap();
+        assert_eq!(city[0]["id"], 2);
+        assert_eq!(city[0]["title_ranges"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_match_ranges_merges_overlaps() {
+        assert_eq!(match_ranges("абвгд", &["абв", "бвг"]), vec![[0, 4]]);
+        assert_eq!(match_ranges("aa aa", &["aa"]), vec![[0, 2], [3, 5]]);
+    }
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("", ""), 0);
+        assert_eq!(levenshtein_distance("abc", ""), 3);
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("масква", "москва"), 1);
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Купим ноутбуки!"), "kupim-noutbuki");
+        assert_eq!(slugify("  Объём — 5 ЛИТРОВ, цена/шт.  "), "obem-5-litrov-tsena-sht");
+        assert_eq!(slugify("Пицца 🍕🍕 на всех"), "pitstsa-na-vsekh");
+        assert_eq!(slugify("!!! --- ???"), "");
+        assert_eq!(slugify(""), "");
+
+        // Capped at 80 chars without splitting "shch"
+        let slug = slugify(&format!("{}щука", "a".repeat(77)));
+        assert_eq!(slug, "a".repeat(77));
+        let slug = slugify(&"слово ".repeat(30));
+        assert!(slug.len() <= 80);
+        assert!(!slug.ends_with('-'));
+    }
+
+    #[test]
+    fn test_truncate_text() {
+        assert_eq!(truncate_text("Купим ноутбуки оптом", 10), "Купим ноу…");
+        assert_eq!(truncate_text("Купим ноутбуки", 7), "Купим…");
+        assert_eq!(truncate_text("Коротко", 7), "Коротко");
+        assert_eq!(truncate_text("Коротко", 50), "Коротко");
+        assert_eq!(truncate_text("abc", 0), "");
+        assert_eq!(truncate_text("abc", -5), "");
+        assert_eq!(truncate_text("abc", 1), "…");
+
+        // Emoji and combined graphemes at the boundary are kept whole or dropped whole
+        assert_eq!(truncate_text("ab👨‍👩‍👧cd", 4), "ab👨‍👩‍👧…");
+        assert_eq!(truncate_text("ab👨‍👩‍👧cd", 3), "ab…");
+        assert_eq!(truncate_text("ab🇷🇺", 3), "ab🇷🇺");
+    }
+
+    #[test]
+    fn test_render_description_md() {
+        let md = "# Мёд с пасеки\n\nСобираем **заказ** до пятницы.\nПодробнее: https://example.com/honey?a=1&b=2.\n\n## Что входит\n- Липовый мёд\n- Гречишный _тёмный_ мёд\n\nДоставка по городу";
+        assert_eq!(render_description_md(md), concat!(
+            "<h2>Мёд с пасеки</h2>",
+            "<p>Собираем <b>заказ</b> до пятницы.<br>",
+            "Подробнее: <a href=\"https://example.com/honey?a=1&b=2\" target=\"_blank\" rel=\"noopener\">https://example.com/honey?a=1&amp;b=2</a>.</p>",
+            "<h3>Что входит</h3>",
+            "<ul><li>Липовый мёд</li><li>Гречишный <i>тёмный</i> мёд</li></ul>",
+            "<p>Доставка по городу</p>",
+        ));
+
+        assert_eq!(render_description_md("- один\n- *два*"), "<ul><li>один</li><li><b>два</b></li></ul>");
+        assert_eq!(render_description_md("#хэштег, не заголовок"), "<p>#хэштег, не заголовок</p>");
+        assert_eq!(render_description_md(""), "");
+
+        // A literal "**" that isn't a bold marker survives untouched
+        assert_eq!(render_description_md("Объём 2**3 литра"), "<p>Объём 2**3 литра</p>");
+        assert_eq!(render_description_md("Скидка ** только сегодня"), "<p>Скидка ** только сегодня</p>");
+        assert_eq!(render_description_md("**Важно:** 2*3 = 6"), "<p><b>Важно:</b> 2*3 = 6</p>");
+        assert_eq!(render_description_md("**мёд _липовый_**"), "<p><b>мёд <i>липовый</i></b></p>");
+    }
+
+    #[test]
+    fn test_render_description_md_injection() {
+        let html = render_description_md("# <script>alert(1)</script>\n- <img src=x onerror=alert(1)>\n![pic](javascript:alert(1))");
+        assert!(!html.contains("<script") && !html.contains("<img"));
+        assert!(html.contains("<h2>&lt;script&gt;alert(1)&lt;/script&gt;</h2>"));
+        assert!(html.contains("<li>&lt;img src=x onerror=alert(1)&gt;</li>"));
+        assert!(html.contains("<p>![pic](javascript:alert(1))</p>"));
+
+        let html = render_description_md("https://evil.example/\"onmouseover=\"alert(1)");
+        assert!(!html.contains("\"onmouseover"));
+    }
+
+    #[test]
+    fn test_message_preview() {
+        let text = "Привет всем!\n\nСегодня *последний* день   сбора,\nне забудьте оплатить";
+        assert_eq!(message_preview(text, 100, "text"), "Привет всем! Сегодня последний день сбора, не забудьте оплатить");
+        assert_eq!(message_preview(text, 20, "text"), "Привет всем! Сегодн…");
+        assert_eq!(message_preview("👨\u{200d}👩\u{200d}👧 ура ура", 4, "text"), "👨\u{200d}👩\u{200d}👧 у…");
+
+        assert_eq!(message_preview("Иван присоединился к закупке", 50, "system"), "Иван присоединился к закупке");
+        assert_eq!(message_preview("Иван присоединился к закупке", 10, "system"), "Иван прис…");
+        assert_eq!(message_preview("Сбор  продлён\nдо *пятницы*", 50, "system"), "Сбор продлён до пятницы");
+
+        assert_eq!(message_preview("", 50, "image"), "Фото");
+        assert_eq!(message_preview("смотрите", 50, "image"), "смотрите");
+        assert_eq!(message_preview("  ", 50, "file"), "Файл");
+    }
+
+    #[test]
+    fn test_message_length() {
+        let text = "Привет 👨\u{200d}👩\u{200d}👧";
+        assert_eq!(text.len(), 31);
+        assert_eq!(text.chars().count(), 12);
+        assert_eq!(message_length(text), 8);
+
+        assert_eq!(message_length(""), 0);
+        assert_eq!(message_length("🇷🇺👍🏽"), 2);
+        assert_eq!(message_length("e\u{301}"), 1);
+
+        assert!(message_length_ok(text, 8));
+        assert!(!message_length_ok(text, 7));
+        assert!(message_length_ok("", 0));
+        assert!(!message_length_ok("a", -1));
+    }
+
+    #[test]
+    fn test_text_stats() {
+        let stats: serde_json::Value = serde_json::from_str(&text_stats("Купим 10 ноутбуков Lenovo, доставка — в Москву!")).unwrap();
+        assert_eq!(stats["chars"], 47);
+        assert_eq!(stats["words"], 7);
+        assert_eq!(stats["reading_minutes"], 1);
+
+        let long = "слово ".repeat(181);
+        let stats: serde_json::Value = serde_json::from_str(&text_stats(&long)).unwrap();
+        assert_eq!(stats["words"], 181);
+        assert_eq!(stats["reading_minutes"], 2);
+
+        for empty in ["", "   \n\t "] {
+            let stats: serde_json::Value = serde_json::from_str(&text_stats(empty)).unwrap();
+            assert_eq!(stats, serde_json::json!({"chars": 0, "words": 0, "reading_minutes": 0}));
+        }
+    }
+
+    #[test]
+    fn test_highlight_terms() {
+        assert_eq!(
+            highlight_terms("Купим ноутбуки в Москве", "москве НОУТ"),
+            "Купим <mark>ноут</mark>буки в <mark>Москве</mark>"
+        );
+        // Overlapping and adjacent matches merge instead of nesting
+        assert_eq!(highlight_terms("абвгд", "абв бвг"), "<mark>абвг</mark>д");
+        assert_eq!(highlight_terms("abcdef", "abc def"), "<mark>abcdef</mark>");
+
+        // Entities produced by escaping are never highlighted from the inside
+        assert_eq!(highlight_terms("Tom & Jerry", "amp"), "Tom &amp; Jerry");
+        assert_eq!(highlight_terms("a < b & c", "& b"), "a &lt; <mark>b</mark> <mark>&amp;</mark> c");
+        assert_eq!(highlight_terms("<b>x</b>", ""), "&lt;b&gt;x&lt;/b&gt;");
+    }
+
+    #[test]
+    fn test_validate_procurement_forms() {
+        let now = 1_710_504_000_000.0;
+        let json = serde_json::json!([
+            {"title": "Мёд", "description": "Алтайский мёд", "city": "Москва",
+             "target_amount": 5000.0, "deadline_ms": now + 86_400_000.0},
+            {"title": "", "description": "Описание", "city": "Москва",
+             "target_amount": 500.0, "deadline_ms": now + 86_400_000.0, "min_target": 1000.0},
+            "not a form",
+        ]).to_string();
+
+        let results: Vec<serde_json::Value> = serde_json::from_str(&validate_procurement_forms_at(&json, now)).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], serde_json::json!({}));
+        assert_eq!(results[1]["title"], "Название обязательно");
+        assert!(results[1]["target_amount"].as_str().unwrap().starts_with("Целевая сумма должна быть не меньше"));
+        assert_eq!(results[2]["form"], "Неверный формат формы");
+
+        // Same result as the single-form validator
+        let single = validate_procurement_form_at("", "Описание", "Москва", 500.0, now + 86_400_000.0, 1000.0, 0, now);
+        assert_eq!(results[1], serde_json::from_str::<serde_json::Value>(&single).unwrap());
+        assert_eq!(validate_procurement_forms_at("oops", now), "[]");
+    }
+
+    #[test]
+    fn test_parse_deadline() {
+        // Date-only input means the end of that day
+        assert_eq!(parse_deadline("2024-03-15"), 1_710_547_199_999.0);
+        assert_eq!(parse_deadline(" 2024-03-15 "), 1_710_547_199_999.0);
+        assert_eq!(parse_deadline("2024-03-15T12:00:00Z"), 1_710_504_000_000.0);
+        assert_eq!(parse_deadline("2024-03-15T15:00:00+03:00"), 1_710_504_000_000.0);
+        assert!(parse_deadline("15.03.2024").is_nan());
+        assert!(parse_deadline("2024-02-30").is_nan());
+        assert!(parse_deadline("").is_nan());
+    }
+
+    #[test]
+    fn test_validate_deadline_string() {
+        let now = 1_710_504_000_000.0; // 2024-03-15T12:00:00Z
+        assert_eq!(validate_deadline_string_at("2024-04-01", now), "");
+        // Today as a date-only deadline is still valid until the end of the day
+        assert_eq!(validate_deadline_string_at("2024-03-15", now), "");
+        assert_eq!(validate_deadline_string_at("2024-03-14", now), "Дедлайн должен быть в будущем");
+        assert_eq!(validate_deadline_string_at("2024-03-15T11:00:00Z", now), "Дедлайн должен быть в будущем");
+        assert_eq!(validate_deadline_string_at("завтра", now), "Неверный формат даты");
+        assert_eq!(validate_deadline_string_at("  ", now), "Дедлайн обязателен");
+    }
+
+    #[test]
+    fn test_format_file_size() {
+        assert_eq!(format_file_size(0.0), "0 Б");
+        assert_eq!(format_file_size(1023.0), "1023 Б");
+        assert_eq!(format_file_size(1024.0), "1,0 КБ");
+        assert_eq!(format_file_size(1536.0), "1,5 КБ");
+        assert_eq!(format_file_size(1_048_575.0), "1,0 МБ");
+        assert_eq!(format_file_size(1_048_576.0), "1,0 МБ");
+        assert_eq!(format_file_size(3.4 * 1_048_576.0), "3,4 МБ");
+        assert_eq!(format_file_size(1_073_741_823.0), "1,0 ГБ");
+        assert_eq!(format_file_size(5.0 * 1_099_511_627_776.0), "5120,0 ГБ");
+        assert_eq!(format_file_size(-1.0), "0 Б");
+        assert_eq!(format_file_size(f64::NAN), "0 Б");
+        assert_eq!(format_file_size(f64::INFINITY), "0 Б");
+        assert_eq!(format_file_size(f64::NEG_INFINITY), "0 Б");
+    }
+
+    #[test]
+    fn test_validate_attachment() {
+        assert_eq!(validate_attachment("image/png", 1024.0), "");
+        assert_eq!(validate_attachment("IMAGE/JPEG", 0.0), "");
+        assert_eq!(validate_attachment("video/mp4; codecs=\"avc1\"", 10_000_000.0), "");
+        assert_eq!(validate_attachment("image/png", 25.0 * 1024.0 * 1024.0), "");
+        assert_eq!(validate_attachment("image/png", 25.0 * 1024.0 * 1024.0 + 1.0), "Файл слишком большой");
+        assert_eq!(validate_attachment("application/pdf", 1024.0), "Недопустимый формат");
+        assert_eq!(validate_attachment("", 1024.0), "Недопустимый формат");
+        assert_eq!(validate_attachment("image/gif", f64::NAN), "Некорректный размер файла");
+    }
+
+    #[test]
+    fn test_base64url() {
+        assert_eq!(base64url_encode(b""), "");
+        assert_eq!(base64url_encode(b"f"), "Zg");
+        assert_eq!(base64url_encode(b"foob"), "Zm9vYg");
+        assert_eq!(base64url_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64url_encode(&[0xfb, 0xff]), "-_8");
+        assert_eq!(base64url_decode("Zm9vYg"), b"foob".to_vec());
+        assert_eq!(base64url_decode("Zm9vYg=="), b"foob".to_vec());
+        assert_eq!(base64url_decode("-_8"), vec![0xfb, 0xff]);
+        assert_eq!(base64url_decode("Zm9vY"), Vec::<u8>::new());
+        assert_eq!(base64url_decode("Zm+v"), Vec::<u8>::new());
+        assert_eq!(base64url_decode("Zm9v Yg"), Vec::<u8>::new());
+        assert_eq!(try_base64url_decode(""), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_base64url_binary_round_trip() {
+        // Bytes that standard base64 encodes with '+' and '/'
+        let data = [0xfb, 0xef, 0xbe, 0xff, 0xff, 0xff, 0x3e, 0x3f];
+        assert_eq!(base64url_encode(&data), "----____Pj8");
+        assert_eq!(base64url_decode(&base64url_encode(&data)), data.to_vec());
+
+        let all: Vec<u8> = (0..=255).collect();
+        for len in 0..all.len() {
+            let encoded = base64url_encode(&all[..len]);
+            assert!(!encoded.contains(['+', '/', '=']));
+            assert_eq!(base64url_decode(&encoded), all[..len].to_vec());
+        }
+    }
+
+    #[test]
+    fn test_share_link_round_trip() {
+        assert_eq!(build_share_link("groupbuy_bot", 123), "https://t.me/groupbuy_bot?start=cHJvY18xMjM");
+        assert_eq!(build_share_link("@groupbuy_bot", 123), "https://t.me/groupbuy_bot?start=cHJvY18xMjM");
+
+        for id in [0, 1, 42, 123, 99_999, i64::MAX] {
+            let link = build_share_link("groupbuy_bot", id);
+            let param = link.split("start=").nth(1).unwrap();
+            assert!(param.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+            assert_eq!(parse_start_param(param), id);
+        }
+    }
+
+    #[test]
+    fn test_parse_start_param_malformed() {
+        assert_eq!(parse_start_param(""), -1);
+        assert_eq!(parse_start_param("proc_123"), -1);
+        assert_eq!(parse_start_param("!!!"), -1);
+        assert_eq!(parse_start_param(&base64url_encode(b"user_123")), -1);
+        assert_eq!(parse_start_param(&base64url_encode(b"proc_")), -1);
+        assert_eq!(parse_start_param(&base64url_encode(b"proc_-5")), -1);
+        assert_eq!(parse_start_param(&base64url_encode(b"proc_12a")), -1);
+        assert_eq!(parse_start_param(&base64url_encode(&[0xff, 0xfe])), -1);
+    }
+
+    #[test]
+    fn test_verify_hmac_sha256() {
+        let payload = r#"{"event":"payment.succeeded","amount":1500}"#;
+        let signature = "01688f9c29c21baefc3ae272991d51ddd07bb72e734901c7848a1f1e75f9886a";
+        assert!(verify_hmac_sha256(payload, signature, "whsec_test"));
+        assert!(verify_hmac_sha256(payload, &signature.to_uppercase(), "whsec_test"));
+
+        assert!(!verify_hmac_sha256(payload, signature, "whsec_other"));
+        assert!(!verify_hmac_sha256(&payload.replace("1500", "15000"), signature, "whsec_test"));
+        // Truncated, odd-length, non-hex and empty signatures are rejected
+        assert!(!verify_hmac_sha256(payload, &signature[..62], "whsec_test"));
+        assert!(!verify_hmac_sha256(payload, &signature[..63], "whsec_test"));
+        assert!(!verify_hmac_sha256(payload, &signature.replace('0', "g"), "whsec_test"));
+        assert!(!verify_hmac_sha256(payload, "", "whsec_test"));
+
+        assert_eq!(hex_decode("0fA0"), Some(vec![0x0f, 0xa0]));
+        assert_eq!(hex_decode("+f"), None);
+        assert_eq!(hex_decode("+f+f"), None);
+        assert_eq!(hex_decode("-1"), None);
+    }
+
+    const TEST_BOT_TOKEN: &str = "123456:ABC-DEF1234ghIkl-zyx57W2v1u123ew11";
+    const TEST_INIT_DATA: &str = "auth_date=1700000000&query_id=AAHdF6IQAAAAAN0XohDhrOrc&user=%7B%22id%22%3A279058397%2C%22first_name%22%3A%22%D0%98%D0%B2%D0%B0%D0%BD%22%2C%22username%22%3A%22ivan%22%7D&hash=724960347a36e73c5144e8992fc0b3d91d67d1dcec029b38d9d6435c9d4462bc";
+
+    #[test]
+    fn test_verify_telegram_init_data() {
+        assert!(verify_telegram_init_data(TEST_INIT_DATA, TEST_BOT_TOKEN));
+        // Field order in the query string doesn't matter
+        let (fields, hash) = TEST_INIT_DATA.rsplit_once('&').unwrap();
+        assert!(verify_telegram_init_data(&format!("{}&{}", hash, fields), TEST_BOT_TOKEN));
+
+        // Wrong bot token
+        assert!(!verify_telegram_init_data(TEST_INIT_DATA, "123456:other-token"));
+    }
+
+    #[test]
+    fn test_verify_telegram_init_data_tampered() {
+        let tampered = TEST_INIT_DATA.replace("279058397", "279058398");
+        assert!(!verify_telegram_init_data(&tampered, TEST_BOT_TOKEN));
+        let tampered = TEST_INIT_DATA.replace("auth_date=1700000000", "auth_date=1800000000");
+        assert!(!verify_telegram_init_data(&tampered, TEST_BOT_TOKEN));
+
+        let (without_hash, _) = TEST_INIT_DATA.rsplit_once('&').unwrap();
+        assert!(!verify_telegram_init_data(without_hash, TEST_BOT_TOKEN));
+        assert!(!verify_telegram_init_data(&format!("{}&hash=zz", without_hash), TEST_BOT_TOKEN));
+        assert!(!verify_telegram_init_data("", TEST_BOT_TOKEN));
+    }
+
+    #[test]
+    fn test_procurements_to_csv() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Ноутбуки, мыши", "city": "Москва", "status": "active",
+             "current_amount": 1500.5, "target_amount": 3000.0, "participant_count": 4,
+             "deadline": "2024-03-15T12:00:00Z"},
+            {"id": 2, "title": "Чай \"Да Хун Пао\""},
+        ]).to_string();
+
+        let csv = procurements_to_csv(&json);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], "id,title,city,status,current_amount,target_amount,progress,participant_count,deadline");
+        assert_eq!(lines[1], "1,\"Ноутбуки, мыши\",Москва,active,1500.5,3000,50,4,2024-03-15T12:00:00Z");
+        assert_eq!(lines[2], "2,\"Чай \"\"Да Хун Пао\"\"\",,,,,0,,");
+        assert_eq!(lines[3], "");
+        assert_eq!(lines.len(), 4);
+
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+        assert_eq!(procurements_to_csv("not json"), "");
+    }
+
+    #[test]
+    fn test_parse_csv_records() {
+        let records = parse_csv_records("a,b\r\n\"x, y\",\"say \"\"hi\"\"\"\r\n\r\n\"multi\nline\",\n");
+        assert_eq!(records, vec![
+            vec!["a".to_string(), "b".to_string()],
+            vec!["x, y".to_string(), "say \"hi\"".to_string()],
+            vec!["multi\nline".to_string(), "".to_string()],
+        ]);
+        assert!(parse_csv_records("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_procurements_csv() {
+        let now = 1_710_504_000_000.0; // 2024-03-15T12:00:00Z
+        let csv = "id,title,city,status,current_amount,target_amount,progress,participant_count,deadline\r\n\
+                   1,\"Ноутбуки, мыши\",Москва,active,1500.5,3000,50,4,2024-04-01\r\n\
+                   2,Чай,Казань,draft,,\"2000,5\",0,,2024-05-01T10:00:00Z\r\n";
+        let result: serde_json::Value = serde_json::from_str(&parse_procurements_csv_at(csv, now)).unwrap();
+
+        assert_eq!(result["errors"], serde_json::json!([{}, {}]));
+        let procurements = result["procurements"].as_array().unwrap();
+        assert_eq!(procurements.len(), 2);
+        assert_eq!(procurements[0]["id"], 1);
+        assert_eq!(procurements[0]["title"], "Ноутбуки, мыши");
+        assert_eq!(procurements[0]["current_amount"], 1500.5);
+        assert_eq!(procurements[0]["participant_count"], 4);
+        assert_eq!(procurements[1]["target_amount"], 2000.5);
+        assert_eq!(procurements[1]["current_amount"], serde_json::Value::Null);
+
+        // Round-trips the export format
+        let exported = procurements_to_csv(&serde_json::to_string(procurements).unwrap());
+        let reparsed: serde_json::Value = serde_json::from_str(&parse_procurements_csv_at(&exported, now)).unwrap();
+        assert_eq!(&reparsed["procurements"], &result["procurements"]);
+    }
+
+    #[test]
+    fn test_parse_procurements_csv_malformed_row() {
+        let now = 1_710_504_000_000.0; // 2024-03-15T12:00:00Z
+        let csv = "id,title,city,status,current_amount,target_amount,progress,participant_count,deadline\n\
+                   1,Чай,Казань,draft,0,1000,0,0,2024-04-01\n\
+                   2,Кофе,Казань\n\
+                   3,,Москва,draft,0,0,0,0,2024-03-01\n\
+                   4,Сахар,Москва,draft,0,100,0,0,завтра\n\
+                   x5,Соль,Москва,draft,много,100,0,пять,2024-04-01\n";
+        let result: serde_json::Value = serde_json::from_str(&parse_procurements_csv_at(csv, now)).unwrap();
+        let errors = result["errors"].as_array().unwrap();
+        let procurements = result["procurements"].as_array().unwrap();
+        assert_eq!(errors.len(), 5);
+        assert_eq!(procurements.len(), 5);
+
+        assert_eq!(errors[0], serde_json::json!({}));
+        assert_eq!(procurements[1], serde_json::Value::Null);
+        assert_eq!(errors[1]["row"], "Неверное число столбцов: ожидается 9, получено 3");
+        assert_eq!(errors[2]["title"], "Название обязательно");
+        assert_eq!(errors[2]["target_amount"], "Целевая сумма должна быть положительной");
+        assert_eq!(errors[2]["deadline"], "Дедлайн должен быть в будущем");
+        assert_eq!(errors[3]["deadline"], "Неверный формат даты");
+        assert_eq!(procurements[3]["title"], "Сахар");
+
+        assert_eq!(errors[4], serde_json::json!({
+            "id": "Некорректный идентификатор",
+            "current_amount": "Некорректная сумма",
+            "participant_count": "Некорректное число участников",
+        }));
+        assert_eq!(procurements[4]["id"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_batch_process_procurements_msgpack_matches_json() {
+        let now = 1_710_504_000_000.0; // 2024-03-15T12:00:00Z
+        let sample = serde_json::json!([
+            {"id": 1, "title": "Ноутбуки", "city": "Москва", "status": "active",
+             "current_amount": 1500.5, "target_amount": 3000.0, "participant_count": 4,
+             "deadline": "2024-03-25T12:00:00Z", "created_at": "2024-03-01T09:00:00Z"},
+            {"id": 2, "title": "Чай", "description": "Пуэр"},
+        ]);
+
+        let from_json: serde_json::Value =
+            serde_json::from_str(&batch_process_procurements_at(&sample.to_string(), now)).unwrap();
+        let input = rmp_serde::to_vec_named(&sample).unwrap();
+        let output = batch_process_procurements_msgpack_at(&input, now);
+        let from_msgpack: serde_json::Value = rmp_serde::from_slice(&output).unwrap();
+
+        assert_eq!(from_msgpack, from_json);
+        assert_eq!(from_msgpack[0]["days_left"], 10);
+        assert_eq!(from_msgpack[0]["progress"], 50);
+
+        let empty: Vec<serde_json::Value> = rmp_serde::from_slice(&batch_process_procurements_msgpack_at(b"junk", now)).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_benchmark_batch_processing_breakdown() {
+        let clock = std::time::Instant::now();
+        let now = || clock.elapsed().as_secs_f64() * 1000.0;
+        let result: serde_json::Value = serde_json::from_str(&benchmark_batch_processing_with(200, 42, &now)).unwrap();
+
+        let parts = ["generate_ms", "batch_ms", "search_ms", "sort_ms", "aggregate_ms"];
+        let sum: f64 = parts.iter().map(|key| result[key].as_f64().unwrap()).sum();
+        let total = result["total_ms"].as_f64().unwrap();
+        assert!(parts.iter().all(|key| result[key].as_f64().unwrap() >= 0.0));
+        assert!(total >= sum - 0.001, "total {} < sum of parts {}", total, sum);
+    }
+
+    #[test]
+    fn test_generate_benchmark_procurements_seeded() {
+        let first = generate_benchmark_procurements(100, 42);
+        assert_eq!(first, generate_benchmark_procurements(100, 42));
+        assert_ne!(first, generate_benchmark_procurements(100, 43));
+        assert_eq!(generate_benchmark_procurements(10, 0), generate_benchmark_procurements(10, 0));
+
+        let procurements: Vec<Procurement> = serde_json::from_str(&first).unwrap();
+        assert_eq!(procurements.len(), 100);
+        assert!(procurements.iter().all(|p| p.deadline.as_deref().and_then(parse_iso_ms).is_some()));
+        assert_eq!(generate_benchmark_procurements(0, 1), "[]");
+    }
+
+    #[test]
+    fn test_diff_procurements() {
+        let old = serde_json::json!([
+            {"id": 1, "title": "A", "status": "active", "current_amount": 100.0},
+            {"id": 2, "title": "B", "status": "active", "current_amount": 200.0},
+            {"id": 3, "title": "C", "status": "draft"},
+        ]).to_string();
+        let new = serde_json::json!([
+            {"id": 4, "title": "D", "status": "draft"},
+            {"id": 1, "title": "A", "status": "active", "current_amount": 150.0},
+            {"id": 2, "title": "B", "status": "active", "current_amount": 200.0, "description": "untracked"},
+        ]).to_string();
+
+        let diff: serde_json::Value = serde_json::from_str(&diff_procurements(&old, &new)).unwrap();
+        assert_eq!(diff["added"], serde_json::json!([4]));
+        assert_eq!(diff["removed"], serde_json::json!([3]));
+        assert_eq!(diff["changed"], serde_json::json!([1]));
+
+        let diff: serde_json::Value = serde_json::from_str(&diff_procurements(&old, &old)).unwrap();
+        assert_eq!(diff, serde_json::json!({"added": [], "removed": [], "changed": []}));
+        let diff: serde_json::Value = serde_json::from_str(&diff_procurements("oops", &new)).unwrap();
+        assert_eq!(diff, serde_json::json!({"added": [], "removed": [], "changed": []}));
+    }
+
+    #[test]
+    fn test_validate_email_strict() {
+        assert!(validate_email_strict("ivan.petrov@example.com"));
+        assert!(validate_email_strict("org+shop@mail.co.uk"));
+        assert!(validate_email_strict("заказ@пример.рф"));
+
+        assert!(!validate_email_strict("a..b@x.com"));
+        assert!(!validate_email_strict(".a@x.com"));
+        assert!(!validate_email_strict("a.@x.com"));
+        assert!(!validate_email_strict("a@x"));
+        assert!(!validate_email_strict("a@x.c"));
+        assert!(!validate_email_strict("a@x.c0m"));
+        assert!(!validate_email_strict("a b@x.com"));
+        assert!(!validate_email_strict("a@b@x.com"));
+        assert!(!validate_email_strict("a@x..com"));
+        assert!(!validate_email_strict("a@-x.com"));
+        assert!(!validate_email_strict("@x.com"));
+        assert!(!validate_email_strict(""));
+
+        // The lenient validator still accepts what strict mode rejects
+        assert!(validate_email("a..b@x.com"));
+    }
 
-    // ── Validation tests ──
+    #[test]
+    fn test_is_disposable_email() {
+        assert!(DISPOSABLE_EMAIL_DOMAINS.windows(2).all(|w| w[0] < w[1]), "list must stay sorted");
+
+        assert!(is_disposable_email("buyer@mailinator.com"));
+        assert!(is_disposable_email("Buyer@MAILINATOR.COM"));
+        assert!(is_disposable_email("buyer@inbox.guerrillamail.com"));
+        assert!(is_disposable_email("x@a.b.yopmail.com"));
+
+        assert!(!is_disposable_email("ivan@gmail.com"));
+        assert!(!is_disposable_email("ivan@notmailinator.com"));
+        assert!(!is_disposable_email("mailinator.com"));
+        assert!(!is_disposable_email(""));
+    }
 
     #[test]
-    fn test_validate_phone_valid() {
-        assert!(validate_phone("+79991234567"));
-        assert!(validate_phone("+7 999 123 4567"));
-        assert!(validate_phone("")); // empty is valid (optional)
+    fn test_censor_profanity() {
+        assert_eq!(censor_profanity("What the fuck, man"), "What the f***, man");
+        assert_eq!(censor_profanity("Ну ты и сука!"), "Ну ты и с***!");
+        assert_eq!(censor_profanity("FUCKING slow delivery"), "F****** slow delivery");
+        // Leetspeak and Latin look-alikes
+        assert_eq!(censor_profanity("this is sh1t"), "this is s***");
+        assert_eq!(censor_profanity("b1tch please"), "b**** please");
+        assert_eq!(censor_profanity("cук@ опять"), "c*** опять");
+
+        // Clean text and words that merely contain a listed word stay untouched
+        let clean = "Скидка на хурму и суккуленты, dickens и @user! Цена 300₽";
+        assert_eq!(censor_profanity(clean), clean);
+        assert!(!contains_profanity(clean));
+        assert!(contains_profanity("ну бля"));
+        assert_eq!(censor_profanity(""), "");
     }
 
     #[test]
-    fn test_validate_phone_invalid() {
-        assert!(!validate_phone("123")); // too short, no +
-        assert!(!validate_phone("+123")); // too short even with +
+    fn test_message_spam_score() {
+        let normal = message_spam_score("Добрый день! Когда будет доставка ноутбуков в Москву?");
+        let spam = message_spam_score(
+            "БЕСПЛАТНО!!!!! ЗАРАБОТОК ОТ 100К https://a.ru https://b.ru https://c.ru ПЕРЕХОДИ");
+        assert!(normal < 0.1, "normal message scored {}", normal);
+        assert!(spam > 0.8, "spam message scored {}", spam);
+        assert!(spam <= 1.0);
+
+        // More links never lower the score
+        let mut previous = 0.0;
+        for n in 0..6 {
+            let text = format!("смотри {}", "https://x.ru ".repeat(n));
+            let score = message_spam_score(&text);
+            assert!(score >= previous);
+            previous = score;
+        }
+        assert_eq!(message_spam_score(""), 0.0);
     }
 
     #[test]
-    fn test_validate_email_valid() {
-        assert!(validate_email("user@example.com"));
-        assert!(validate_email("test@mail.ru"));
-        assert!(validate_email("")); // empty is valid (optional)
+    fn test_gzip_round_trip() {
+        let json = generate_benchmark_procurements(100, 3);
+        assert!(json.len() > 10_000);
+
+        let compressed = gzip_compress(&json);
+        assert!(compressed.len() < json.len() / 3, "{} vs {}", compressed.len(), json.len());
+        assert_eq!(&compressed[..2], &[0x1f, 0x8b]);
+        assert_eq!(gzip_decompress(&compressed), json);
+
+        assert_eq!(gzip_decompress(&gzip_compress("")), "");
+        assert_eq!(gzip_decompress(&gzip_compress("Привет 👋")), "Привет 👋");
     }
 
     #[test]
-    fn test_validate_email_invalid() {
-        assert!(!validate_email("notanemail"));
-        assert!(!validate_email("@domain.com"));
-        assert!(!validate_email("user@"));
+    fn test_gzip_decompress_invalid() {
+        assert_eq!(gzip_decompress(b""), "");
+        assert_eq!(gzip_decompress(b"not gzip at all"), "");
+        let compressed = gzip_compress(&"мёд ".repeat(1000));
+        assert_eq!(gzip_decompress(&compressed[..compressed.len() / 2]), "");
     }
 
-    // ── Formatting tests ──
+    #[test]
+    fn test_rate_limiter() {
+        let mut limiter = RateLimiter::new_at(3.0, 2.0, 1000.0);
+        assert_eq!(limiter.tokens_remaining_at(1000.0), 3.0);
+
+        // Burst up to capacity, then throttle
+        assert!(limiter.try_acquire_at(1000.0));
+        assert!(limiter.try_acquire_at(1000.0));
+        assert!(limiter.try_acquire_at(1000.0));
+        assert!(!limiter.try_acquire_at(1000.0));
+        assert!(!limiter.try_acquire_at(1400.0)); // 0.8 tokens refilled
+
+        // 2 tokens/sec: one token after 500 ms
+        assert!(limiter.try_acquire_at(1500.0));
+        assert!(!limiter.try_acquire_at(1500.0));
+
+        // Refill is capped at capacity, and a clock going backwards changes nothing
+        assert_eq!(limiter.tokens_remaining_at(60_000.0), 3.0);
+        assert_eq!(limiter.tokens_remaining_at(10_000.0), 3.0);
+    }
 
     #[test]
-    fn test_calculate_progress() {
-        assert_eq!(calculate_progress(500.0, 1000.0), 50);
-        assert_eq!(calculate_progress(0.0, 1000.0), 0);
-        assert_eq!(calculate_progress(1500.0, 1000.0), 100); // capped at 100
-        assert_eq!(calculate_progress(100.0, 0.0), 0); // division by zero guard
+    fn test_fold_diacritics() {
+        assert_eq!(fold_diacritics("café", false), "cafe");
+        assert_eq!(fold_diacritics("Naïve Crème Brûlée", false), "Naive Creme Brulee");
+        assert_eq!(fold_diacritics("cafe\u{301}", false), "cafe"); // decomposed input
+        assert_eq!(fold_diacritics("Ёлка и йогурт", false), "Ёлка и йогурт");
+        assert_eq!(fold_diacritics("Ёлка и йогурт", true), "Елка и йогурт");
     }
 
     #[test]
-    fn test_format_currency() {
-        assert_eq!(format_currency(1000.0), "1 000 ₽");
-        assert_eq!(format_currency(0.0), "0 ₽");
-        assert_eq!(format_currency(1234567.89), "1 234 567,89 ₽");
-        assert_eq!(format_currency(99.5), "99,50 ₽");
+    fn test_search_procurements_folds_diacritics() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Café equipment"},
+            {"id": 2, "title": "Ёлочные игрушки"},
+            {"id": 3, "title": "Елки живые"},
+        ]).to_string();
+
+        let ids = |query: &str| -> Vec<i64> {
+            let results: Vec<serde_json::Value> = serde_json::from_str(&search_procurements(&json, query, 0, 0, 0, false, 0)).unwrap();
+            let mut ids: Vec<i64> = results.iter().map(|r| r["id"].as_i64().unwrap()).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids("cafe"), vec![1]);
+        assert_eq!(ids("CAFÉ"), vec![1]);
+        assert_eq!(ids("елоч"), vec![2]);
+        assert_eq!(ids("ёлки"), vec![3]);
+        assert_eq!(ids("ел"), vec![2, 3]);
+
+        let highlighted: serde_json::Value = serde_json::from_str(&search_procurements_highlighted(&json, "cafe")).unwrap();
+        assert_eq!(highlighted[0]["title_ranges"], serde_json::json!([[0, 4]]));
+
+        // Folding shrinks a decomposed "e\u{301}" to one char; ranges still index the title
+        let decomposed = serde_json::json!([
+            {"id": 1, "title": "Cafe\u{301} и мед"},
+            {"id": 2, "title": "İstanbul мед"},
+        ]).to_string();
+        let highlighted: Vec<serde_json::Value> =
+            serde_json::from_str(&search_procurements_highlighted(&decomposed, "мед cafe")).unwrap();
+        let ranges = |id: i64| highlighted.iter().find(|r| r["id"] == id).unwrap()["title_ranges"].clone();
+        assert_eq!(ranges(1), serde_json::json!([[0, 5], [8, 11]]));
+        assert_eq!(ranges(2), serde_json::json!([[9, 12]]));
     }
 
     #[test]
-    fn test_get_avatar_color() {
-        let color = get_avatar_color("Тест");
-        assert!(color.starts_with('#'));
-        assert_eq!(color.len(), 7); // #RRGGBB format
+    fn test_validate_username() {
+        assert_eq!(validate_username("ivan_petrov"), "");
+        assert_eq!(validate_username("Buyer2024"), "");
+
+        assert_eq!(validate_username("ab"), "Имя пользователя должно содержать от 3 до 32 символов");
+        assert_eq!(validate_username(&"a".repeat(33)), "Имя пользователя должно содержать от 3 до 32 символов");
+        assert_eq!(validate_username("1ivan"), "Имя пользователя должно начинаться с латинской буквы");
+        assert_eq!(validate_username("_ivan"), "Имя пользователя должно начинаться с латинской буквы");
+        assert_eq!(validate_username("иван"), "Имя пользователя должно начинаться с латинской буквы");
+        assert_eq!(validate_username("ivan-p"), "Допустимы только латинские буквы, цифры и _");
+        assert_eq!(validate_username("ivan__p"), "Имя пользователя не может содержать два _ подряд");
+        assert_eq!(validate_username("admin"), "Это имя пользователя зарезервировано");
+        assert_eq!(validate_username("Support"), "Это имя пользователя зарезервировано");
+        assert_eq!(validate_username("bot"), "Это имя пользователя зарезервировано");
+        assert_eq!(validate_username("admin1"), "");
+    }
 
-        // Same name should always produce same color
-        assert_eq!(get_avatar_color("Иван"), get_avatar_color("Иван"));
+    #[test]
+    fn test_normalize_username() {
+        assert_eq!(normalize_username("  Ivan_Petrov "), "ivan_petrov");
+        assert_eq!(normalize_username(""), "");
     }
 
     #[test]
-    fn test_get_initials() {
-        assert_eq!(get_initials("Иван", "Петров"), "ИП");
-        assert_eq!(get_initials("Anna", ""), "A");
-        assert_eq!(get_initials("", ""), "?");
+    fn test_password_strength() {
+        let strength = |pw: &str| -> serde_json::Value { serde_json::from_str(&password_strength(pw)).unwrap() };
+
+        let weak = strength("123456");
+        assert_eq!(weak["score"], 0);
+        assert_eq!(weak["length_ok"], false);
+        assert_eq!(weak["has_digit"], true);
+        assert!(weak["suggestions"].as_array().unwrap().contains(&serde_json::json!("Этот пароль слишком распространён")));
+
+        let strong = strength("Tr0ub4dour&3");
+        assert_eq!(strong["score"], 4);
+        assert_eq!(strong, serde_json::json!({
+            "score": 4, "length_ok": true, "has_upper": true, "has_lower": true,
+            "has_digit": true, "has_symbol": true, "suggestions": [],
+        }));
+
+        let empty = strength("");
+        assert_eq!(empty["score"], 0);
+        assert_eq!(empty["length_ok"], false);
+        assert_eq!(empty["suggestions"], serde_json::json!(["Введите пароль"]));
+
+        // Common passwords are caught regardless of case; runs cost a point
+        assert_eq!(strength("Password1")["score"], 0);
+        assert_eq!(strength("Xk9#mQ2!")["score"], 3);
+        assert_eq!(strength("Xk9#abc!")["score"], 2);
+        assert_eq!(strength("Xk9#qqq!")["score"], 2);
     }
 
     #[test]
-    fn test_escape_html() {
-        assert_eq!(escape_html("<script>alert('xss')</script>"),
-            "&lt;script&gt;alert(&#x27;xss&#x27;)&lt;/script&gt;");
-        assert_eq!(escape_html("Hello & World"), "Hello &amp; World");
-        assert_eq!(escape_html("\"quoted\""), "&quot;quoted&quot;");
-        assert_eq!(escape_html("normal text"), "normal text");
+    fn test_validate_inn() {
+        assert!(validate_inn("7707083893")); // legal entity
+        assert!(validate_inn("500100732259")); // individual
+
+        assert!(!validate_inn("7707083894")); // wrong check digit
+        assert!(!validate_inn("500100732258")); // wrong second check digit
+        assert!(!validate_inn("500100732269")); // wrong first check digit
+        assert!(!validate_inn("770708389"));
+        assert!(!validate_inn("77070838931"));
+        assert!(!validate_inn("77070a3893"));
+        assert!(!validate_inn(" 7707083893"));
+        assert!(!validate_inn(""));
     }
 
     #[test]
-    fn test_format_message_text_basic() {
-        assert_eq!(format_message_text(""), "");
-        assert_eq!(format_message_text("Hello"), "Hello");
+    fn test_build_payment_payload() {
+        assert_eq!(build_payment_payload(42, 1500.0, "rub", "Order"),
+            "amount=1500.00&currency=RUB&procurement_id=42&purpose=Order");
+        assert_eq!(build_payment_payload(42, 99.999, "RUB", "x"),
+            "amount=100.00&currency=RUB&procurement_id=42&purpose=x");
+        assert_eq!(build_payment_payload(42, 0.5, "RUB", "x"),
+            "amount=0.50&currency=RUB&procurement_id=42&purpose=x");
+
+        assert_eq!(build_payment_payload(7, 250.0, "RUB", "Взнос за чай & кофе"),
+            "amount=250.00&currency=RUB&procurement_id=7&purpose=%D0%92%D0%B7%D0%BD%D0%BE%D1%81%20%D0%B7%D0%B0%20%D1%87%D0%B0%D0%B9%20%26%20%D0%BA%D0%BE%D1%84%D0%B5");
+        assert_eq!(percent_decode("%D0%92%D0%B7%D0%BD%D0%BE%D1%81%20%26"), Some("Взнос &".to_string()));
+        assert_eq!(percent_decode("a+b%2b"), Some("a b+".to_string()));
+        assert_eq!(percent_decode("%+f"), None);
+        assert_eq!(percent_decode("%-1"), None);
+        assert_eq!(percent_decode("%2"), None);
+
+        assert_eq!(build_payment_payload(1, 0.0, "RUB", "x"), "");
+        assert_eq!(build_payment_payload(1, -10.0, "RUB", "x"), "");
+        assert_eq!(build_payment_payload(1, f64::NAN, "RUB", "x"), "");
     }
 
     #[test]
-    fn test_format_message_text_xss() {
-        let result = format_message_text("<script>alert(1)</script>");
-        assert!(!result.contains("<script>"));
-        assert!(result.contains("&lt;script&gt;"));
+    fn test_time_until_label() {
+        let now = 1_710_504_000_000.0;
+        let hour = 3_600_000.0;
+        assert_eq!(time_until_label_at(now + 3.0 * hour, now), "3 часа");
+        assert_eq!(time_until_label_at(now + 10.0 * 60_000.0, now), "1 час");
+        assert_eq!(time_until_label_at(now + 11.0 * hour, now), "11 часов");
+        assert_eq!(time_until_label_at(now + 20.5 * hour, now), "21 час");
+        assert_eq!(time_until_label_at(now + 23.5 * hour, now), "24 часа");
+        assert_eq!(time_until_label_at(now + 48.0 * hour, now), "2 дня");
+        assert_eq!(time_until_label_at(now + 60.0 * hour, now), "2 дня");
+        assert_eq!(time_until_label_at(now + 5.0 * 24.0 * hour, now), "5 дней");
+        assert_eq!(time_until_label_at(now + 21.0 * 24.0 * hour, now), "21 день");
+        assert_eq!(time_until_label_at(now - hour, now), "Завершено");
+        assert_eq!(time_until_label_at(now, now), "Завершено");
+        assert_eq!(time_until_label_at(f64::NAN, now), "Завершено");
     }
 
     #[test]
-    fn test_format_message_text_urls() {
-        let result = format_message_text("Visit https://example.com please");
-        assert!(result.contains("<a href=\"https://example.com\""));
-        assert!(result.contains("target=\"_blank\""));
+    fn test_deadline_phrase() {
+        let now = 1_710_504_000_000.0;
+        let day = 86_400_000.0;
+        let phrase = |deadline: f64| -> serde_json::Value {
+            serde_json::from_str(&deadline_phrase_at(deadline, now)).unwrap()
+        };
+
+        let one = phrase(now + day + 3_600_000.0);
+        assert_eq!(one["label"], "остался 1 день");
+        assert_eq!(one["days"], 1);
+        assert_eq!(one["urgency"], "soon");
+        assert_eq!(one["color"], "#e53935");
+
+        let two = phrase(now + 2.0 * day);
+        assert_eq!(two["label"], "осталось 2 дня");
+        assert_eq!(two["days"], 2);
+        assert_eq!(two["urgency"], "soon");
+
+        let five = phrase(now + 5.0 * day);
+        assert_eq!(five["label"], "осталось 5 дней");
+        assert_eq!(five["days"], 5);
+        assert_eq!(five["urgency"], "week");
+        assert_eq!(five["color"], "#fb8c00");
+
+        assert_eq!(phrase(now + 30.0 * day)["color"], "#43a047");
+
+        let overdue = phrase(now - 2.0 * day);
+        assert_eq!(overdue["label"], "Завершено");
+        assert_eq!(overdue["days"], -2);
+        assert_eq!(overdue["urgency"], "overdue");
+        assert_eq!(overdue["color"], "#9e9e9e");
+
+        let today = phrase(now + 5.0 * 3_600_000.0);
+        assert_eq!(today["label"], "осталось менее дня");
+        assert_eq!(today["days"], 0);
+        assert_eq!(today["urgency"], "soon");
+
+        for missing in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let invalid = phrase(missing);
+            assert_eq!(invalid["label"], "Без срока");
+            assert!(invalid["days"].is_null());
+            assert_eq!(invalid["urgency"], "none");
+        }
     }
 
     #[test]
-    fn test_format_message_text_newlines() {
-        let result = format_message_text("Line 1\nLine 2");
-        assert!(result.contains("<br>"));
+    fn test_procurement_health() {
+        let day = 86_400_000.0;
+        let created = 0.0;
+        let deadline = 10.0 * day;
+        let now = 5.0 * day;
+
+        // On track: half funded at half time, a participant a day
+        let on_track = procurement_health_at(5000.0, 10000.0, deadline, 5, created, now);
+        assert_eq!(on_track, 87.5);
+        // Ahead of pace
+        let ahead = procurement_health_at(8000.0, 10000.0, deadline, 5, created, now);
+        assert_eq!(ahead, 95.0);
+        assert!(ahead > on_track);
+        // Stalled close to the deadline
+        let stalled = procurement_health_at(1000.0, 10000.0, deadline, 1, created, 9.0 * day);
+        assert!(stalled < 10.0, "stalled scored {}", stalled);
+
+        assert_eq!(procurement_health_at(10000.0, 10000.0, deadline, 0, created, now), 100.0);
+        assert_eq!(procurement_health_at(0.0, 0.0, deadline, 0, created, 20.0 * day), 0.0);
+        let no_dates = procurement_health_at(0.0, 10000.0, f64::NAN, 0, f64::NAN, now);
+        assert_eq!(no_dates, 60.0);
     }
 
-    // ── Search and sorting tests ──
+    #[test]
+    fn test_merge_procurements() {
+        let base = serde_json::json!([
+            {"id": 1, "title": "A", "current_amount": 100.0},
+            {"id": 2, "title": "B", "current_amount": 200.0},
+            {"id": 3, "title": "C", "current_amount": 300.0},
+        ]).to_string();
+
+        // Replace in place
+        let patch = serde_json::json!([{"id": 2, "title": "B", "current_amount": 250.0, "extra": true}]).to_string();
+        let merged: serde_json::Value = serde_json::from_str(&merge_procurements(&base, &patch)).unwrap();
+        assert_eq!(merged, serde_json::json!([
+            {"id": 1, "title": "A", "current_amount": 100.0},
+            {"id": 2, "title": "B", "current_amount": 250.0, "extra": true},
+            {"id": 3, "title": "C", "current_amount": 300.0},
+        ]));
+
+        // Append new ids after the base, in patch order
+        let patch = serde_json::json!([
+            {"id": 5, "title": "E"},
+            {"id": 1, "title": "A2"},
+            {"id": 4, "title": "D"},
+            {"title": "no id"},
+        ]).to_string();
+        let merged: Vec<serde_json::Value> = serde_json::from_str(&merge_procurements(&base, &patch)).unwrap();
+        let ids: Vec<i64> = merged.iter().map(|p| p["id"].as_i64().unwrap()).collect();
+        assert_eq!(ids, vec![1, 2, 3, 5, 4]);
+        assert_eq!(merged[0]["title"], "A2");
+
+        assert_eq!(merge_procurements(&base, "[]"), serde_json::to_string(&serde_json::from_str::<serde_json::Value>(&base).unwrap()).unwrap());
+        assert_eq!(merge_procurements("oops", "[]"), "[]");
+    }
 
     #[test]
-    fn test_search_procurements() {
+    fn test_related_procurements() {
         let json = serde_json::json!([
-            {"id": 1, "title": "Мед натуральный", "description": "Свежий мед", "city": "Москва"},
-            {"id": 2, "title": "Масло оливковое", "description": "Из Греции", "city": "Санкт-Петербург"},
-            {"id": 3, "title": "Чай зеленый", "description": "Японский чай", "city": "Москва"},
+            {"id": 1, "title": "Ноутбуки Lenovo оптом", "city": "Москва", "category": "Электроника"},
+            {"id": 2, "title": "Мёд алтайский", "city": "Москва", "category": "Продукты"},
+            {"id": 3, "title": "Наушники", "city": "Казань", "category": "электроника"},
+            {"id": 4, "title": "Ноутбуки ASUS", "city": "Москва", "category": "Электроника"},
+            {"id": 5, "title": "Кофе", "city": "Омск", "category": "Продукты"},
         ]).to_string();
 
-        let result = search_procurements(&json, "Москва");
-        let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
-        assert_eq!(parsed.len(), 2); // two procurements in Moscow
+        let results: Vec<serde_json::Value> = serde_json::from_str(&related_procurements(&json, 1, 0)).unwrap();
+        let ids: Vec<i64> = results.iter().map(|r| r["id"].as_i64().unwrap()).collect();
+        // Same category (with title overlap, then without) before same city only; unrelated dropped
+        assert_eq!(ids, vec![4, 3, 2]);
+        assert!(results[1]["score"].as_f64().unwrap() > results[2]["score"].as_f64().unwrap());
 
-        let result_empty = search_procurements(&json, "");
-        assert_eq!(result_empty, "[]");
+        let results: Vec<serde_json::Value> = serde_json::from_str(&related_procurements(&json, 1, 1)).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(related_procurements(&json, 99, 5), "[]");
+        assert_eq!(related_procurements("oops", 1, 5), "[]");
+    }
 
-        let result_none = search_procurements(&json, "Несуществующий");
-        let parsed_none: Vec<serde_json::Value> = serde_json::from_str(&result_none).unwrap();
-        assert_eq!(parsed_none.len(), 0);
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("sitting", "kitten"), 3);
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("abc", ""), 3);
+        // Cyrillic is compared per character, not per UTF-8 byte
+        assert_eq!(levenshtein("привет", "пирвет"), 2);
+        assert_eq!(levenshtein("ёж", "еж"), 1);
+
+        // Large input sanity check: 2000 x 2000 chars stays well under a second
+        let a = "абвгд".repeat(400);
+        let b = "абвге".repeat(400);
+        let start = std::time::Instant::now();
+        assert_eq!(levenshtein(&a, &b), 400);
+        assert!(start.elapsed().as_secs_f64() < 1.0);
     }
 
     #[test]
@@ -869,12 +6058,12 @@ mod tests {
         ]).to_string();
 
         // Sort by title ascending
-        let sorted = sort_procurements(&json, "title", "asc");
+        let sorted = sort_procurements_at(&json, "title", "asc", "", 0, 0, 0.0);
         let ids: Vec<i64> = serde_json::from_str(&sorted).unwrap();
         assert_eq!(ids, vec![2, 1, 3]); // Апельсин, Банан, Вишня
 
         // Sort by amount descending
-        let sorted_desc = sort_procurements(&json, "amount", "desc");
+        let sorted_desc = sort_procurements_at(&json, "amount", "desc", "", 0, 0, 0.0);
         let ids_desc: Vec<i64> = serde_json::from_str(&sorted_desc).unwrap();
         assert_eq!(ids_desc, vec![1, 3, 2]); // 300, 200, 100
     }
@@ -897,6 +6086,182 @@ mod tests {
         assert_eq!(stats["cities"].as_array().unwrap().len(), 2); // Москва, СПб
     }
 
+    #[test]
+    fn test_filter_procurements() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "A", "status": "active", "city": "Москва", "current_amount": 500.0, "target_amount": 1000.0},
+            {"id": 2, "title": "B", "status": "active", "city": "Казань", "current_amount": 1500.0, "target_amount": 2000.0},
+            {"id": 3, "title": "C", "status": "completed", "city": "Москва", "current_amount": 2000.0, "target_amount": 2000.0},
+            {"id": 4, "title": "D", "status": "active", "city": "москва", "current_amount": 100.0, "target_amount": 1000.0},
+            {"id": 5, "title": "E"},
+        ]).to_string();
+
+        // Amount range only
+        let ids: Vec<i64> = serde_json::from_str(&filter_procurements(&json, r#"{"min_amount": 500, "max_amount": 1500}"#)).unwrap();
+        assert_eq!(ids, vec![1, 2]);
+
+        // Status and city combined
+        let ids: Vec<i64> = serde_json::from_str(&filter_procurements(&json, r#"{"status": "active", "city": "Москва"}"#)).unwrap();
+        assert_eq!(ids, vec![1, 4]);
+
+        // Progress range
+        let ids: Vec<i64> = serde_json::from_str(&filter_procurements(&json, r#"{"min_progress": 50, "max_progress": 99}"#)).unwrap();
+        assert_eq!(ids, vec![1, 2]);
+
+        // No filters keeps everything, invalid filters give an empty result
+        let ids: Vec<i64> = serde_json::from_str(&filter_procurements(&json, "{}")).unwrap();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+        assert_eq!(filter_procurements(&json, "not json"), "[]");
+    }
+
+    #[test]
+    fn test_aggregate_procurement_stats_funding() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Funded", "status": "completed", "current_amount": 1000.0, "target_amount": 1000.0},
+            {"id": 2, "title": "Overfunded", "status": "active", "current_amount": 1500.0, "target_amount": 1000.0},
+            {"id": 3, "title": "Underfunded", "status": "completed", "current_amount": 999.0, "target_amount": 1000.0},
+            {"id": 4, "title": "Zero target", "status": "active", "current_amount": 500.0, "target_amount": 0.0},
+            {"id": 5, "title": "No amounts", "status": "draft"},
+            {"id": 6, "title": "Underfunded", "status": "active", "current_amount": 10.0, "target_amount": 1000.0},
+        ]).to_string();
+
+        let stats: serde_json::Value = serde_json::from_str(&aggregate_procurement_stats(&json)).unwrap();
+        assert_eq!(stats["completed_count"], 2);
+        assert_eq!(stats["funded_count"], 2);
+        assert_eq!(stats["funding_rate"], 33); // 2 of 6
+        // Only the 150%-funded item counts as over-funded; zero targets are ignored
+        assert_eq!(stats["overfunded_count"], 1);
+        assert_eq!(stats["overfunded_total"], 500.0);
+
+        let empty: serde_json::Value = serde_json::from_str(&aggregate_procurement_stats("[]")).unwrap();
+        assert_eq!(empty["funded_count"], 0);
+        assert_eq!(empty["funding_rate"], 0);
+        assert_eq!(empty["overfunded_count"], 0);
+        assert_eq!(empty["overfunded_total"], 0.0);
+    }
+
+    #[test]
+    fn test_canonicalize_category() {
+        assert_eq!(canonicalize_category("Электроника"), "Электроника");
+        assert_eq!(canonicalize_category("  электроника "), "Электроника");
+        assert_eq!(canonicalize_category("Electronics"), "Электроника");
+        assert_eq!(canonicalize_category("elektronika"), "Электроника");
+        assert_eq!(canonicalize_category("МЕД"), "Мёд и пчеловодство");
+        assert_eq!(canonicalize_category("для  дома"), "Товары для дома");
+
+        assert_eq!(canonicalize_category("  ручная   РАБОТА "), "Ручная Работа");
+        assert_eq!(canonicalize_category("книги"), "Книги");
+        // Transliterated aliases only apply without an exact hit
+        assert_eq!(canonicalize_category("sad"), "Сад и огород");
+        assert_eq!(canonicalize_category("home"), "Товары для дома");
+        assert!(CATEGORY_LOOKUP.get("sad").is_none());
+        assert_eq!(canonicalize_category("   "), "");
+
+        let json = serde_json::json!([
+            {"id": 1, "title": "A", "category": "Электроника", "current_amount": 100.0},
+            {"id": 2, "title": "B", "category": "electronics", "current_amount": 200.0},
+            {"id": 3, "title": "C", "category": "Elektronika", "current_amount": 300.0},
+        ]).to_string();
+        let stats: serde_json::Value = serde_json::from_str(&aggregate_procurement_stats(&json)).unwrap();
+        assert_eq!(stats["by_category"], serde_json::json!({"Электроника": {"count": 3, "total_amount": 600.0}}));
+    }
+
+    #[test]
+    fn test_procurement_fingerprint() {
+        let base = procurement_fingerprint("Мёд алтайский", "Москва", "Анна Петрова", 10000.0);
+        assert_eq!(base.len(), 64);
+        assert!(base.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+
+        assert_eq!(procurement_fingerprint("  МЁД   Алтайский ", "москва", "анна\tпетрова", 10000.0), base);
+        assert_eq!(procurement_fingerprint("Мёд алтайский", "Москва", "Анна Петрова", 10000.001), base);
+
+        assert_ne!(procurement_fingerprint("Мёд алтайский", "Москва", "Анна Петрова", 10001.0), base);
+        assert_ne!(procurement_fingerprint("Мёд алтайский", "Казань", "Анна Петрова", 10000.0), base);
+        assert_ne!(procurement_fingerprint("Мёдалтайский", "Москва", "Анна Петрова", 10000.0), base);
+        assert_ne!(procurement_fingerprint("ab", "c", "", 0.0), procurement_fingerprint("a", "bc", "", 0.0));
+    }
+
+    #[test]
+    fn test_find_duplicate_procurements() {
+        let json = serde_json::json!([
+            {"id": 5, "title": "Мёд алтайский", "city": "Москва", "organizer_name": "Анна", "target_amount": 10000.0},
+            {"id": 2, "title": "Чай", "city": "Казань", "target_amount": 5000.0},
+            {"id": 3, "title": "Кофе", "city": "Москва", "target_amount": 8000.0},
+            {"id": 1, "title": "мёд  АЛТАЙСКИЙ", "city": "москва", "organizer_name": "анна", "target_amount": 10000.0},
+            {"id": 4, "title": "Чай", "city": "Казань", "target_amount": 5000.0},
+            {"id": 6, "title": "Кофе", "city": "Москва", "target_amount": 9000.0},
+        ]).to_string();
+
+        assert_eq!(find_duplicate_procurements(&json), "[[1,5],[2,4]]");
+
+        let unique = serde_json::json!([{"id": 1, "title": "A"}, {"id": 2, "title": "B"}]).to_string();
+        assert_eq!(find_duplicate_procurements(&unique), "[]");
+        assert_eq!(find_duplicate_procurements("bad"), "[]");
+    }
+
+    #[test]
+    fn test_aggregate_procurement_stats_breakdowns() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "A", "city": "Москва", "category": "Продукты", "current_amount": 500.0},
+            {"id": 2, "title": "B", "city": "Москва", "category": "Электроника", "current_amount": 1500.0},
+            {"id": 3, "title": "C", "city": "Казань", "category": "Продукты", "current_amount": 200.0},
+            {"id": 4, "title": "D", "city": "Пермь", "current_amount": 100.0},
+            {"id": 5, "title": "E", "city": "", "category": "Продукты"},
+        ]).to_string();
+
+        let stats: serde_json::Value = serde_json::from_str(&aggregate_procurement_stats(&json)).unwrap();
+        let by_city = &stats["by_city"];
+        assert_eq!(by_city["Москва"], serde_json::json!({"count": 2, "total_amount": 2000.0}));
+        assert_eq!(by_city["Казань"], serde_json::json!({"count": 1, "total_amount": 200.0}));
+        assert_eq!(by_city["Пермь"], serde_json::json!({"count": 1, "total_amount": 100.0}));
+        assert_eq!(by_city["unknown"], serde_json::json!({"count": 1, "total_amount": 0.0}));
+
+        let by_category = &stats["by_category"];
+        assert_eq!(by_category["Продукты питания"], serde_json::json!({"count": 3, "total_amount": 700.0}));
+        assert_eq!(by_category["Электроника"], serde_json::json!({"count": 1, "total_amount": 1500.0}));
+        assert_eq!(by_category["unknown"], serde_json::json!({"count": 1, "total_amount": 100.0}));
+
+        // Existing cities list is unchanged
+        assert_eq!(stats["cities"], serde_json::json!(["Москва", "Казань", "Пермь"]));
+    }
+
+    #[test]
+    fn test_aggregate_procurement_stats_distribution() {
+        // Odd count
+        let odd = serde_json::json!([
+            {"id": 1, "title": "A", "target_amount": 300.0, "participant_count": 1},
+            {"id": 2, "title": "B", "target_amount": 100.0, "participant_count": 9},
+            {"id": 3, "title": "C", "target_amount": 100000.0, "participant_count": 4},
+        ]).to_string();
+        let stats: serde_json::Value = serde_json::from_str(&aggregate_procurement_stats(&odd)).unwrap();
+        assert_eq!(stats["median_target"], 300.0);
+        assert_eq!(stats["median_participants"], 4.0);
+
+        // Even count: mean of the two middle values
+        let even = serde_json::json!([
+            {"id": 1, "title": "A", "target_amount": 100.0, "participant_count": 2},
+            {"id": 2, "title": "B", "target_amount": 400.0, "participant_count": 3},
+            {"id": 3, "title": "C", "target_amount": 200.0, "participant_count": 5},
+            {"id": 4, "title": "D", "target_amount": 1000.0, "participant_count": 10},
+        ]).to_string();
+        let stats: serde_json::Value = serde_json::from_str(&aggregate_procurement_stats(&even)).unwrap();
+        assert_eq!(stats["median_target"], 300.0);
+        assert_eq!(stats["median_participants"], 4.0);
+
+        // p90 of 10, 20, ..., 100 is 90
+        let items: Vec<serde_json::Value> = (1..=10)
+            .map(|i| serde_json::json!({"id": i, "title": "X", "target_amount": i as f64 * 10.0}))
+            .collect();
+        let stats: serde_json::Value = serde_json::from_str(&aggregate_procurement_stats(&serde_json::json!(items).to_string())).unwrap();
+        assert_eq!(stats["p90_target"], 90.0);
+        assert_eq!(stats["median_target"], 55.0);
+
+        let empty: serde_json::Value = serde_json::from_str(&aggregate_procurement_stats("[]")).unwrap();
+        assert_eq!(empty["median_target"], 0.0);
+        assert_eq!(empty["median_participants"], 0.0);
+        assert_eq!(empty["p90_target"], 0.0);
+    }
+
     #[test]
     fn test_search_messages() {
         let json = serde_json::json!([
@@ -918,8 +6283,8 @@ mod tests {
 
     #[test]
     fn test_empty_json_input() {
-        assert_eq!(search_procurements("[]", "test"), "[]");
-        assert_eq!(sort_procurements("[]", "title", "asc"), "[]");
+        assert_eq!(search_procurements("[]", "test", 0, 0, 0, false, 0), "[]");
+        assert_eq!(sort_procurements_at("[]", "title", "asc", "", 0, 0, 0.0), "[]");
         // Empty array returns valid stats object with zero values
         let stats: serde_json::Value = serde_json::from_str(&aggregate_procurement_stats("[]")).unwrap();
         assert_eq!(stats["total_count"], 0);
@@ -929,11 +6294,519 @@ mod tests {
 
     #[test]
     fn test_invalid_json_input() {
-        assert_eq!(search_procurements("not json", "test"), "[]");
-        assert_eq!(sort_procurements("{bad}", "title", "asc"), "[]");
+        assert_eq!(search_procurements("not json", "test", 0, 0, 0, false, 0), "[]");
+        assert_eq!(sort_procurements_at("{bad}", "title", "asc", "", 0, 0, 0.0), "[]");
         assert_eq!(aggregate_procurement_stats("invalid"), "{}");
     }
 
+    #[test]
+    fn test_batch_process_messages_sender_runs() {
+        let json = serde_json::json!([
+            {"id": 1, "text": "Привет", "user": {"id": 10, "first_name": "Иван"}},
+            {"id": 2, "text": "Как дела?", "user": {"id": 10, "first_name": "Иван"}},
+            {"id": 3, "text": "Иван присоединился", "message_type": "system"},
+            {"id": 4, "text": "Есть кто?", "user": {"id": 10, "first_name": "Иван"}},
+            {"id": 5, "text": "Да", "user": {"id": 20, "first_name": "Мария"}},
+            {"id": 6, "text": "Я тут", "user": {"id": 1, "first_name": "Я"}},
+            {"id": 7, "text": "И я", "user": {"id": 1, "first_name": "Я"}},
+        ]).to_string();
+
+        let result = batch_process_messages_at(&json, 1, 0, 1_700_000_000_000.0);
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
+        let show: Vec<bool> = parsed.iter().map(|m| m["show_sender"].as_bool().unwrap()).collect();
+        assert_eq!(show, vec![true, false, false, true, true, true, false]);
+
+        let names: Vec<&str> = parsed.iter().map(|m| m["sender_name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["Иван", "", "", "Иван", "Мария", "", ""]);
+    }
+
+    #[test]
+    fn test_batch_process_messages_tz_offset() {
+        // Message at 23:30 UTC, "now" is 00:15 UTC the next day
+        let json = serde_json::json!([
+            {"id": 1, "text": "Поздно", "created_at": "2026-03-10T23:30:00Z", "user": {"id": 2, "first_name": "Иван"}},
+        ]).to_string();
+        let now = parse_iso_ms("2026-03-11T00:15:00Z").unwrap();
+
+        let utc: Vec<serde_json::Value> = serde_json::from_str(&batch_process_messages_at(&json, 1, 0, now)).unwrap();
+        assert_eq!(utc[0]["date_divider"], "Вчера");
+        assert_eq!(utc[0]["formatted_time"], "23:30");
+
+        let moscow: Vec<serde_json::Value> = serde_json::from_str(&batch_process_messages_at(&json, 1, 180, now)).unwrap();
+        assert_eq!(moscow[0]["date_divider"], "Сегодня");
+        assert_eq!(moscow[0]["formatted_time"], "02:30");
+
+        let new_york: Vec<serde_json::Value> = serde_json::from_str(&batch_process_messages_at(&json, 1, -300, now)).unwrap();
+        assert_eq!(new_york[0]["date_divider"], "Сегодня");
+        assert_eq!(new_york[0]["formatted_time"], "18:30");
+    }
+
+    #[test]
+    fn test_batch_process_messages_date_divider_shows_sender() {
+        let json = serde_json::json!([
+            {"id": 1, "text": "a", "created_at": "2026-01-05T10:00:00Z", "user": {"id": 2, "first_name": "Иван"}},
+            {"id": 2, "text": "b", "created_at": "2026-01-05T10:01:00Z", "user": {"id": 2, "first_name": "Иван"}},
+            {"id": 3, "text": "c", "created_at": "2026-01-06T09:00:00Z", "user": {"id": 2, "first_name": "Иван"}},
+        ]).to_string();
+        let now = parse_iso_ms("2026-03-11T12:00:00Z").unwrap();
+
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&batch_process_messages_at(&json, 1, 0, now)).unwrap();
+        assert_eq!(parsed[0]["date_divider"], "5 января");
+        assert_eq!(parsed[2]["date_divider"], "6 января");
+        let show: Vec<bool> = parsed.iter().map(|m| m["show_sender"].as_bool().unwrap()).collect();
+        assert_eq!(show, vec![true, false, true]);
+    }
+
+    #[test]
+    fn test_messages_by_date() {
+        let json = serde_json::json!([
+            {"id": 1, "text": "a", "created_at": "2026-03-11T09:00:00Z"},
+            {"id": 2, "text": "b", "created_at": "2026-01-05T10:00:00Z"},
+            {"id": 3, "text": "c", "created_at": "2026-03-10T12:00:00Z"},
+            {"id": 4, "text": "d"},
+            {"id": 5, "text": "e", "created_at": "2026-03-11T11:00:00Z"},
+            {"id": 6, "text": "f", "created_at": "2026-01-05T22:00:00Z"},
+        ]).to_string();
+        let now = parse_iso_ms("2026-03-11T12:00:00Z").unwrap();
+
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&messages_by_date_at(&json, 0, now)).unwrap();
+        assert_eq!(parsed, vec![
+            serde_json::json!({"date": "2026-01-05", "label": "5 января", "count": 2}),
+            serde_json::json!({"date": "2026-03-10", "label": "Вчера", "count": 1}),
+            serde_json::json!({"date": "2026-03-11", "label": "Сегодня", "count": 2}),
+            serde_json::json!({"date": null, "label": null, "count": 1}),
+        ]);
+
+        // 2026-01-05T22:00Z is already 01:00 on the 6th in Moscow
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&messages_by_date_at(&json, 180, now)).unwrap();
+        assert_eq!(parsed[0]["date"], "2026-01-05");
+        assert_eq!(parsed[1]["date"], "2026-01-06");
+        assert_eq!(parsed[1]["label"], "6 января");
+
+        assert_eq!(messages_by_date_at("not json", 0, now), "[]");
+        assert_eq!(messages_by_date_at("[]", 0, now), "[]");
+    }
+
+    #[test]
+    fn test_search_messages_by_user() {
+        let json = serde_json::json!([
+            {"id": 1, "text": "Когда доставка?", "user": {"id": 10}},
+            {"id": 2, "text": "Завтра", "user": {"id": 20}},
+            {"id": 3, "text": "Иван присоединился", "message_type": "system"},
+            {"id": 4, "text": "Спасибо за ДОСТАВКУ", "user": {"id": 10}},
+            {"id": 5, "text": "Ок", "user": {"id": 10}},
+        ]).to_string();
+
+        let indices = |result: String| -> Vec<u64> {
+            let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
+            parsed.iter().map(|m| m["index"].as_u64().unwrap()).collect()
+        };
+
+        assert_eq!(indices(search_messages_by_user(&json, 10, "")), vec![0, 3, 4]);
+        assert_eq!(indices(search_messages_by_user(&json, 10, "доставк")), vec![0, 3]);
+        assert_eq!(indices(search_messages_by_user(&json, 20, "доставк")), Vec::<u64>::new());
+        assert_eq!(indices(search_messages_by_user(&json, 99, "")), Vec::<u64>::new());
+        assert_eq!(search_messages_by_user("bad", 10, ""), "[]");
+    }
+
+    #[test]
+    fn test_search_messages_in_range() {
+        let json = serde_json::json!([
+            {"id": 1, "text": "Оплатил", "created_at": "2026-03-10T08:00:00Z"},
+            {"id": 2, "text": "Оплата прошла?", "created_at": "2026-03-10T12:00:00Z"},
+            {"id": 3, "text": "Без даты"},
+            {"id": 4, "text": "Оплата есть", "created_at": "2026-03-10T15:00:00Z"},
+            {"id": 5, "text": "Оплата", "created_at": "2026-03-11T09:00:00Z"},
+            {"id": 6, "text": "Оплата", "created_at": "вчера"},
+        ]).to_string();
+        let start = parse_iso_ms("2026-03-10T12:00:00Z").unwrap();
+        let end = parse_iso_ms("2026-03-10T23:59:59Z").unwrap();
+
+        let indices = |result: String| -> Vec<u64> {
+            let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
+            parsed.iter().map(|m| m["index"].as_u64().unwrap()).collect()
+        };
+
+        assert_eq!(indices(search_messages_in_range(&json, start, end, "")), vec![1, 3]);
+        assert_eq!(indices(search_messages_in_range(&json, start, end, "прошла")), vec![1]);
+        assert_eq!(indices(search_messages_in_range(&json, f64::NEG_INFINITY, f64::INFINITY, "оплат")), vec![0, 1, 3, 4]);
+        assert_eq!(search_messages_in_range(&json, end, start, ""), "[]");
+        assert_eq!(search_messages_in_range(&json, f64::NAN, end, ""), "[]");
+    }
+
+    #[test]
+    fn test_validate_procurement_json() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Мёд", "target_amount": 10000, "current_amount": 2500.5, "city": null, "extra": true},
+            {"id": 2, "title": "Чай", "target_amount": "10000"},
+            {"title": "Без id", "participant_count": 3},
+            {"id": 4.5, "title": 7, "participant_count": 3_000_000_000u64},
+            "not an object",
+            {"id": 6, "title": "Кофе", "deadline": 20260401, "current_amount": null},
+        ]).to_string();
+
+        let errors: serde_json::Value = serde_json::from_str(&validate_procurement_json(&json)).unwrap();
+        assert_eq!(errors, serde_json::json!([
+            {"index": 1, "field": "target_amount", "error": "expected number, got string"},
+            {"index": 2, "field": "id", "error": "required field is missing"},
+            {"index": 3, "field": "id", "error": "expected integer, got number"},
+            {"index": 3, "field": "title", "error": "expected string, got integer"},
+            {"index": 3, "field": "participant_count", "error": "integer out of range"},
+            {"index": 4, "field": null, "error": "expected an object, got string"},
+            {"index": 5, "field": "deadline", "error": "expected string, got integer"},
+        ]));
+
+        // Inputs without reported errors deserialize for the batch functions
+        let valid = serde_json::json!([{"id": 1, "title": "Мёд", "target_amount": 10000}]).to_string();
+        assert_eq!(validate_procurement_json(&valid), "[]");
+        assert!(serde_json::from_str::<Vec<Procurement>>(&valid).is_ok());
+
+        let not_array: serde_json::Value = serde_json::from_str(&validate_procurement_json(r#"{"id": 1}"#)).unwrap();
+        assert_eq!(not_array[0]["error"], "expected an array of procurements, got object");
+        let malformed: serde_json::Value = serde_json::from_str(&validate_procurement_json("[{")).unwrap();
+        assert!(malformed[0]["index"].is_null());
+        assert!(!malformed[0]["error"].as_str().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_batch_process_procurements_chunk() {
+        let items: Vec<serde_json::Value> = (1..=7).map(|i| serde_json::json!({
+            "id": i,
+            "title": format!("Закупка {}", i),
+            "current_amount": i as f64 * 1000.0,
+            "target_amount": 10000.0,
+            "deadline": "2026-04-01T00:00:00Z",
+        })).collect();
+        let json = serde_json::Value::Array(items).to_string();
+        let now = parse_iso_ms("2026-03-11T12:00:00Z").unwrap();
+
+        let full: Vec<serde_json::Value> = serde_json::from_str(&batch_process_procurements_at(&json, now)).unwrap();
+        let mut chunked = Vec::new();
+        for offset in (0..7).step_by(3) {
+            let chunk: Vec<serde_json::Value> =
+                serde_json::from_str(&batch_process_procurements_chunk_at(&json, offset, 3, now)).unwrap();
+            assert!(chunk.len() <= 3);
+            chunked.extend(chunk);
+        }
+        assert_eq!(chunked, full);
+
+        let tail: Vec<serde_json::Value> =
+            serde_json::from_str(&batch_process_procurements_chunk_at(&json, 5, 0, now)).unwrap();
+        assert_eq!(tail, full[5..].to_vec());
+
+        assert_eq!(batch_process_procurements_chunk_at(&json, 7, 3, now), "[]");
+        assert_eq!(batch_process_procurements_chunk_at(&json, 100, 3, now), "[]");
+        assert_eq!(batch_process_procurements_chunk_at("bad", 0, 3, now), "[]");
+    }
+
+    #[test]
+    fn test_batch_process_messages_regression() {
+        // Pins the full output so the single-parse date handling stays byte-for-byte stable
+        let json = serde_json::json!([
+            {"id": 1, "text": "Старт", "created_at": "2025-12-31T21:30:00Z", "user": {"id": 2, "first_name": "Иван"}},
+            {"id": 2, "text": "Иван вошёл", "created_at": "2026-03-10T08:05:00Z", "message_type": "system"},
+            {"id": 3, "text": "@anna см. #12", "created_at": "2026-03-10T08:06:00Z", "user": {"id": 2, "first_name": "Иван"}},
+            {"id": 4, "text": "👍", "created_at": "2026-03-11T07:00:00Z", "user": {"id": 1, "first_name": "Я"}},
+            {"id": 5, "text": "без даты", "user": {"id": 1, "first_name": "Я"}},
+        ]).to_string();
+        let now = parse_iso_ms("2026-03-11T12:00:00Z").unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_str(&batch_process_messages_at(&json, 1, 180, now)).unwrap();
+        let expected = serde_json::json!([
+            {"id": 1, "text": "Старт", "formatted_text": "Старт", "is_own": false, "is_system": false,
+             "formatted_time": "00:30", "sender_name": "Иван", "show_sender": true, "is_big_emoji": false,
+             "date_divider": "1 января"},
+            {"id": 2, "text": "Иван вошёл", "formatted_text": "Иван вошёл", "is_own": false, "is_system": true,
+             "formatted_time": "11:05", "sender_name": "", "show_sender": false, "is_big_emoji": false,
+             "date_divider": "Вчера"},
+            {"id": 3, "text": "@anna см. #12", "formatted_text": format_message_text("@anna см. #12"),
+             "is_own": false, "is_system": false, "formatted_time": "11:06", "sender_name": "Иван",
+             "show_sender": true, "is_big_emoji": false},
+            {"id": 4, "text": "👍", "formatted_text": "👍", "is_own": true, "is_system": false,
+             "formatted_time": "10:00", "sender_name": "", "show_sender": true, "is_big_emoji": true,
+             "date_divider": "Сегодня"},
+            {"id": 5, "text": "без даты", "formatted_text": "без даты", "is_own": true, "is_system": false,
+             "formatted_time": "", "sender_name": "", "show_sender": false, "is_big_emoji": false},
+        ]);
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_procurement_ref_matches_owned() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Мёд \"алтайский\"\nоптом", "city": "Москва", "category": "Продукты",
+             "current_amount": 5000.0, "target_amount": 10000.0, "deadline": "2026-04-01T00:00:00Z"},
+            {"id": 2, "title": "Мед липовый", "description": "Свежий мёд", "organizer_name": "Анна",
+             "created_at": "2026-01-05T10:00:00Z"},
+            {"id": 3, "title": "Чай", "city": null, "participant_count": 4},
+        ]).to_string();
+
+        let owned: Vec<Procurement> = serde_json::from_str(&json).unwrap();
+        let borrowed: Vec<ProcurementRef> = serde_json::from_str(&json).unwrap();
+        assert!(matches!(borrowed[0].title, Cow::Owned(_)));
+        assert!(matches!(borrowed[1].title, Cow::Borrowed(_)));
+        assert!(matches!(borrowed[1].description, Some(Cow::Borrowed(_))));
+        assert!(borrowed[2].city.is_none() && borrowed[2].deadline.is_none());
+
+        let search = SearchQuery::new("мед москва", true);
+        for (o, b) in owned.iter().zip(&borrowed) {
+            assert_eq!(o.title, b.title);
+            assert_eq!(
+                procurement_match_score(&o.borrowed(), &search, 1),
+                procurement_match_score(b, &search, 1),
+            );
+        }
+
+        // Same inputs are rejected as by the owned struct
+        let bad = r#"[{"id": 1, "title": "x", "city": 5}]"#;
+        assert!(serde_json::from_str::<Vec<Procurement>>(bad).is_err());
+        assert!(serde_json::from_str::<Vec<ProcurementRef>>(bad).is_err());
+        assert_eq!(search_procurements(bad, "x", 0, 0, 0, false, 0), "[]");
+        assert_eq!(sort_procurements_at(bad, "title", "asc", "", 0, 0, 0.0), "[]");
+    }
+
+    #[test]
+    fn test_query_procurements() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "Мёд алтайский", "city": "Москва", "status": "active", "current_amount": 3000.0, "target_amount": 10000.0},
+            {"id": 2, "title": "Мёд липовый", "city": "Казань", "status": "active", "current_amount": 9000.0, "target_amount": 10000.0},
+            {"id": 3, "title": "Чай", "city": "Москва", "status": "active", "current_amount": 1000.0, "target_amount": 10000.0},
+            {"id": 4, "title": "Мед гречишный", "city": "москва", "status": "active", "current_amount": 7000.0, "target_amount": 10000.0},
+            {"id": 5, "title": "Мёд цветочный", "city": "Москва", "status": "draft", "current_amount": 5000.0, "target_amount": 10000.0},
+            {"id": 6, "title": "Мёд с орехами", "city": "Москва", "status": "active", "current_amount": 5000.0, "target_amount": 10000.0},
+        ]).to_string();
+        let now = parse_iso_ms("2026-03-11T12:00:00Z").unwrap();
+        let run = |params: serde_json::Value| -> serde_json::Value {
+            serde_json::from_str(&query_procurements_at(&json, &params.to_string(), now)).unwrap()
+        };
+
+        let result = run(serde_json::json!({
+            "query": "мед",
+            "filters": {"city": "Москва", "status": "active"},
+            "sort_by": "amount",
+            "order": "desc",
+            "offset": 0,
+            "limit": 2,
+        }));
+        assert_eq!(result, serde_json::json!({"total": 3, "ids": [4, 6]}));
+
+        let next = run(serde_json::json!({
+            "query": "мед", "filters": {"city": "Москва", "status": "active"},
+            "sort_by": "amount", "order": "desc", "offset": 2, "limit": 2,
+        }));
+        assert_eq!(next, serde_json::json!({"total": 3, "ids": [1]}));
+
+        // Matches the chained calls
+        let searched: Vec<serde_json::Value> = serde_json::from_str(&search_procurements(&json, "мед", 0, 0, 0, false, 0)).unwrap();
+        let searched_ids: Vec<i64> = searched.iter().map(|r| r["id"].as_i64().unwrap()).collect();
+        assert_eq!(run(serde_json::json!({"query": "мед"}))["ids"], serde_json::json!(searched_ids));
+        assert_eq!(run(serde_json::json!({"sort_by": "title"}))["ids"],
+            serde_json::from_str::<serde_json::Value>(&sort_procurements_at(&json, "title", "asc", "", 0, 0, now)).unwrap());
+
+        assert_eq!(run(serde_json::json!({})), serde_json::json!({"total": 6, "ids": [1, 2, 3, 4, 5, 6]}));
+        let empty = serde_json::json!({"total": 0, "ids": []}).to_string();
+        assert_eq!(query_procurements_at("bad", "{}", now), empty);
+        assert_eq!(query_procurements_at(&json, "bad", now), empty);
+    }
+
+    #[test]
+    fn test_search_procurements_cyrillic_case() {
+        let json = serde_json::json!([
+            {"id": 1, "title": "ЁЛКА НОВОГОДНЯЯ"},
+            {"id": 2, "title": "Йогурт ФЕРМЕРСКИЙ"},
+            {"id": 3, "title": "Елочные игрушки"},
+        ]).to_string();
+        let ids = |query: &str| -> Vec<i64> {
+            let parsed: Vec<serde_json::Value> =
+                serde_json::from_str(&search_procurements(&json, query, 0, 0, 0, false, 0)).unwrap();
+            parsed.iter().map(|r| r["id"].as_i64().unwrap()).collect()
+        };
+
+        assert_eq!(ids("ёлка"), vec![1]);
+        assert_eq!(ids("ЁЛКА"), vec![1]);
+        assert_eq!(ids("елка"), vec![1]);
+        assert_eq!(ids("ЁЛОЧНЫЕ"), vec![3]);
+        assert_eq!(ids("йогурт"), vec![2]);
+        assert_eq!(ids("ЙОГУРТ фермерский"), vec![2]);
+        assert!(ids("иогурт").is_empty());
+
+        let search = SearchQuery::new("  ЁЛКА  Йогурт ", true);
+        assert_eq!(search.words, vec!["елка", "йогурт"]);
+        assert_eq!(search.words_tr, vec!["elka", "yogurt"]);
+    }
+
+    #[test]
+    fn test_checked_variants() {
+        let now = parse_iso_ms("2026-03-11T12:00:00Z").unwrap();
+        let procurements = serde_json::json!([
+            {"id": 1, "title": "Мёд", "current_amount": 500.0, "target_amount": 1000.0},
+            {"id": 2, "title": "Чай", "current_amount": 100.0, "target_amount": 1000.0},
+        ]).to_string();
+        let messages = serde_json::json!([{"id": 1, "text": "Привет", "user": {"id": 2}}]).to_string();
+        let parse = |s: String| serde_json::from_str::<serde_json::Value>(&s).unwrap();
+
+        // Valid input: data matches the lenient function
+        let ok = parse(batch_process_procurements_checked_at(&procurements, now));
+        assert_eq!(ok["ok"], true);
+        assert_eq!(ok["data"], parse(batch_process_procurements_at(&procurements, now)));
+        let ok = parse(batch_process_messages_checked_at(&messages, 1, 0, now));
+        assert_eq!(ok["data"], parse(batch_process_messages_at(&messages, 1, 0, now)));
+        let ok = parse(search_procurements_checked(&procurements, "мед", 0, 0, 0, false, 0));
+        assert_eq!(ok["data"], parse(search_procurements(&procurements, "мед", 0, 0, 0, false, 0)));
+        let ok = parse(sort_procurements_checked_at(&procurements, "amount", "asc", "", 0, 1, now));
+        assert_eq!(ok["data"], serde_json::json!([2]));
+        let ok = parse(aggregate_procurement_stats_checked(&procurements));
+        assert_eq!(ok["data"], parse(aggregate_procurement_stats(&procurements)));
+
+        // Empty results stay distinguishable from malformed input
+        let empty = parse(search_procurements_checked("[]", "мед", 0, 0, 0, false, 0));
+        assert_eq!(empty, serde_json::json!({"ok": true, "data": []}));
+
+        let malformed = [
+            batch_process_procurements_checked_at("not json", now),
+            batch_process_procurements_checked_at(r#"[{"id": "x", "title": "a"}]"#, now),
+            batch_process_messages_checked_at(r#"{"id": 1}"#, 1, 0, now),
+            search_procurements_checked("[{", "мед", 0, 0, 0, false, 0),
+            sort_procurements_checked_at(r#"[{"title": "no id"}]"#, "title", "asc", "", 0, 0, now),
+            aggregate_procurement_stats_checked(""),
+        ];
+        for result in malformed {
+            let parsed = parse(result);
+            assert_eq!(parsed["ok"], false);
+            assert!(!parsed["error"].as_str().unwrap().is_empty());
+            assert!(parsed.get("data").is_none());
+        }
+    }
+
+    #[test]
+    fn test_batch_process_procurements_urgency() {
+        let now = parse_iso_ms("2026-03-11T12:00:00Z").unwrap();
+        let deadlines = [
+            Some("2026-03-11T11:59:59Z"),
+            Some("2026-03-11T12:00:00Z"),
+            Some("2026-03-14T12:00:00Z"),
+            Some("2026-03-14T12:00:01Z"),
+            Some("2026-03-18T12:00:00Z"),
+            Some("2026-03-18T12:00:01Z"),
+            None,
+            Some("когда-нибудь"),
+        ];
+        let items: Vec<serde_json::Value> = deadlines.iter().enumerate().map(|(i, d)| {
+            serde_json::json!({"id": i, "title": "x", "deadline": d})
+        }).collect();
+        let json = serde_json::Value::Array(items).to_string();
+
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&batch_process_procurements_at(&json, now)).unwrap();
+        let urgency: Vec<&str> = parsed.iter().map(|p| p["urgency"].as_str().unwrap()).collect();
+        assert_eq!(urgency, vec!["overdue", "soon", "soon", "week", "week", "ok", "none", "none"]);
+    }
+
+    #[test]
+    fn test_search_procurements_tie_order() {
+        let json = serde_json::json!([
+            {"id": 3, "title": "Мёд"},
+            {"id": 10, "title": "Мёд"},
+            {"id": 1, "title": "Мёд липовый"},
+            {"id": 7, "title": "Мёд"},
+            {"id": 5, "title": "Чай с мёдом"},
+        ]).to_string();
+        let ids = |result: String| -> Vec<i64> {
+            let parsed: Vec<serde_json::Value> = serde_json::from_str(&result).unwrap();
+            parsed.iter().map(|r| r["id"].as_i64().unwrap()).collect()
+        };
+
+        // All titles starting with "мед" score equally; "Чай с мёдом" has no prefix bonus
+        assert_eq!(ids(search_procurements(&json, "мед", 0, 0, 0, false, 0)), vec![10, 7, 3, 1, 5]);
+        assert_eq!(ids(search_procurements(&json, "мед", 0, 0, 0, false, 2)), vec![10, 7]);
+        assert_eq!(ids(search_procurements_highlighted(&json, "мед")), vec![10, 7, 3, 1, 5]);
+
+        // Input order does not matter
+        let mut items: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        items.reverse();
+        let reversed = serde_json::Value::Array(items).to_string();
+        assert_eq!(ids(search_procurements(&reversed, "мед", 0, 0, 0, false, 0)), vec![10, 7, 3, 1, 5]);
+    }
+
+    #[test]
+    fn test_search_procurements_top_n() {
+        let mut rng = XorShift32::new(42);
+        let items: Vec<serde_json::Value> = (0..300).map(|i| {
+            // Few distinct titles so many scores tie
+            let title = ["Мёд", "Мёд липовый", "Липовый чай", "Чай мёдовый"][rng.below(4) as usize];
+            let city = ["Москва", "Казань"][rng.below(2) as usize];
+            serde_json::json!({"id": i, "title": title, "city": city, "description": "мёд"})
+        }).collect();
+        let json = serde_json::Value::Array(items).to_string();
+
+        let full: Vec<serde_json::Value> =
+            serde_json::from_str(&search_procurements(&json, "мед москва", 0, 0, 0, false, 0)).unwrap();
+        for top_n in [1, 7, 50, 299, 300, 1000] {
+            let top: Vec<serde_json::Value> =
+                serde_json::from_str(&search_procurements(&json, "мед москва", 0, 0, 0, false, top_n)).unwrap();
+            let expected = &full[..full.len().min(top_n as usize)];
+            assert_eq!(top, expected, "top_n = {}", top_n);
+        }
+
+        // Pagination applies within the top-N window
+        let page: Vec<serde_json::Value> =
+            serde_json::from_str(&search_procurements(&json, "мед москва", 0, 5, 10, false, 12)).unwrap();
+        assert_eq!(page, full[5..12].to_vec());
+    }
+
+    #[test]
+    fn test_parse_iso_ms() {
+        assert_eq!(parse_iso_ms("1970-01-01"), Some(0.0));
+        assert_eq!(parse_iso_ms("1970-01-01T00:00:01Z"), Some(1000.0));
+        assert_eq!(parse_iso_ms("2026-03-11T03:15:00+03:00"), parse_iso_ms("2026-03-11T00:15:00Z"));
+        assert_eq!(parse_iso_ms("2026-03-11 00:15:00.500"), Some(parse_iso_ms("2026-03-11T00:15:00Z").unwrap() + 500.0));
+        assert_eq!(parse_iso_ms("2024-02-29"), Some(19782.0 * 86_400_000.0));
+        assert_eq!(parse_iso_ms("2023-02-29"), None);
+        assert_eq!(parse_iso_ms("not a date"), None);
+        assert_eq!(parse_iso_ms("2026-13-01"), None);
+
+        assert!(is_zoneless_date_time("2026-03-10T08:05:00"));
+        assert!(is_zoneless_date_time("2026-03-10 08:05"));
+        assert!(!is_zoneless_date_time("2026-03-10"));
+        assert!(!is_zoneless_date_time("2026-03-10T08:05:00Z"));
+        assert!(!is_zoneless_date_time("2026-03-10T08:05:00-03:00"));
+        assert!(!is_zoneless_date_time("2026-03-10T08:05:00+0300"));
+        // Outside wasm there is no JS Date, so zone-less timestamps stay UTC
+        assert_eq!(parse_timestamp_ms("2026-03-10T08:05:00"), parse_iso_ms("2026-03-10T08:05:00Z"));
+    }
+
+    #[test]
+    fn test_date_parts_from_ms() {
+        let date = DateParts::from_ms(parse_iso_ms("2026-12-31T23:59:00Z").unwrap());
+        assert_eq!(date, DateParts { year: 2026, month: 11, day: 31, hour: 23, minute: 59 });
+        let before_epoch = DateParts::from_ms(-60_000.0);
+        assert_eq!(before_epoch, DateParts { year: 1969, month: 11, day: 31, hour: 23, minute: 59 });
+    }
+
+    #[test]
+    fn test_is_big_emoji() {
+        assert!(is_big_emoji("👍"));
+        assert!(is_big_emoji("😀😀😀"));
+        assert!(is_big_emoji("  ❤️ "));
+        assert!(is_big_emoji("🇷🇺👨\u{200d}👩\u{200d}👧"));
+        assert!(is_big_emoji("👍🏽"));
+        assert!(!is_big_emoji("😀😀😀😀")); // too many
+        assert!(!is_big_emoji("hi 😀"));
+        assert!(!is_big_emoji("😀 😀")); // whitespace between emoji is text
+        assert!(!is_big_emoji("123"));
+        assert!(!is_big_emoji(""));
+
+        // Text-presentation symbols need U+FE0F to count as emoji
+        assert!(!is_big_emoji("©"));
+        assert!(!is_big_emoji("™"));
+        assert!(!is_big_emoji("↔"));
+        assert!(!is_big_emoji("❤"));
+        assert!(!is_big_emoji("🅰"));
+        assert!(is_big_emoji("©\u{fe0f}"));
+        assert!(is_big_emoji("↔\u{fe0f}"));
+        assert!(is_big_emoji("☝🏽"));
+        assert!(is_big_emoji("⚡⭐"));
+    }
+
     #[test]
     fn test_format_ru_date() {
         assert_eq!(format_ru_date(15, 0, 2026, 2026), "15 января"); // same year
@@ -0,0 +1,65 @@
+//! Tests that need a JS runtime (run with `wasm-pack test --node`)
+
+#![cfg(target_arch = "wasm32")]
+
+use groupbuy_wasm::{aggregate_procurement_stats_js, batch_process_messages, RateLimiter};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::*;
+
+fn field(obj: &JsValue, key: &str) -> JsValue {
+    js_sys::Reflect::get(obj, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn aggregate_procurement_stats_js_returns_object() {
+    let json = r#"[
+        {"id": 1, "title": "A", "status": "active", "city": "Москва", "current_amount": 500.0, "target_amount": 1000.0, "participant_count": 3},
+        {"id": 2, "title": "B", "status": "completed", "city": "Казань", "current_amount": 2000.0, "target_amount": 2000.0, "participant_count": 5}
+    ]"#;
+    let stats = aggregate_procurement_stats_js(json);
+
+    assert!(stats.is_object());
+    assert_eq!(field(&stats, "total_count").as_f64(), Some(2.0));
+    assert_eq!(field(&stats, "total_amount").as_f64(), Some(2500.0));
+    assert_eq!(field(&stats, "funded_count").as_f64(), Some(1.0));
+    assert_eq!(field(&stats, "formatted_total_amount").as_string(), Some("2 500 ₽".to_string()));
+
+    // Nested maps are plain objects, not JS Maps
+    let by_status = field(&stats, "by_status");
+    assert!(!by_status.is_instance_of::<js_sys::Map>());
+    assert_eq!(field(&by_status, "active").as_f64(), Some(1.0));
+    assert_eq!(field(&field(&field(&stats, "by_city"), "Москва"), "count").as_f64(), Some(1.0));
+}
+
+#[wasm_bindgen_test]
+fn aggregate_procurement_stats_js_invalid_input() {
+    let stats = aggregate_procurement_stats_js("not json");
+    assert!(stats.is_object());
+    assert_eq!(js_sys::Object::keys(stats.unchecked_ref()).length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn rate_limiter_burst_then_refill() {
+    let mut limiter = RateLimiter::new(3.0, 50.0);
+    for _ in 0..3 {
+        assert!(limiter.try_acquire());
+    }
+    assert!(!limiter.try_acquire());
+
+    // Wait ~60 ms of real time: 50 tokens/sec refills at least one token
+    let start = js_sys::Date::now();
+    while js_sys::Date::now() - start < 60.0 {}
+    assert!(limiter.tokens_remaining() >= 1.0);
+    assert!(limiter.try_acquire());
+}
+
+#[wasm_bindgen_test]
+fn batch_process_messages_zoneless_timestamp_is_local_time() {
+    // Like JS Date, a timestamp without a zone is browser local time, so with the
+    // browser's own offset it formats back to the wall-clock time as written
+    let local = js_sys::Date::new(&JsValue::from_str("2026-03-10T08:05:00"));
+    let tz_offset = -local.get_timezone_offset() as i32;
+    let json = r#"[{"id": 1, "text": "Привет", "created_at": "2026-03-10T08:05:00"}]"#;
+    let result: serde_json::Value = serde_json::from_str(&batch_process_messages(json, 0, tz_offset)).unwrap();
+    assert_eq!(result[0]["formatted_time"], "08:05");
+}